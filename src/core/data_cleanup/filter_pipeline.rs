@@ -0,0 +1,152 @@
+use crate::core::data_cleanup::machine_filtering::{
+    filter_applies, keeps_category, Category, MachineFilter,
+};
+use crate::models::Machine;
+use std::collections::HashMap;
+
+/// A set of filtering operations applied to the machines in a single pass.
+///
+/// Chaining `remove_machines_by_filter`, `remove_machines_by_category` and similar functions
+/// clones the whole map of machines once per stage. A `FilterPipeline` accumulates the operations
+/// instead, and `apply` evaluates all of them for each machine, cloning the retained machines only once.
+///
+/// A machine is retained only if it passes every operation in the pipeline, so the result is the same
+/// as applying each operation in sequence.
+///
+/// # Example
+///
+/// ```
+/// use mame_parser::models::{Category, FilterPipeline, Machine, MachineFilter};
+/// use std::collections::HashMap;
+///
+/// let machines: HashMap<String, Machine> = HashMap::new();
+///
+/// let filtered_machines = FilterPipeline::new()
+///     .remove_filter(MachineFilter::Device)
+///     .remove_filter(MachineFilter::Clones)
+///     .remove_category(Category::Computer)
+///     .keep_language("English")
+///     .apply(&machines);
+/// ```
+///
+#[derive(Default)]
+pub struct FilterPipeline {
+    /// The operations to apply, in the order they were added.
+    stages: Vec<FilterStage>,
+}
+
+/// An operation of a `FilterPipeline`.
+enum FilterStage {
+    /// Removes the machines matching the filter.
+    RemoveFilter(MachineFilter),
+    /// Removes the machines in the category, and the machines without category.
+    RemoveCategory(Category),
+    /// Keeps only the machines supporting the language.
+    KeepLanguage(String),
+}
+
+impl FilterPipeline {
+    /// Creates an empty `FilterPipeline`, which retains every machine.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the machines matching the given filter, as `remove_machines_by_filter` does.
+    pub fn remove_filter(mut self, filter: MachineFilter) -> Self {
+        self.stages.push(FilterStage::RemoveFilter(filter));
+        self
+    }
+
+    /// Removes the machines in the given category, as `remove_machines_by_category` does.
+    ///
+    /// Machines without a category are removed as well.
+    pub fn remove_category(mut self, category: Category) -> Self {
+        self.stages.push(FilterStage::RemoveCategory(category));
+        self
+    }
+
+    /// Keeps only the machines supporting the given language.
+    pub fn keep_language(mut self, language: impl Into<String>) -> Self {
+        self.stages.push(FilterStage::KeepLanguage(language.into()));
+        self
+    }
+
+    /// Applies all the operations of the pipeline to the machines.
+    ///
+    /// # Arguments
+    ///
+    /// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+    ///   the machine's name, and the value is a `Machine` struct containing the machine details.
+    ///
+    /// # Returns
+    ///
+    /// * `HashMap<String, Machine>` - A new `HashMap` containing the machines that pass every operation.
+    ///
+    pub fn apply(&self, machines: &HashMap<String, Machine>) -> HashMap<String, Machine> {
+        machines
+            .iter()
+            .filter(|(_, machine)| self.retains(machine))
+            .map(|(name, machine)| (name.clone(), machine.clone()))
+            .collect()
+    }
+
+    /// Determines if a machine passes every operation of the pipeline.
+    fn retains(&self, machine: &Machine) -> bool {
+        self.stages.iter().all(|stage| match stage {
+            FilterStage::RemoveFilter(filter) => !filter_applies(machine, filter),
+            FilterStage::RemoveCategory(category) => keeps_category(machine, &[category.as_str()]),
+            FilterStage::KeepLanguage(language) => machine
+                .languages
+                .iter()
+                .any(|machine_language| machine_language == language),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_pipeline_apply() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .category("Maze")
+                .add_language("English")
+                .build(),
+            Machine::builder("puckman")
+                .clone_of("pacman")
+                .category("Maze")
+                .add_language("Japanese")
+                .build(),
+            Machine::builder("galaga")
+                .category("Shooter")
+                .add_language("English")
+                .build(),
+            Machine::builder("z80")
+                .is_device(true)
+                .add_language("English")
+                .build(),
+            Machine::builder("apple2")
+                .category("Computer")
+                .add_language("English")
+                .build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let filtered_machines = FilterPipeline::new()
+            .remove_filter(MachineFilter::Device)
+            .remove_filter(MachineFilter::Clones)
+            .remove_category(Category::Computer)
+            .keep_language("English")
+            .apply(&machines);
+
+        let mut names: Vec<&String> = filtered_machines.keys().collect();
+        names.sort();
+        assert_eq!(names, ["galaga", "pacman"]);
+
+        assert_eq!(FilterPipeline::new().apply(&machines).len(), machines.len());
+    }
+}
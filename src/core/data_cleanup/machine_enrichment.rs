@@ -0,0 +1,496 @@
+use crate::{core::data_cleanup::name_normalization::extract_alternate_titles, models::Machine};
+use std::collections::HashMap;
+
+/// Attaches the titles of the software lists to the machines referencing them.
+///
+/// The main MAME data only contains the names of the software lists supported by each machine.
+/// This function fills the `titles` of each `Software` in the `software_list` of the machines with
+/// the names of the software titles read from the software list files, matched by list name.
+/// Lists without any known title are left with empty `titles`.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `software_titles` - A reference to a `HashMap` with the software titles, as returned by
+///   `read_software_list_file`, where each title stores the name of its list in `software_list`.
+///
+pub fn attach_software_titles(
+    machines: &mut HashMap<String, Machine>,
+    software_titles: &HashMap<String, Machine>,
+) {
+    let mut titles_by_list: HashMap<&str, Vec<String>> = HashMap::new();
+
+    for title in software_titles.values() {
+        for list in &title.software_list {
+            titles_by_list
+                .entry(&list.name)
+                .or_default()
+                .push(title.name.clone());
+        }
+    }
+
+    for titles in titles_by_list.values_mut() {
+        titles.sort_unstable();
+    }
+
+    for machine in machines.values_mut() {
+        for software in machine.software_list.iter_mut() {
+            software.titles = titles_by_list
+                .get(software.name.as_str())
+                .cloned()
+                .unwrap_or_default();
+        }
+    }
+}
+
+/// Attaches the resources read from a resources data file to the machines.
+///
+/// This allows to enrich machines read earlier, e.g. from a cached MAME data file, without reading
+/// every data file again. The `resources` of each machine present in `resources` are replaced with the
+/// ones read; the other machines are left unchanged, and machines not present in `machines` are ignored.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `resources` - A reference to a `HashMap` with the machines as returned by `read_resources_file`.
+///
+pub fn attach_resources(
+    machines: &mut HashMap<String, Machine>,
+    resources: &HashMap<String, Machine>,
+) {
+    attach_with(machines, resources, |machine, source| {
+        machine.resources = source.resources.clone();
+    });
+}
+
+/// Attaches the history sections read from a history data file to the machines.
+///
+/// The `history_sections` of each machine present in `history` are replaced with the ones read.
+/// See `attach_resources` for the handling of the other machines.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `history` - A reference to a `HashMap` with the machines as returned by `read_history_file`.
+///
+pub fn attach_history(machines: &mut HashMap<String, Machine>, history: &HashMap<String, Machine>) {
+    attach_with(machines, history, |machine, source| {
+        machine.history_sections = source.history_sections.clone();
+    });
+}
+
+/// Attaches the categories read from a catver data file to the machines.
+///
+/// The `category`, `subcategory`, `is_mature` and `version_added` fields read for each machine present
+/// in `catver` replace those of the machine; fields missing from the data file are left unchanged.
+/// See `attach_resources` for the handling of the other machines.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `catver` - A reference to a `HashMap` with the machines as returned by `read_catver_file`.
+///
+pub fn attach_catver(machines: &mut HashMap<String, Machine>, catver: &HashMap<String, Machine>) {
+    attach_with(machines, catver, |machine, source| {
+        if source.category.is_some() {
+            machine.category = source.category.clone();
+            machine.subcategory = source.subcategory.clone();
+            machine.is_mature = source.is_mature;
+        }
+        if source.is_mature == Some(true) {
+            machine.is_mature = Some(true);
+        }
+        if source.version_added.is_some() {
+            machine.version_added = source.version_added.clone();
+        }
+    });
+}
+
+/// Attaches the series read from a series data file to the machines.
+///
+/// The `series` of each machine present in `series` is replaced with the one read.
+/// See `attach_resources` for the handling of the other machines.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `series` - A reference to a `HashMap` with the machines as returned by `read_series_file`.
+///
+pub fn attach_series(machines: &mut HashMap<String, Machine>, series: &HashMap<String, Machine>) {
+    attach_with(machines, series, |machine, source| {
+        machine.series = source.series.clone();
+    });
+}
+
+/// Attaches the languages read from a languages data file to the machines.
+///
+/// The `languages` of each machine present in `languages` are replaced with the ones read.
+/// See `attach_resources` for the handling of the other machines.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `languages` - A reference to a `HashMap` with the machines as returned by `read_languages_file`.
+///
+pub fn attach_languages(
+    machines: &mut HashMap<String, Machine>,
+    languages: &HashMap<String, Machine>,
+) {
+    attach_with(machines, languages, |machine, source| {
+        machine.languages = source.languages.clone();
+    });
+}
+
+/// Attaches the number of players read from a nplayers data file to the machines.
+///
+/// The `players` of each machine present in `nplayers` is replaced with the one read, along with
+/// the normalized `players`, `max_players` and `players_simultaneous` of its extended data. See `attach_resources` for the handling of the other machines.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `nplayers` - A reference to a `HashMap` with the machines as returned by `read_nplayers_file`.
+///
+pub fn attach_nplayers(
+    machines: &mut HashMap<String, Machine>,
+    nplayers: &HashMap<String, Machine>,
+) {
+    attach_with(machines, nplayers, |machine, source| {
+        machine.players = source.players.clone();
+
+        let source_data = source.extended_data.clone().unwrap_or_default();
+        let extended_data = machine.extended_data.get_or_insert_with(Default::default);
+        extended_data.players = source_data.players;
+        extended_data.max_players = source_data.max_players;
+        extended_data.players_simultaneous = source_data.players_simultaneous;
+    });
+}
+
+/// Rewrites the categories of the machines according to a mapping, e.g. to collapse the catver categories into genres.
+///
+/// The mapping is looked up with the full category of each machine first, written as in the catver file
+/// (`"Shooter / Flying Vertical"`), then with its category alone (`"Shooter"`). The `category` of the machines
+/// found in the mapping is replaced with the mapped label and their `subcategory` is left unchanged. Machines
+/// without a category, or whose category is not mapped, are left unchanged.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `mapping` - A reference to a `HashMap` mapping the catver categories to the new category labels.
+///
+pub fn reclassify_categories(
+    machines: &mut HashMap<String, Machine>,
+    mapping: &HashMap<String, String>,
+) {
+    for machine in machines.values_mut() {
+        let Some(category) = &machine.category else {
+            continue;
+        };
+
+        let full_category = machine
+            .subcategory
+            .as_ref()
+            .map(|subcategory| format!("{} / {}", category, subcategory));
+        let label = full_category
+            .and_then(|full_category| mapping.get(&full_category))
+            .or_else(|| mapping.get(category));
+
+        if let Some(label) = label {
+            machine.category = Some(label.clone());
+        }
+    }
+}
+
+/// Sets the `is_parent` flag of the extended data of every machine from its `clone_of` and `rom_of` references.
+///
+/// Only `read_mame_file` sets the flag, so machines assembled from other sources (e.g. a cached JSON export,
+/// or the history and resources readers, which create bare machines) have no flag. This function applies the
+/// rule of `read_mame_file` to the whole map, typically once the data files have been merged: a machine is a
+/// parent if it references neither a parent (`clone_of`) nor a ROM set (`rom_of`). Existing flags are overwritten,
+/// and the extended data is created for the machines without one.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+pub fn recompute_parent_flags(machines: &mut HashMap<String, Machine>) {
+    for machine in machines.values_mut() {
+        let is_parent = machine.references_no_parent();
+        machine
+            .extended_data
+            .get_or_insert_with(Default::default)
+            .is_parent = Some(is_parent);
+    }
+}
+
+/// Stores the alternate titles of every machine in its extended data.
+///
+/// The alternate titles are extracted from the description of each machine with `extract_alternate_titles`,
+/// and replace the `alternate_titles` of the extended data, which is created for the machines without one.
+/// Once stored, they are also used by `machine_tags` without being extracted again.
+///
+/// # Arguments
+///
+/// * `machines` - A mutable reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+pub fn attach_alternate_titles(machines: &mut HashMap<String, Machine>) {
+    for machine in machines.values_mut() {
+        let alternate_titles = extract_alternate_titles(machine);
+        machine
+            .extended_data
+            .get_or_insert_with(Default::default)
+            .alternate_titles = alternate_titles;
+    }
+}
+
+/// Applies the data read for each machine present in both maps to the machine.
+fn attach_with(
+    machines: &mut HashMap<String, Machine>,
+    source: &HashMap<String, Machine>,
+    apply: impl Fn(&mut Machine, &Machine),
+) {
+    for (name, source_machine) in source {
+        if let Some(machine) = machines.get_mut(name) {
+            apply(machine, source_machine);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ExtendedData, Software};
+
+    fn software_list(name: &str) -> Software {
+        Software {
+            name: name.to_string(),
+            titles: vec![],
+        }
+    }
+
+    #[test]
+    fn test_recompute_parent_flags() {
+        let mut machines: HashMap<String, Machine> = [
+            Machine::builder("pacman").build(),
+            Machine::builder("puckman").clone_of("pacman").build(),
+            Machine::builder("mspacman")
+                .rom_of("pacman")
+                .extended_data(ExtendedData {
+                    is_parent: Some(true),
+                    ..Default::default()
+                })
+                .build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        // Machines merged from the history data, without extended data
+        let history: HashMap<String, Machine> = ["pacman", "galaga"]
+            .into_iter()
+            .map(|name| (name.to_string(), Machine::new(name.to_string())))
+            .collect();
+        for (name, machine) in history {
+            machines
+                .entry(name)
+                .and_modify(|existing| existing.combine(&machine))
+                .or_insert(machine);
+        }
+
+        recompute_parent_flags(&mut machines);
+
+        let is_parent = |name: &str| machines[name].extended_data.as_ref().unwrap().is_parent;
+        assert_eq!(is_parent("pacman"), Some(true));
+        assert_eq!(is_parent("galaga"), Some(true));
+        assert_eq!(is_parent("puckman"), Some(false));
+        assert_eq!(is_parent("mspacman"), Some(false));
+    }
+
+    #[test]
+    fn test_attach_software_titles() {
+        let mut machines: HashMap<String, Machine> = [
+            Machine::builder("nes")
+                .add_software(software_list("nes"))
+                .add_software(software_list("nes_ade"))
+                .build(),
+            Machine::builder("pacman").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let software_titles: HashMap<String, Machine> =
+            [("nes", "smb"), ("nes", "zelda"), ("snes", "smw")]
+                .into_iter()
+                .map(|(list, name)| {
+                    let title = Machine::builder(name)
+                        .add_software(software_list(list))
+                        .build();
+                    (format!("{}:{}", list, name), title)
+                })
+                .collect();
+
+        attach_software_titles(&mut machines, &software_titles);
+
+        let nes = &machines["nes"];
+        assert_eq!(nes.software_list[0].titles, vec!["smb", "zelda"]);
+        assert!(nes.software_list[1].titles.is_empty());
+        assert!(machines["pacman"].software_list.is_empty());
+    }
+
+    #[test]
+    fn test_attach_helpers() {
+        let mut machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .series("Old series")
+                .add_language("Japanese")
+                .build(),
+            Machine::builder("galaga").series("Galaxian").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let catver: HashMap<String, Machine> = [
+            (
+                "pacman",
+                Machine::builder("pacman")
+                    .category("Maze")
+                    .subcategory("Collect")
+                    .build(),
+            ),
+            (
+                "dkong",
+                Machine::builder("dkong").category("Platform").build(),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, machine)| (name.to_string(), machine))
+        .collect();
+        let series: HashMap<String, Machine> = [(
+            "pacman".to_string(),
+            Machine::builder("pacman").series("Pac-Man").build(),
+        )]
+        .into();
+        let languages: HashMap<String, Machine> = [(
+            "pacman".to_string(),
+            Machine::builder("pacman").add_language("English").build(),
+        )]
+        .into();
+        let nplayers: HashMap<String, Machine> = [(
+            "galaga".to_string(),
+            Machine::builder("galaga")
+                .players("2P alt")
+                .extended_data(ExtendedData {
+                    players: Some("2P alt".to_string()),
+                    ..ExtendedData::default()
+                })
+                .build(),
+        )]
+        .into();
+
+        attach_catver(&mut machines, &catver);
+        attach_series(&mut machines, &series);
+        attach_languages(&mut machines, &languages);
+        attach_nplayers(&mut machines, &nplayers);
+
+        assert_eq!(machines.len(), 2);
+        let pacman = &machines["pacman"];
+        assert_eq!(pacman.category.as_deref(), Some("Maze"));
+        assert_eq!(pacman.subcategory.as_deref(), Some("Collect"));
+        assert_eq!(pacman.series.as_deref(), Some("Pac-Man"));
+        assert_eq!(pacman.languages, vec!["English"]);
+
+        let galaga = &machines["galaga"];
+        assert_eq!(galaga.series.as_deref(), Some("Galaxian"));
+        assert_eq!(galaga.players.as_deref(), Some("2P alt"));
+        assert_eq!(
+            galaga.extended_data.as_ref().unwrap().players.as_deref(),
+            Some("2P alt")
+        );
+    }
+
+    #[test]
+    fn test_reclassify_categories() {
+        let mut machines: HashMap<String, Machine> = [
+            Machine::builder("galaga")
+                .category("Shooter")
+                .subcategory("Flying Vertical")
+                .build(),
+            Machine::builder("defender")
+                .category("Shooter")
+                .subcategory("Flying Horizontal")
+                .build(),
+            Machine::builder("pacman")
+                .category("Maze")
+                .subcategory("Collect")
+                .build(),
+            Machine::builder("sf2")
+                .category("Fighter")
+                .subcategory("Versus")
+                .build(),
+            Machine::builder("z80").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let mapping: HashMap<String, String> = [
+            ("Shooter / Flying Vertical", "Shmup"),
+            ("Shooter / Flying Horizontal", "Shmup"),
+            ("Maze", "Puzzle"),
+        ]
+        .into_iter()
+        .map(|(category, label)| (category.to_string(), label.to_string()))
+        .collect();
+
+        reclassify_categories(&mut machines, &mapping);
+
+        assert_eq!(machines["galaga"].category.as_deref(), Some("Shmup"));
+        assert_eq!(
+            machines["galaga"].subcategory.as_deref(),
+            Some("Flying Vertical")
+        );
+        assert_eq!(machines["defender"].category.as_deref(), Some("Shmup"));
+        assert_eq!(machines["pacman"].category.as_deref(), Some("Puzzle"));
+        assert_eq!(machines["sf2"].category.as_deref(), Some("Fighter"));
+        assert_eq!(machines["z80"].category, None);
+    }
+
+    #[test]
+    fn test_attach_alternate_titles() {
+        let mut machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .description("Pac-Man (also known as Puck-Man)")
+                .build(),
+            Machine::builder("galaga").description("Galaga").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+        machines.get_mut("pacman").unwrap().extended_data = None;
+
+        attach_alternate_titles(&mut machines);
+
+        let alternate_titles = |name: &str| {
+            machines[name]
+                .extended_data
+                .as_ref()
+                .unwrap()
+                .alternate_titles
+                .clone()
+        };
+        assert_eq!(alternate_titles("pacman"), vec!["Puck-Man"]);
+        assert!(alternate_titles("galaga").is_empty());
+    }
+}
@@ -1,5 +1,9 @@
+use crate::core::data_cleanup::name_normalization::{parse_year, YearValue};
 use crate::models::Machine;
-use std::{collections::HashMap, error::Error};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
 
 /// Removes machines from the given HashMap based on a list of filter criteria.
 ///
@@ -94,9 +98,7 @@ pub fn remove_machines_by_category(
         .collect();
 
     for (name, machine) in machines {
-        let category_str = machine.category.as_deref();
-
-        if category_str.is_some() && !categories_to_remove_str.contains(&category_str.unwrap()) {
+        if keeps_category(machine, &categories_to_remove_str) {
             filtered_machines.insert(name.clone(), machine.clone());
         }
     }
@@ -104,6 +106,440 @@ pub fn remove_machines_by_category(
     Ok(filtered_machines)
 }
 
+/// Keeps only the machines belonging to one of the given categories.
+///
+/// This function is the whitelist counterpart of `remove_machines_by_category`: it returns a new
+/// `HashMap` containing only the machines whose category is one of the specified categories, compared
+/// with `Category::as_str`. Machines without a category are removed. If the input `machines` is empty,
+/// it returns an error.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `categories_to_keep` - A slice of `Category` enums that define the categories
+///   of machines to be kept.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, Machine>)` - A new `HashMap` containing the machines that
+///   belong to any of the specified categories.
+/// * `Err(Box<dyn Error>)` - An error if the input `machines` is empty.
+///
+/// # Errors
+///
+/// Returns an error if the input `machines` HashMap is empty.
+///
+pub fn keep_machines_by_category(
+    machines: &HashMap<String, Machine>,
+    categories_to_keep: &[Category],
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    let categories_to_keep_str: Vec<&str> =
+        categories_to_keep.iter().map(|cat| cat.as_str()).collect();
+
+    let filtered_machines = machines
+        .iter()
+        .filter(|(_, machine)| {
+            machine
+                .category
+                .as_deref()
+                .is_some_and(|category| categories_to_keep_str.contains(&category))
+        })
+        .map(|(name, machine)| (name.clone(), machine.clone()))
+        .collect();
+
+    Ok(filtered_machines)
+}
+
+/// Counts the machines that `remove_machines_by_filter` would remove.
+///
+/// This function evaluates the same filter criteria as `remove_machines_by_filter`, but only
+/// counts the matching machines, without building a new `HashMap` or cloning any machine.
+/// It can be used to preview the effect of a filter before applying it.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `filters` - A slice of `MachineFilter` enums that define the filter criteria.
+///
+/// # Returns
+///
+/// * `usize` - The number of machines matching at least one of the filter criteria.
+///   It is `0` if `machines` is empty.
+///
+pub fn count_matching_filter(
+    machines: &HashMap<String, Machine>,
+    filters: &[MachineFilter],
+) -> usize {
+    machines
+        .values()
+        .filter(|machine| filters.iter().any(|filter| filter_applies(machine, filter)))
+        .count()
+}
+
+/// Counts the machines that `remove_machines_by_category` would remove.
+///
+/// This function evaluates the same category criteria as `remove_machines_by_category`, but only
+/// counts the matching machines, without building a new `HashMap` or cloning any machine.
+/// As with `remove_machines_by_category`, the machines without a category are counted too.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `categories` - A slice of `Category` enums that define the categories of machines to count.
+///
+/// # Returns
+///
+/// * `usize` - The number of machines belonging to one of the categories or without a category.
+///   It is `0` if `machines` is empty.
+///
+pub fn count_matching_category(
+    machines: &HashMap<String, Machine>,
+    categories: &[Category],
+) -> usize {
+    let categories_str: Vec<&str> = categories.iter().map(|cat| cat.as_str()).collect();
+
+    machines
+        .values()
+        .filter(|machine| !keeps_category(machine, &categories_str))
+        .count()
+}
+
+/// Removes machines whose ROMs have all been marked as not dumped.
+///
+/// This function takes a reference to a `HashMap` of machines and returns a new `HashMap`
+/// without the machines that have at least one ROM where every ROM has a `nodump` status,
+/// as nothing from those machines is actually available. Machines with at least one good or
+/// bad dump are kept, as are machines without ROMs. If the input `machines` is empty, it
+/// returns an error.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, Machine>)` - A new `HashMap` containing the machines with at least
+///   one dumped ROM, or without ROMs.
+/// * `Err(Box<dyn Error>)` - An error if the input `machines` is empty.
+///
+/// # Errors
+///
+/// Returns an error if the input `machines` HashMap is empty.
+///
+pub fn remove_undumped_machines(
+    machines: &HashMap<String, Machine>,
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    let filtered_machines = machines
+        .iter()
+        .filter(|(_, machine)| !is_undumped(machine))
+        .map(|(name, machine)| (name.clone(), machine.clone()))
+        .collect();
+
+    Ok(filtered_machines)
+}
+
+/// Keeps only the machines defined in the given driver source files.
+///
+/// This function takes a reference to a `HashMap` of machines and a slice of source file
+/// names (e.g. `"cps1.cpp"`). It returns a new `HashMap` containing only the machines whose
+/// `source_file` is one of the given files. Machines without a source file are removed.
+/// If the input `machines` is empty, it returns an error.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `source_files` - A slice with the names of the source files of the machines to keep.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, Machine>)` - A new `HashMap` containing the machines defined in
+///   any of the given source files.
+/// * `Err(Box<dyn Error>)` - An error if the input `machines` is empty.
+///
+/// # Errors
+///
+/// Returns an error if the input `machines` HashMap is empty.
+///
+pub fn keep_machines_by_source_file(
+    machines: &HashMap<String, Machine>,
+    source_files: &[&str],
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    let filtered_machines = machines
+        .iter()
+        .filter(|(_, machine)| {
+            machine
+                .source_file
+                .as_deref()
+                .is_some_and(|source_file| source_files.contains(&source_file))
+        })
+        .map(|(name, machine)| (name.clone(), machine.clone()))
+        .collect();
+
+    Ok(filtered_machines)
+}
+
+/// Keeps only the machines whose names are in the given set.
+///
+/// This function takes a reference to a `HashMap` of machines and a set of machine names, e.g. the
+/// games of a physical collection. It returns a new `HashMap` containing only the machines whose name
+/// is in the set. Names of the set that are not present in `machines` are ignored.
+/// If the input `machines` is empty, it returns an error.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `names` - A reference to a `HashSet` with the names of the machines to keep.
+/// * `case_insensitive` - When `true`, the names are compared in a case-insensitive manner.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, Machine>)` - A new `HashMap` containing the machines in the set.
+/// * `Err(Box<dyn Error>)` - An error if the input `machines` is empty.
+///
+/// # Errors
+///
+/// Returns an error if the input `machines` HashMap is empty.
+///
+pub fn keep_machines_in_set(
+    machines: &HashMap<String, Machine>,
+    names: &HashSet<String>,
+    case_insensitive: bool,
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    filter_machines_in_set(machines, names, case_insensitive, true)
+}
+
+/// Removes the machines whose names are in the given set.
+///
+/// This function is the counterpart of `keep_machines_in_set`: it returns a new `HashMap` containing
+/// only the machines whose name is not in the set. If the input `machines` is empty, it returns an error.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `names` - A reference to a `HashSet` with the names of the machines to remove.
+/// * `case_insensitive` - When `true`, the names are compared in a case-insensitive manner.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, Machine>)` - A new `HashMap` containing the machines not in the set.
+/// * `Err(Box<dyn Error>)` - An error if the input `machines` is empty.
+///
+/// # Errors
+///
+/// Returns an error if the input `machines` HashMap is empty.
+///
+pub fn remove_machines_in_set(
+    machines: &HashMap<String, Machine>,
+    names: &HashSet<String>,
+    case_insensitive: bool,
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    filter_machines_in_set(machines, names, case_insensitive, false)
+}
+
+/// Keeps the machines whose names are in the set when `keep` is `true`, or the other ones when it is `false`.
+fn filter_machines_in_set(
+    machines: &HashMap<String, Machine>,
+    names: &HashSet<String>,
+    case_insensitive: bool,
+    keep: bool,
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    let lowercase_names: HashSet<String> = if case_insensitive {
+        names.iter().map(|name| name.to_lowercase()).collect()
+    } else {
+        HashSet::new()
+    };
+    let in_set = |name: &String| {
+        if case_insensitive {
+            lowercase_names.contains(&name.to_lowercase())
+        } else {
+            names.contains(name)
+        }
+    };
+
+    let filtered_machines = machines
+        .iter()
+        .filter(|(name, _)| in_set(name) == keep)
+        .map(|(name, machine)| (name.clone(), machine.clone()))
+        .collect();
+
+    Ok(filtered_machines)
+}
+
+/// The default keywords identifying the test and debug machines, used by `remove_test_machines`.
+pub const DEFAULT_TEST_KEYWORDS: &[&str] = &["test", "debug"];
+
+/// The phrases ignored in the descriptions before matching the keywords, as they name playable releases.
+const RELEASE_STAGE_PHRASES: &[&str] = &["location test"];
+
+/// The default driver source files containing only test machines, used by `remove_test_machines`.
+pub const DEFAULT_TEST_SOURCE_FILES: &[&str] =
+    &["test.cpp", "testcpu.cpp", "testpat.cpp", "empty.cpp"];
+
+/// Removes MAME's internal test and debug machines from the given HashMap.
+///
+/// This function takes a reference to a `HashMap` of machines and returns a new `HashMap`
+/// without the development artifacts, using `DEFAULT_TEST_KEYWORDS` and `DEFAULT_TEST_SOURCE_FILES`.
+/// Unlike the `Modified` filter, which targets bootlegs and prototypes, it only removes machines
+/// that are not meant to be played. If the input `machines` is empty, it returns an error.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, Machine>)` - A new `HashMap` containing the machines that are not test machines.
+/// * `Err(Box<dyn Error>)` - An error if the input `machines` is empty.
+///
+/// # Errors
+///
+/// Returns an error if the input `machines` HashMap is empty.
+///
+pub fn remove_test_machines(
+    machines: &HashMap<String, Machine>,
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    remove_test_machines_with(machines, DEFAULT_TEST_KEYWORDS, DEFAULT_TEST_SOURCE_FILES)
+}
+
+/// Removes the test and debug machines from the given HashMap, using custom keywords and source files.
+///
+/// A machine is removed when its description contains one of the keywords as a whole word, or when its
+/// source file is one of the given source files. The "location test" releases, which are playable games,
+/// are kept. Keywords are matched in a case-insensitive manner, and source files are compared by file name,
+/// so `"testpat.cpp"` also matches `"misc/testpat.cpp"`. If the input `machines` is empty, it returns an error.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `keywords` - A slice with the keywords identifying the test machines by description.
+/// * `source_files` - A slice with the names of the source files containing only test machines.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, Machine>)` - A new `HashMap` containing the machines that are not test machines.
+/// * `Err(Box<dyn Error>)` - An error if the input `machines` is empty.
+///
+/// # Errors
+///
+/// Returns an error if the input `machines` HashMap is empty.
+///
+pub fn remove_test_machines_with(
+    machines: &HashMap<String, Machine>,
+    keywords: &[&str],
+    source_files: &[&str],
+) -> Result<HashMap<String, Machine>, Box<dyn Error>> {
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    let keywords: Vec<String> = keywords
+        .iter()
+        .map(|keyword| keyword.to_lowercase())
+        .collect();
+
+    let filtered_machines = machines
+        .iter()
+        .filter(|(_, machine)| !is_test_machine(machine, &keywords, source_files))
+        .map(|(name, machine)| (name.clone(), machine.clone()))
+        .collect();
+
+    Ok(filtered_machines)
+}
+
+/// Determines if a machine is a test or debug machine.
+///
+/// # Arguments
+///
+/// * `machine` - A reference to a `Machine` struct representing the machine to be evaluated.
+/// * `keywords` - A slice with the lowercase keywords identifying the test machines.
+/// * `source_files` - A slice with the names of the source files containing only test machines.
+///
+/// # Returns
+///
+/// * `bool` - `true` if the description or source file of the machine identifies it
+///   as a test machine; `false` otherwise.
+///
+fn is_test_machine(machine: &Machine, keywords: &[String], source_files: &[&str]) -> bool {
+    if let Some(description) = &machine.description {
+        let mut description = description.to_lowercase();
+        for phrase in RELEASE_STAGE_PHRASES {
+            description = description.replace(phrase, "");
+        }
+        let mut words = description.split(|c: char| !c.is_alphanumeric());
+        if words.any(|word| keywords.iter().any(|keyword| keyword == word)) {
+            return true;
+        }
+    }
+
+    machine.source_file.as_deref().is_some_and(|source_file| {
+        let file_name = source_file.rsplit('/').next().unwrap_or(source_file);
+        source_files.contains(&file_name)
+    })
+}
+
+/// Determines if all the ROMs of a machine are marked as not dumped.
+///
+/// # Arguments
+///
+/// * `machine` - A reference to a `Machine` struct representing the machine to be evaluated.
+///
+/// # Returns
+///
+/// * `bool` - `true` if the machine has ROMs and all of them have a `nodump` status;
+///   `false` otherwise.
+///
+fn is_undumped(machine: &Machine) -> bool {
+    !machine.roms.is_empty()
+        && machine
+            .roms
+            .iter()
+            .all(|rom| rom.status.as_deref() == Some("nodump"))
+}
+
+/// Determines if a machine is kept when removing a list of categories.
+///
+/// # Arguments
+///
+/// * `machine` - A reference to a `Machine` struct representing the machine to be evaluated.
+/// * `categories_to_remove` - A slice with the names of the categories to be removed.
+///
+/// # Returns
+///
+/// * `bool` - `true` if the machine has a category and it is not one of the categories to remove;
+///   `false` otherwise.
+///
+pub(crate) fn keeps_category(machine: &Machine, categories_to_remove: &[&str]) -> bool {
+    machine
+        .category
+        .as_deref()
+        .is_some_and(|category| !categories_to_remove.contains(&category))
+}
+
 /// Checks if a given machine matches a specified filter criteria.
 ///
 /// This function evaluates a `Machine` against a given `MachineFilter` and returns `true`
@@ -120,7 +556,7 @@ pub fn remove_machines_by_category(
 ///
 /// * `bool` - `true` if the machine matches the filter criteria; `false` otherwise.
 ///
-fn filter_applies(machine: &Machine, machine_filter: &MachineFilter) -> bool {
+pub(crate) fn filter_applies(machine: &Machine, machine_filter: &MachineFilter) -> bool {
     match machine_filter {
         MachineFilter::Device => machine.is_device.unwrap_or(false),
         MachineFilter::Bios => machine.is_bios.unwrap_or(false),
@@ -131,6 +567,8 @@ fn filter_applies(machine: &Machine, machine_filter: &MachineFilter) -> bool {
                 || has_invalid_players(&machine)
         }
         MachineFilter::Clones => is_clone(machine),
+        MachineFilter::NoManufacturer => has_no_manufacturer(machine),
+        MachineFilter::NoYear => has_no_year(machine),
     }
 }
 
@@ -272,6 +710,41 @@ fn is_clone(machine: &Machine) -> bool {
     machine.clone_of.is_some() || machine.rom_of.is_some()
 }
 
+/// Checks if a machine has no manufacturer.
+///
+/// # Arguments
+///
+/// * `machine` - A reference to a `Machine` struct whose manufacturer is to be evaluated.
+///
+/// # Returns
+///
+/// * `bool` - `true` if the manufacturer is missing or only contains whitespace; `false` otherwise.
+///
+fn has_no_manufacturer(machine: &Machine) -> bool {
+    match machine.manufacturer.as_deref() {
+        Some(manufacturer) => manufacturer.trim().is_empty(),
+        None => true,
+    }
+}
+
+/// Checks if a machine has no known year.
+///
+/// # Arguments
+///
+/// * `machine` - A reference to a `Machine` struct whose year is to be evaluated.
+///
+/// # Returns
+///
+/// * `bool` - `true` if the year is not an exact year or a range of years according to `parse_year`,
+///   e.g. when it is missing, empty, or partially unknown (`19??` or `198?`); `false` otherwise.
+///
+fn has_no_year(machine: &Machine) -> bool {
+    !matches!(
+        parse_year(machine.year.as_deref().unwrap_or("")),
+        YearValue::Exact(_) | YearValue::Range(..)
+    )
+}
+
 /// Represents different filter criteria for filtering machines.
 ///
 /// The `MachineFilter` enum defines various criteria that can be used to filter
@@ -286,6 +759,11 @@ fn is_clone(machine: &Machine) -> bool {
 /// * `Modified` - Filters machines that are considered modified based on their description,
 ///   manufacturer validity, or player information.
 /// * `Clones` - Filters machines that are identified as clones of other machines.
+/// * `NoManufacturer` - Filters machines without a manufacturer, or with an empty one.
+/// * `NoYear` - Filters machines without a known year: missing, empty, or containing a `?` (e.g. `19??`).
+///
+/// `NoManufacturer` and `NoYear` are stricter than `Modified`, which only removes machines whose manufacturer
+/// is a known placeholder (e.g. "unknown"): they remove every machine missing the metadata, whatever its origin.
 ///
 pub enum MachineFilter {
     /// Filters machines that are marked as devices.
@@ -298,6 +776,10 @@ pub enum MachineFilter {
     Modified,
     /// Filters machines that are identified as clones of other machines.
     Clones,
+    /// Filters machines without a manufacturer.
+    NoManufacturer,
+    /// Filters machines without a known year.
+    NoYear,
 }
 
 /// Represents the different categories a machine can belong to.
@@ -418,7 +900,7 @@ pub enum Category {
 
 impl Category {
     /// Returns the string representation of the `Category` enum variant.
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             Category::Arcade => "Arcade",
             Category::BallAndPaddle => "Ball & Paddle",
@@ -475,3 +957,248 @@ impl Category {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Rom;
+
+    fn rom(name: &str, status: Option<&str>) -> Rom {
+        Rom {
+            name: name.to_string(),
+            size: 1024,
+            merge: None,
+            status: status.map(|status| status.to_string()),
+            crc: None,
+            sha1: None,
+            region: None,
+            offset: None,
+            bios: None,
+        }
+    }
+
+    #[test]
+    fn test_remove_undumped_machines() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("undumped")
+                .add_rom(rom("a.bin", Some("nodump")))
+                .add_rom(rom("b.bin", Some("nodump")))
+                .build(),
+            Machine::builder("mixed")
+                .add_rom(rom("a.bin", Some("nodump")))
+                .add_rom(rom("b.bin", Some("baddump")))
+                .build(),
+            Machine::builder("good").add_rom(rom("a.bin", None)).build(),
+            Machine::builder("no_roms").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let filtered = remove_undumped_machines(&machines).unwrap();
+
+        assert!(!filtered.contains_key("undumped"));
+        assert!(filtered.contains_key("mixed"));
+        assert!(filtered.contains_key("good"));
+        assert!(filtered.contains_key("no_roms"));
+    }
+
+    #[test]
+    fn test_remove_undumped_machines_empty() {
+        assert!(remove_undumped_machines(&HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_keep_machines_by_category() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("galaga").category("Shooter").build(),
+            Machine::builder("pacman").category("Maze").build(),
+            Machine::builder("sf2").category("Fighter").build(),
+            Machine::builder("unknown").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let filtered_machines =
+            keep_machines_by_category(&machines, &[Category::Shooter, Category::Maze]).unwrap();
+
+        let mut names: Vec<&String> = filtered_machines.keys().collect();
+        names.sort();
+        assert_eq!(names, ["galaga", "pacman"]);
+        assert!(keep_machines_by_category(&HashMap::new(), &[Category::Shooter]).is_err());
+    }
+
+    #[test]
+    fn test_keep_machines_by_source_file() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("sf2")
+                .source_file("capcom/cps1.cpp")
+                .build(),
+            Machine::builder("ffight")
+                .source_file("capcom/cps1.cpp")
+                .build(),
+            Machine::builder("pacman")
+                .source_file("pacman/pacman.cpp")
+                .build(),
+            Machine::builder("unknown").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let filtered_machines =
+            keep_machines_by_source_file(&machines, &["capcom/cps1.cpp"]).unwrap();
+
+        let mut names: Vec<&String> = filtered_machines.keys().collect();
+        names.sort();
+        assert_eq!(names, ["ffight", "sf2"]);
+
+        let source_files = crate::models::collections::get_source_files_list(&machines);
+        assert_eq!(source_files.len(), 2);
+        assert_eq!(source_files["capcom/cps1.cpp"], 2);
+    }
+
+    #[test]
+    fn test_keep_and_remove_machines_in_set() {
+        let machines: HashMap<String, Machine> = ["pacman", "galaga", "dkong"]
+            .into_iter()
+            .map(|name| (name.to_string(), Machine::builder(name).build()))
+            .collect();
+        let names: HashSet<String> = ["pacman", "Galaga", "sf2"]
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        let kept = keep_machines_in_set(&machines, &names, false).unwrap();
+        assert_eq!(kept.keys().collect::<Vec<_>>(), ["pacman"]);
+
+        let mut kept: Vec<String> = keep_machines_in_set(&machines, &names, true)
+            .unwrap()
+            .into_keys()
+            .collect();
+        kept.sort();
+        assert_eq!(kept, ["galaga", "pacman"]);
+
+        let removed = remove_machines_in_set(&machines, &names, true).unwrap();
+        assert_eq!(removed.keys().collect::<Vec<_>>(), ["dkong"]);
+
+        assert!(keep_machines_in_set(&HashMap::new(), &names, false).is_err());
+    }
+
+    fn machines_with_metadata() -> HashMap<String, Machine> {
+        [
+            Machine::builder("complete")
+                .manufacturer("Namco")
+                .year("1980")
+                .build(),
+            Machine::builder("empty").manufacturer("").year("").build(),
+            Machine::builder("missing").build(),
+            Machine::builder("partial")
+                .manufacturer("Sega")
+                .year("19??")
+                .build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect()
+    }
+
+    #[test]
+    fn test_remove_machines_by_filter_no_manufacturer() {
+        let machines = machines_with_metadata();
+
+        let filtered_machines =
+            remove_machines_by_filter(&machines, &[MachineFilter::NoManufacturer]).unwrap();
+
+        let mut names: Vec<&String> = filtered_machines.keys().collect();
+        names.sort();
+        assert_eq!(names, ["complete", "partial"]);
+    }
+
+    #[test]
+    fn test_remove_machines_by_filter_no_year() {
+        let machines = machines_with_metadata();
+
+        let filtered_machines =
+            remove_machines_by_filter(&machines, &[MachineFilter::NoYear]).unwrap();
+
+        let names: Vec<&String> = filtered_machines.keys().collect();
+        assert_eq!(names, ["complete"]);
+    }
+
+    #[test]
+    fn test_count_matching_filter() {
+        let machines = machines_with_metadata();
+        let filters = [MachineFilter::NoManufacturer, MachineFilter::NoYear];
+
+        let removed = machines.len()
+            - remove_machines_by_filter(&machines, &filters)
+                .unwrap()
+                .len();
+
+        assert_eq!(count_matching_filter(&machines, &filters), removed);
+        assert_eq!(count_matching_filter(&machines, &[]), 0);
+        assert_eq!(count_matching_filter(&HashMap::new(), &filters), 0);
+    }
+
+    #[test]
+    fn test_count_matching_category() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman").category("Maze").build(),
+            Machine::builder("galaga").category("Shooter").build(),
+            Machine::builder("cdrom").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        assert_eq!(count_matching_category(&machines, &[Category::Maze]), 2);
+        assert_eq!(
+            remove_machines_by_category(&machines, &[Category::Maze])
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_remove_test_machines() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("testcpu")
+                .source_file("testcpu.cpp")
+                .build(),
+            Machine::builder("tpatgen")
+                .description("TV Test Pattern Generator")
+                .build(),
+            Machine::builder("smpte")
+                .source_file("misc/testpat.cpp")
+                .build(),
+            Machine::builder("greatgun")
+                .description("Greatest Gun")
+                .build(),
+            Machine::builder("sf2ua")
+                .description("Street Fighter II: The World Warrior (location test)")
+                .build(),
+            Machine::builder("testris").description("Testris").build(),
+            Machine::builder("pacman")
+                .source_file("pacman/pacman.cpp")
+                .build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let filtered_machines = remove_test_machines(&machines).unwrap();
+
+        let mut names: Vec<&String> = filtered_machines.keys().collect();
+        names.sort();
+        assert_eq!(names, ["greatgun", "pacman", "sf2ua", "testris"]);
+
+        let filtered_machines =
+            remove_test_machines_with(&machines, &["greatest"], &["pacman.cpp"]).unwrap();
+        assert_eq!(filtered_machines.len(), 5);
+        assert!(!filtered_machines.contains_key("greatgun"));
+        assert!(!filtered_machines.contains_key("pacman"));
+    }
+}
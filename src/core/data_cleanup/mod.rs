@@ -1,2 +1,4 @@
+pub mod filter_pipeline;
+pub mod machine_enrichment;
 pub mod machine_filtering;
 pub mod name_normalization;
@@ -1,3 +1,4 @@
+use crate::models::Machine;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
@@ -6,6 +7,8 @@ use std::collections::HashMap;
 lazy_static! {
     static ref RE_COMMON: Regex = Regex::new(r"(?i)\b(Games|Corp|Inc|Ltd|Co|Corporation|Industries|Elc|S\.R\.L|S\.A|inc|of America|Japan|UK|USA|Europe|do Brasil|du Canada|Canada|America|Austria|of)\b\.?").unwrap();
     static ref RE_PUNCTUATION: Regex = Regex::new(r"[.,?]+$|-$").unwrap();
+    static ref RE_AKA: Regex = Regex::new(r"(?i)\(\s*(?:also known as|a\.?k\.?a\.?)\s+([^)]+)\)").unwrap();
+    static ref RE_PARENTHESES: Regex = Regex::new(r"\([^)]*\)").unwrap();
     static ref NEEDS_CLEANING: Regex = Regex::new(r"[\(/,?]|(Games|Corp|Inc|Ltd|Co|Corporation|Industries|Elc|S\.R\.L|S\.A|inc|of America|Japan|UK|USA|Europe|do Brasil|du Canada|Canada|America|Austria|of)").unwrap();
 }
 
@@ -30,6 +33,121 @@ const SUBSTITUTIONS_ARRAY: &[(&str, &str)] = &[
     ("Non-arcade", "Non-arcade game"),
 ];
 
+/// Represents the value of a MAME year string, as interpreted by `parse_year`.
+///
+/// # Variants
+/// - `Exact`: A single known year (e.g. `1985`).
+/// - `Range`: A range of years, both included (e.g. `1985-1987`).
+/// - `Decade`: A year whose last digit is unknown (e.g. `198?`), holding the first year of the decade.
+/// - `Unknown`: A missing, empty, or unparseable year, or one with more than one unknown digit (e.g. `19??`).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YearValue {
+    /// A single known year.
+    Exact(u16),
+    /// A range of years, both included.
+    Range(u16, u16),
+    /// A year known to the decade, holding the first year of the decade.
+    Decade(u16),
+    /// An unknown year.
+    Unknown,
+}
+
+/// Parses a MAME year string.
+///
+/// This function centralizes the interpretation of the year strings found in the MAME data files,
+/// so every filter, export and normalization handles the unknown digits in the same way.
+///
+/// # Parameters
+/// - `raw`: A `&str` with the year as found in the data files. Surrounding whitespace is ignored.
+///
+/// # Returns
+/// Returns the `YearValue` of the string:
+/// - `YearValue::Exact` for four digits (e.g. `1985`).
+/// - `YearValue::Range` for two exact years separated by `-`, the first not after the second (e.g. `1985-1987`).
+/// - `YearValue::Decade` for three digits followed by `?` (e.g. `198?`).
+/// - `YearValue::Unknown` otherwise (e.g. ``, `19??` or `????`).
+pub fn parse_year(raw: &str) -> YearValue {
+    let raw = raw.trim();
+
+    if let Some((start, end)) = raw.split_once('-') {
+        return match (parse_year(start), parse_year(end)) {
+            (YearValue::Exact(start), YearValue::Exact(end)) if start <= end => {
+                YearValue::Range(start, end)
+            }
+            _ => YearValue::Unknown,
+        };
+    }
+
+    let is_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+    match (raw.len(), raw.strip_suffix('?')) {
+        (4, None) if is_digits(raw) => raw.parse().map_or(YearValue::Unknown, YearValue::Exact),
+        (4, Some(decade)) if is_digits(decade) => decade
+            .parse::<u16>()
+            .map_or(YearValue::Unknown, |decade| YearValue::Decade(decade * 10)),
+        _ => YearValue::Unknown,
+    }
+}
+
+/// Represents the kind of a hash found in the MAME data files, as validated by `normalize_hash`.
+///
+/// # Variants
+/// - `Crc`: A CRC32 hash, written as 8 hexadecimal digits.
+/// - `Sha1`: A SHA-1 hash, written as 40 hexadecimal digits.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashKind {
+    /// A CRC32 hash.
+    Crc,
+    /// A SHA-1 hash.
+    Sha1,
+}
+
+/// Normalizes a hash string to its canonical form.
+///
+/// The data files write the hashes with varying case and occasional whitespace. This function returns
+/// them in a single form, so hashes from different datasets can be compared.
+///
+/// # Parameters
+/// - `raw`: A `&str` with the hash as found in the data files. Surrounding whitespace is ignored.
+/// - `kind`: The `HashKind` the hash is validated against.
+///
+/// # Returns
+/// Returns an `Option<String>`:
+/// - `Some` with the lowercased hash if it has the number of hexadecimal digits of its kind.
+/// - `None` otherwise (e.g. an empty string, a wrong length or a non-hexadecimal character).
+pub fn normalize_hash(raw: &str, kind: HashKind) -> Option<String> {
+    let raw = raw.trim();
+    let length = match kind {
+        HashKind::Crc => 8,
+        HashKind::Sha1 => 40,
+    };
+
+    if raw.len() == length && raw.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        Some(raw.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+/// Normalizes the year of a machine.
+///
+/// # Parameters
+/// - `year`: An `Option<String>` that contains the year of the machine to be normalized.
+///
+/// # Returns
+/// Returns a `String` representing the normalized year:
+/// - If `year` is an exact year or a range of years according to `parse_year`, the function returns it
+///   without the surrounding whitespace (e.g. `1985` or `1985-1987`).
+/// - Otherwise, including when `year` is `None`, empty, or contains `?` (e.g. `198?`), the function returns `"Unknown"`.
+pub(crate) fn normalize_year(year: &Option<String>) -> String {
+    match parse_year(year.as_deref().unwrap_or("")) {
+        YearValue::Exact(year) => year.to_string(),
+        YearValue::Range(start, end) => format!("{}-{}", start, end),
+        YearValue::Decade(_) | YearValue::Unknown => "Unknown".to_string(),
+    }
+}
+
 /// Normalizes a machine's name based on its description.
 ///
 /// This function takes an optional description of a machine and returns a normalized version of the name.
@@ -81,6 +199,67 @@ pub(crate) fn normalize_machine_name(description: &Option<String>) -> String {
     result
 }
 
+/// Extracts the alternate titles of a machine from its description.
+///
+/// MAME descriptions often mention the other names under which a machine was released, which are useful
+/// to index the machine for search. Each alternate title is normalized like the name of the extended data.
+///
+/// # Parameters
+/// - `machine`: A reference to the `Machine` whose `description` is parsed.
+///
+/// # Returns
+/// Returns a `Vec<String>` with the alternate titles, in the order they appear in the description.
+/// The titles equal to the main title, and the duplicated titles, are skipped (ignoring case).
+/// The vector is empty if the machine has no description or no alternate title.
+///
+/// # Recognized Patterns
+/// - `also known as`, `aka` or `a.k.a.` at the start of a group between parentheses, case-insensitive,
+///   e.g. `Pac-Man (also known as Puck-Man)` or `Mr. Do! (aka Yankee DO!)`.
+/// - Titles separated by ` / ` outside of the parentheses, the first one being the main title,
+///   e.g. `Lunar Rescue / Destination Earth (bootleg)`. A `/` without surrounding spaces, as in `AC/DC`,
+///   is part of the title. The `aka` groups can also hold several titles separated by ` / `.
+pub fn extract_alternate_titles(machine: &Machine) -> Vec<String> {
+    let Some(description) = machine.description.as_deref() else {
+        return Vec::new();
+    };
+
+    let without_parentheses = RE_PARENTHESES.replace_all(description, "");
+    let mut titles = without_parentheses.split(" / ");
+    let main_title = normalize_alternate_title(titles.next().unwrap_or(""));
+
+    let aka_titles = RE_AKA
+        .captures_iter(description)
+        .flat_map(|captures| {
+            captures[1]
+                .split(" / ")
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut alternate_titles: Vec<String> = Vec::new();
+    for title in titles.map(str::to_string).chain(aka_titles) {
+        let title = normalize_alternate_title(&title);
+        let is_known = title.eq_ignore_ascii_case(&main_title)
+            || alternate_titles
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(&title));
+        if !title.is_empty() && !is_known {
+            alternate_titles.push(title);
+        }
+    }
+
+    alternate_titles
+}
+
+/// Normalizes an alternate title with `normalize_machine_name`, collapsing its whitespace.
+fn normalize_alternate_title(title: &str) -> String {
+    normalize_machine_name(&Some(title.to_string()))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Normalizes a manufacturer's name by cleaning and formatting it.
 ///
 /// This function processes an optional manufacturer's name, removing unwanted characters, words,
@@ -177,3 +356,109 @@ pub(crate) fn normalize_nplayer_name(nplayers: &Option<String>) -> String {
         .collect::<Vec<_>>()
         .join(", ")
 }
+
+/// Parses the number of players and their simultaneity from an nplayers code.
+///
+/// The modes of a code are separated by `/` (e.g. `4P alt / 2P sim`), each made of a player count
+/// and an optional `alt` or `sim` suffix.
+///
+/// # Parameters
+/// - `nplayers`: A `&str` with the nplayers code (e.g. `2P sim`, `4P alt` or `1P`).
+///
+/// # Returns
+/// Returns a tuple with:
+/// - The maximum number of players of any mode, or `None` if no mode has a player count (e.g. `???`, `BIOS` or `Pinball`).
+/// - `Some(true)` if any mode is simultaneous, `Some(false)` if the modes are alternate or single-player,
+///   or `None` if no mode has a player count.
+pub(crate) fn parse_nplayers(nplayers: &str) -> (Option<u8>, Option<bool>) {
+    let mut max_players: Option<u8> = None;
+    let mut simultaneous = false;
+
+    for mode in nplayers.split('/') {
+        let mut words = mode.split_whitespace();
+        let Some(players) = words
+            .next()
+            .and_then(|count| count.strip_suffix('P'))
+            .and_then(|count| count.parse::<u8>().ok())
+        else {
+            continue;
+        };
+
+        max_players = max_players.max(Some(players));
+        simultaneous |= players > 1 && words.next() == Some("sim");
+    }
+
+    (max_players, max_players.map(|_| simultaneous))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_year() {
+        assert_eq!(parse_year("1985"), YearValue::Exact(1985));
+        assert_eq!(parse_year(" 1985 "), YearValue::Exact(1985));
+        assert_eq!(parse_year("1985-1987"), YearValue::Range(1985, 1987));
+        assert_eq!(parse_year("198?"), YearValue::Decade(1980));
+        assert_eq!(parse_year("19??"), YearValue::Unknown);
+        assert_eq!(parse_year("????"), YearValue::Unknown);
+        assert_eq!(parse_year(""), YearValue::Unknown);
+        assert_eq!(parse_year("1987-1985"), YearValue::Unknown);
+        assert_eq!(parse_year("198X"), YearValue::Unknown);
+    }
+
+    #[test]
+    fn test_normalize_hash() {
+        assert_eq!(
+            normalize_hash(" 0C2F7F5A ", HashKind::Crc).as_deref(),
+            Some("0c2f7f5a")
+        );
+        assert_eq!(normalize_hash("0c2f7f5", HashKind::Crc), None);
+        assert_eq!(normalize_hash("0c2f7f5g", HashKind::Crc), None);
+        assert_eq!(
+            normalize_hash(&"AB".repeat(20), HashKind::Sha1),
+            Some("ab".repeat(20))
+        );
+        assert_eq!(normalize_hash("0c2f7f5a", HashKind::Sha1), None);
+        assert_eq!(normalize_hash("", HashKind::Crc), None);
+    }
+
+    #[test]
+    fn test_parse_nplayers() {
+        assert_eq!(parse_nplayers("2P sim"), (Some(2), Some(true)));
+        assert_eq!(parse_nplayers("4P alt"), (Some(4), Some(false)));
+        assert_eq!(parse_nplayers("1P"), (Some(1), Some(false)));
+        assert_eq!(parse_nplayers("4P alt / 2P sim"), (Some(4), Some(true)));
+        assert_eq!(parse_nplayers("???"), (None, None));
+        assert_eq!(parse_nplayers("BIOS"), (None, None));
+    }
+
+    #[test]
+    fn test_normalize_year() {
+        assert_eq!(normalize_year(&Some("1985".to_string())), "1985");
+        assert_eq!(normalize_year(&Some("1985-1987".to_string())), "1985-1987");
+        assert_eq!(normalize_year(&Some("198?".to_string())), "Unknown");
+        assert_eq!(normalize_year(&None), "Unknown");
+    }
+
+    #[test]
+    fn test_extract_alternate_titles() {
+        let titles = |description: &str| {
+            extract_alternate_titles(&Machine::builder("test").description(description).build())
+        };
+
+        assert_eq!(titles("Pac-Man (also known as Puck-Man)"), vec!["Puck-Man"]);
+        assert_eq!(titles("Mr. Do! (aka Yankee DO!)"), vec!["Yankee DO!"]);
+        assert_eq!(
+            titles("Lunar Rescue / Destination Earth (bootleg)"),
+            vec!["Destination Earth"]
+        );
+        assert_eq!(
+            titles("Block Out / block out (A.K.A. Block  Out 3D / Tetris 3D)"),
+            vec!["Block Out 3D", "Tetris 3D"]
+        );
+        assert!(titles("AC/DC Live (Japan)").is_empty());
+        assert!(extract_alternate_titles(&Machine::builder("test").build()).is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::core::file_handling::file_writer::ExportFileType;
+use crate::models::Machine;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The fields of the machines compared by `verify_export`.
+const VERIFIED_FIELDS: [&str; 6] = [
+    "source_file",
+    "rom_of",
+    "clone_of",
+    "description",
+    "year",
+    "manufacturer",
+];
+
+/// The verified fields of the exported machines, by machine name.
+type ExportedMachines = HashMap<String, HashMap<&'static str, Option<String>>>;
+
+/// Represents a difference between an export and the machines it was written from.
+///
+/// The `Discrepancy` enum is returned by `verify_export` for each difference found.
+///
+/// # Variants
+/// - `Unreadable`: The exported files could not be read back, with the reason.
+/// - `MachineCount`: The number of exported machines differs from the number of source machines.
+/// - `MissingMachine`: A source machine is missing from the export.
+/// - `UnexpectedMachine`: The export contains a machine that is not in the source machines.
+/// - `FieldMismatch`: A field of an exported machine differs from the source machine.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The exported files could not be read back.
+    Unreadable(String),
+    /// The number of exported machines differs from the number of source machines.
+    MachineCount { expected: usize, found: usize },
+    /// A source machine is missing from the export.
+    MissingMachine(String),
+    /// The export contains a machine that is not in the source machines.
+    UnexpectedMachine(String),
+    /// A field of an exported machine differs from the source machine.
+    FieldMismatch {
+        machine: String,
+        field: &'static str,
+        expected: Option<String>,
+        found: Option<String>,
+    },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::Unreadable(reason) => write!(f, "Export could not be read: {}", reason),
+            Discrepancy::MachineCount { expected, found } => write!(
+                f,
+                "Expected {} machines in the export, found {}",
+                expected, found
+            ),
+            Discrepancy::MissingMachine(name) => write!(f, "Machine {} is missing", name),
+            Discrepancy::UnexpectedMachine(name) => write!(f, "Machine {} is unexpected", name),
+            Discrepancy::FieldMismatch {
+                machine,
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Field {} of machine {} is {:?}, expected {:?}",
+                field, machine, found, expected
+            ),
+        }
+    }
+}
+
+/// Verifies that an export faithfully represents the machines it was written from.
+///
+/// This function reads back the machines of an export folder, as returned by `write_files`, and compares the
+/// number of machines and the key fields of each machine (`source_file`, `rom_of`, `clone_of`, `description`,
+/// `year` and `manufacturer`) with the source machines. It is meant as a regression check of the writers, e.g. in CI.
+///
+/// The `machines.db`, `machines.json` or `machines.csv` file of the folder is read, so the export must have been
+/// written without the options changing its layout, such as `table_prefix`, `shards` or `nested_clones`.
+///
+/// # Parameters
+/// - `export_path`: A reference to the `Path` of the export folder.
+/// - `machines`: A reference to the `HashMap` with the machines the export was written from.
+/// - `format`: The `ExportFileType` of the export.
+///
+/// # Returns
+/// Returns a `Result<(), Vec<Discrepancy>>`:
+/// - On success: Returns `Ok(())` if the export matches the machines.
+/// - On failure: Contains every `Discrepancy` found, sorted by machine name, or a single `Discrepancy::Unreadable`
+///   if the export cannot be read.
+///
+pub fn verify_export(
+    export_path: &Path,
+    machines: &HashMap<String, Machine>,
+    format: ExportFileType,
+) -> Result<(), Vec<Discrepancy>> {
+    let exported_machines = match format {
+        ExportFileType::Sqlite => read_sqlite_export(&export_path.join("machines.db")),
+        ExportFileType::Json => read_json_export(&export_path.join("machines.json")),
+        ExportFileType::Csv => read_csv_export(&export_path.join("machines.csv")),
+    }
+    .map_err(|err| vec![Discrepancy::Unreadable(err.to_string())])?;
+
+    let mut discrepancies = Vec::new();
+    if exported_machines.len() != machines.len() {
+        discrepancies.push(Discrepancy::MachineCount {
+            expected: machines.len(),
+            found: exported_machines.len(),
+        });
+    }
+
+    let mut names: Vec<&String> = machines.keys().chain(exported_machines.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        let (machine, exported_fields) = match (machines.get(name), exported_machines.get(name)) {
+            (Some(machine), Some(exported_fields)) => (machine, exported_fields),
+            (Some(_), None) => {
+                discrepancies.push(Discrepancy::MissingMachine(name.clone()));
+                continue;
+            }
+            _ => {
+                discrepancies.push(Discrepancy::UnexpectedMachine(name.clone()));
+                continue;
+            }
+        };
+
+        for field in VERIFIED_FIELDS {
+            let expected = verified_field(machine, field);
+            let found = exported_fields.get(field).cloned().flatten();
+            if expected != found {
+                discrepancies.push(Discrepancy::FieldMismatch {
+                    machine: name.clone(),
+                    field,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    if discrepancies.is_empty() {
+        Ok(())
+    } else {
+        Err(discrepancies)
+    }
+}
+
+/// Returns the value of a verified field of a machine, empty values being treated as missing.
+fn verified_field(machine: &Machine, field: &str) -> Option<String> {
+    let value = match field {
+        "source_file" => &machine.source_file,
+        "rom_of" => &machine.rom_of,
+        "clone_of" => &machine.clone_of,
+        "description" => &machine.description,
+        "year" => &machine.year,
+        "manufacturer" => &machine.manufacturer,
+        _ => &None,
+    };
+
+    value.clone().filter(|value| !value.is_empty())
+}
+
+/// Reads the verified fields of the machines of a SQLite export.
+fn read_sqlite_export(
+    data_base_path: &Path,
+) -> Result<ExportedMachines, Box<dyn Error + Send + Sync>> {
+    if !data_base_path.exists() {
+        return Err(format!("{} not found", data_base_path.display()).into());
+    }
+
+    let conn = Connection::open(data_base_path)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT name, {} FROM machines",
+        VERIFIED_FIELDS.join(", ")
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        let name: String = row.get(0)?;
+        let mut fields = HashMap::new();
+        for (i, field) in VERIFIED_FIELDS.into_iter().enumerate() {
+            let value: Option<String> = row.get(i + 1)?;
+            fields.insert(field, value.filter(|value| !value.is_empty()));
+        }
+        Ok((name, fields))
+    })?;
+
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Reads the verified fields of the machines of a JSON export.
+fn read_json_export(json_path: &Path) -> Result<ExportedMachines, Box<dyn Error + Send + Sync>> {
+    let content = fs::read_to_string(json_path)?;
+    let exported: Vec<serde_json::Value> = serde_json::from_str(&content)?;
+
+    exported
+        .iter()
+        .map(|machine| {
+            let name = machine
+                .get("name")
+                .and_then(|name| name.as_str())
+                .ok_or("Exported machine without name")?;
+            let fields = VERIFIED_FIELDS
+                .into_iter()
+                .map(|field| {
+                    let value = machine
+                        .get(field)
+                        .and_then(|value| value.as_str())
+                        .filter(|value| !value.is_empty())
+                        .map(|value| value.to_string());
+                    (field, value)
+                })
+                .collect();
+            Ok((name.to_string(), fields))
+        })
+        .collect()
+}
+
+/// Reads the verified fields of the machines of a CSV export.
+fn read_csv_export(csv_path: &Path) -> Result<ExportedMachines, Box<dyn Error + Send + Sync>> {
+    let content = fs::read_to_string(csv_path)?;
+    // The files may start with a byte order mark
+    let mut reader = csv::Reader::from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+
+    let headers = reader.headers()?.clone();
+    let column = |field: &str| {
+        headers
+            .iter()
+            .position(|header| header == field)
+            .ok_or_else(|| format!("Column {} not found", field))
+    };
+    let name_column = column("name")?;
+    let field_columns = VERIFIED_FIELDS
+        .into_iter()
+        .map(|field| Ok((field, column(field)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut exported_machines = HashMap::new();
+    for record in reader.records() {
+        let record = record?;
+        let fields = field_columns
+            .iter()
+            .map(|&(field, column)| {
+                let value = record
+                    .get(column)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| value.to_string());
+                (field, value)
+            })
+            .collect();
+        exported_machines.insert(record[name_column].to_string(), fields);
+    }
+
+    Ok(exported_machines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::file_handling::file_writer::write_files;
+
+    #[test]
+    fn test_verify_export() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_verify_export");
+        let mut machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .description("Pac-Man (Midway)")
+                .year("1980")
+                .manufacturer("Namco (Midway license)")
+                .build(),
+            Machine::builder("puckman")
+                .clone_of("pacman")
+                .rom_of("pacman")
+                .description("Puck Man (Japan set 1)")
+                .year("1980")
+                .build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        for format in [
+            ExportFileType::Sqlite,
+            ExportFileType::Json,
+            ExportFileType::Csv,
+        ] {
+            let export_path =
+                write_files(format, &workspace_path, &machines, Box::new(|_| {})).unwrap();
+            assert_eq!(verify_export(&export_path, &machines, format), Ok(()));
+        }
+
+        let export_path = workspace_path.join("export").join("json");
+        machines.get_mut("pacman").unwrap().year = Some("1981".to_string());
+        machines.remove("puckman");
+
+        let discrepancies =
+            verify_export(&export_path, &machines, ExportFileType::Json).unwrap_err();
+        assert_eq!(
+            discrepancies,
+            [
+                Discrepancy::MachineCount {
+                    expected: 1,
+                    found: 2
+                },
+                Discrepancy::FieldMismatch {
+                    machine: "pacman".to_string(),
+                    field: "year",
+                    expected: Some("1981".to_string()),
+                    found: Some("1980".to_string()),
+                },
+                Discrepancy::UnexpectedMachine("puckman".to_string()),
+            ]
+        );
+
+        fs::remove_dir_all(&workspace_path).unwrap();
+    }
+}
@@ -1,18 +1,24 @@
 use crate::helpers::{
-    data_source_helper::{get_data_source, get_file_name_from_url},
-    file_system_helpers::{ensure_folder_exists, WORKSPACE_PATHS},
+    data_source_helper::{
+        compare_versions, get_data_source, get_file_name_from_url, get_version_from_file_name,
+        http_client, is_newer_version,
+    },
+    file_system_helpers::{ensure_folder_exists, find_files_with_pattern, WorkspaceLayout},
 };
 use crate::{
     core::models::{
-        callback_progress::{CallbackType, ProgressCallback, ProgressInfo, SharedProgressCallback},
+        callback_progress::{
+            AggregateProgress, ProgressCallback, ProgressInfo, SharedProgressCallback,
+        },
+        cancellation::{is_cancelled, run_cancellable, CancelFlag},
         mame_data_types::{get_data_type_details, MameDataType},
     },
     helpers::callback_progress_helper::get_progress_info,
 };
-use reqwest::blocking::Client;
+use std::collections::HashMap;
 use std::error::Error;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
@@ -55,9 +61,47 @@ pub fn download_file(
     data_type: MameDataType,
     workspace_path: &Path,
     progress_callback: ProgressCallback,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    download_file_with_options(
+        data_type,
+        workspace_path,
+        &DownloadOptions::default(),
+        progress_callback,
+    )
+}
+
+/// Downloads a specific MAME data file using the provided `DownloadOptions`.
+///
+/// This function behaves like `download_file`, but allows the download process to be customized.
+/// When `options.overwrite` is enabled, the existence check is skipped and the file is downloaded again,
+/// replacing the existing file once the new download has been fully written.
+///
+/// # Parameters
+/// - `data_type`: The `MameDataType` that specifies which data file to download (e.g., ROMs, DAT files).
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the file will be saved.
+/// - `options`: A reference to the `DownloadOptions` that control the download process.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<PathBuf, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the path where the downloaded file is saved.
+/// - On failure: Contains an error if the download fails or there are issues accessing the URL or destination folder.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The destination folder cannot be created.
+/// - The URL cannot be retrieved for the given `MameDataType`.
+/// - The file cannot be downloaded due to network issues or write errors.
+///
+pub fn download_file_with_options(
+    data_type: MameDataType,
+    workspace_path: &Path,
+    options: &DownloadOptions,
+    progress_callback: ProgressCallback,
 ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     // Creates a folder if it does not exist.
-    let destination_folder = workspace_path.join(WORKSPACE_PATHS.download_path);
+    let destination_folder = workspace_path.join(&options.layout.download_path);
     let folder_created = ensure_folder_exists(&destination_folder);
     if let Err(err) = folder_created {
         return Err(Box::new(err));
@@ -66,25 +110,7 @@ pub fn download_file(
     // Retrieves the details for a given `MameDataType`
     let data_type_details = get_data_type_details(data_type);
 
-    // Retrieves the URL for the data type.
-    progress_callback(get_progress_info(
-        format!("Searching URL for {}", data_type_details.name).as_str(),
-    ));
-
-    let download_url =
-        match get_data_source(&data_type_details.source, &data_type_details.source_match) {
-            Ok(url) => url,
-            Err(err) => {
-                progress_callback(ProgressInfo {
-                    progress: 0,
-                    total: 0,
-                    message: format!("Couldn't find URL for {}", data_type_details.name),
-                    callback_type: CallbackType::Error,
-                });
-
-                return Err(err.into());
-            }
-        };
+    let download_url = resolve_download_url(data_type, options, &progress_callback)?;
 
     // Checks if the file already exists.
     let file_name = get_file_name_from_url(&download_url);
@@ -94,13 +120,11 @@ pub fn download_file(
         format!("Checking if file {} already exists", file_name).as_str(),
     ));
 
-    if Path::new(&file_path).exists() {
-        progress_callback(ProgressInfo {
-            progress: 0,
-            total: 0,
-            message: format!("{} already exists", file_name),
-            callback_type: CallbackType::Finish,
-        });
+    if !options.overwrite && Path::new(&file_path).exists() {
+        progress_callback(ProgressInfo::finish(format!(
+            "{} already exists",
+            file_name
+        )));
 
         return Ok(file_path);
     }
@@ -110,7 +134,195 @@ pub fn download_file(
         format!("Downloading {} file", data_type_details.name).as_str(),
     ));
 
-    download(&download_url, &destination_folder, progress_callback)
+    download(
+        &download_url,
+        &destination_folder,
+        options,
+        progress_callback,
+    )
+}
+
+/// Downloads a specific MAME data file into the provided writer.
+///
+/// This function resolves the URL of the given `MameDataType` and streams the downloaded data into `out`
+/// instead of a file of the workspace, e.g. to keep it in memory or to upload it to a cloud storage
+/// in environments without a writable filesystem.
+///
+/// # Parameters
+/// - `data_type`: The `MameDataType` that specifies which data file to download (e.g., ROMs, DAT files).
+/// - `out`: The writer the downloaded data is written to.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<u64, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the number of bytes written to `out`.
+/// - On failure: Contains an error if the download fails or the data cannot be written.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The URL cannot be retrieved for the given `MameDataType`.
+/// - The file cannot be downloaded due to network issues, or is incomplete.
+/// - The data cannot be written to `out`. Data already written is not rolled back.
+///
+pub fn download_to_writer(
+    data_type: MameDataType,
+    out: &mut dyn Write,
+    progress_callback: ProgressCallback,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    download_to_writer_with_options(
+        data_type,
+        out,
+        &DownloadOptions::default(),
+        progress_callback,
+    )
+}
+
+/// Downloads a specific MAME data file into the provided writer using the provided `DownloadOptions`.
+///
+/// This function behaves like `download_to_writer`, honoring the `source_overrides` and `cancel_flag` of the options.
+/// The options related to the workspace, such as `overwrite` and `layout`, are ignored.
+///
+/// # Parameters
+/// - `data_type`: The `MameDataType` that specifies which data file to download (e.g., ROMs, DAT files).
+/// - `out`: The writer the downloaded data is written to.
+/// - `options`: A reference to the `DownloadOptions` that control the download process.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<u64, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the number of bytes written to `out`.
+/// - On failure: Contains an error if the download fails, is cancelled, or the data cannot be written.
+///
+pub fn download_to_writer_with_options(
+    data_type: MameDataType,
+    out: &mut dyn Write,
+    options: &DownloadOptions,
+    progress_callback: ProgressCallback,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let download_url = resolve_download_url(data_type, options, &progress_callback)?;
+    let file_name = get_file_name_from_url(&download_url);
+
+    progress_callback(get_progress_info(
+        format!("Downloading {} file", get_data_type_details(data_type).name).as_str(),
+    ));
+
+    let downloaded = stream_download(&download_url, &file_name, out, options, &progress_callback)?;
+
+    progress_callback(ProgressInfo {
+        message: format!("{} downloaded successfully", file_name),
+        ..ProgressInfo::progress(downloaded, downloaded)
+    });
+
+    Ok(downloaded)
+}
+
+/// Checks whether a newer version of a MAME data file than the one in the workspace is available.
+///
+/// This function resolves the download URL of the data type and extracts the version from its file name
+/// (e.g., `271` for `MAME_Dats_271.7z`). The version is compared numerically against the version unpacked in the
+/// workspace, taken from the name of the unpacked data file when it holds one (e.g., `MAME 0.271.dat`) or else from
+/// the highest version of the downloaded archives, so an unchanged release can be skipped without downloading it.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory of the workspace.
+/// - `data_type`: The `MameDataType` to check.
+///
+/// # Returns
+/// Returns a `Result<Option<String>, Box<dyn Error + Send + Sync>>`:
+/// - `Some(version)`: The published version is newer than the local one, or no version is found in the workspace.
+/// - `None`: The local version is up to date.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The URL cannot be retrieved for the given `MameDataType`.
+/// - No version can be extracted from the name of the published file.
+///
+pub fn is_update_available(
+    workspace_path: &Path,
+    data_type: MameDataType,
+) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    is_update_available_with_options(workspace_path, data_type, &DownloadOptions::default())
+}
+
+/// Checks whether a newer version of a MAME data file is available using the provided `DownloadOptions`.
+///
+/// This function behaves like `is_update_available`, honoring the `layout` and `source_overrides` of the options.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory of the workspace.
+/// - `data_type`: The `MameDataType` to check.
+/// - `options`: A reference to the `DownloadOptions` used to locate the downloaded archive and the published file.
+///
+/// # Returns
+/// Returns a `Result<Option<String>, Box<dyn Error + Send + Sync>>` with the newer version, if any.
+///
+pub fn is_update_available_with_options(
+    workspace_path: &Path,
+    data_type: MameDataType,
+    options: &DownloadOptions,
+) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    let progress_callback: ProgressCallback = Box::new(|_| {});
+    let download_url = resolve_download_url(data_type, options, &progress_callback)?;
+    let file_name = get_file_name_from_url(&download_url);
+
+    let remote_version = get_version_from_file_name(&file_name)
+        .ok_or_else(|| format!("Couldn't find the version of {}", file_name))?;
+
+    match get_local_version(workspace_path, data_type, &options.layout) {
+        Some(local_version) if !is_newer_version(&remote_version, &local_version) => Ok(None),
+        _ => Ok(Some(remote_version)),
+    }
+}
+
+/// Detects the version of a data type available in the workspace.
+///
+/// The version is taken from the name of the unpacked data file when it holds one (e.g. `MAME 0.271.dat`).
+/// Otherwise, it is the highest version among the downloaded archives of the data type.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory of the workspace.
+/// - `data_type`: The `MameDataType` whose version is detected.
+/// - `layout`: The `WorkspaceLayout` with the subfolders of the archives and of the unpacked files.
+///
+/// # Returns
+/// Returns an `Option<String>` with the local version, or `None` if no version is found.
+///
+fn get_local_version(
+    workspace_path: &Path,
+    data_type: MameDataType,
+    layout: &WorkspaceLayout,
+) -> Option<String> {
+    let data_type_details = get_data_type_details(data_type);
+    let highest_version = |folder: PathBuf, pattern| {
+        find_files_with_pattern(&folder.to_string_lossy(), pattern)
+            .iter()
+            .filter_map(|file_path| {
+                Path::new(file_path)
+                    .file_name()
+                    .and_then(|file_name| get_version_from_file_name(&file_name.to_string_lossy()))
+            })
+            .max_by(|version, other_version| compare_versions(version, other_version))
+    };
+
+    // The names of the software lists are not versions
+    let unpacked_version = if data_type == MameDataType::Software {
+        None
+    } else {
+        highest_version(
+            workspace_path
+                .join(&layout.extract_path)
+                .join(data_type_details.name.to_lowercase()),
+            &data_type_details.data_file_pattern,
+        )
+    };
+
+    unpacked_version.or_else(|| {
+        highest_version(
+            workspace_path.join(&layout.download_path),
+            &data_type_details.zip_file_pattern,
+        )
+    })
 }
 
 /// Downloads multiple files concurrently, with progress updates for each file.
@@ -145,6 +357,36 @@ pub fn download_file(
 pub fn download_files(
     workspace_path: &Path,
     progress_callback: SharedProgressCallback,
+) -> Vec<thread::JoinHandle<Result<PathBuf, Box<dyn Error + Send + Sync>>>> {
+    download_files_with_options(
+        workspace_path,
+        &DownloadOptions::default(),
+        progress_callback,
+    )
+}
+
+/// Downloads multiple files concurrently using the provided `DownloadOptions`.
+///
+/// This function behaves like `download_files`, passing the given options to `download_file_with_options`
+/// for every data type. For example, enabling `options.overwrite` downloads every file again.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the files will be saved.
+/// - `options`: A reference to the `DownloadOptions` applied to every download.
+/// - `progress_callback`: A callback function of type `SharedProgressCallback` that tracks the progress of each file download.
+///   The callback receives the `data_type` and a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Vec<thread::JoinHandle<Result<PathBuf, Box<dyn Error + Send + Sync>>>>`:
+/// - Each handle represents a thread responsible for downloading a specific file. The result of the download can be accessed
+///   by joining the thread handle.
+/// - On success: Each thread handle contains the path where the downloaded file is saved.
+/// - On failure: Each thread handle contains an error if the download fails or if there are issues saving the file.
+///
+pub fn download_files_with_options(
+    workspace_path: &Path,
+    options: &DownloadOptions,
+    progress_callback: SharedProgressCallback,
 ) -> Vec<thread::JoinHandle<Result<PathBuf, Box<dyn Error + Send + Sync>>>> {
     let progress_callback = Arc::clone(&progress_callback);
 
@@ -152,26 +394,131 @@ pub fn download_files(
         .iter()
         .map(|&data_type| {
             let workspace_path = workspace_path.to_path_buf();
+            let options = options.clone();
             let progress_callback = Arc::clone(&progress_callback);
 
+            let aggregate_progress = options.aggregate_progress.clone();
+
             thread::spawn(move || {
-                download_file(
-                    data_type,
-                    &workspace_path,
-                    Box::new(move |progress_info| {
-                        progress_callback(data_type, progress_info);
-                    }),
-                )
+                run_cancellable(&options.cancel_flag, || {
+                    download_file_with_options(
+                        data_type,
+                        &workspace_path,
+                        &options,
+                        Box::new(move |progress_info| {
+                            if let Some(aggregate_progress) = &aggregate_progress {
+                                aggregate_progress.update(data_type, &progress_info);
+                            }
+                            progress_callback(data_type, progress_info);
+                        }),
+                    )
+                })
             })
         })
         .collect()
 }
 
+/// Represents the options that control how data files are downloaded.
+///
+/// The `DownloadOptions` struct is passed to `download_file_with_options` and `download_files_with_options`
+/// to customize the download process. The default options reproduce the behavior of `download_file`.
+/// The options can be set field by field, or with the `with_*` methods, e.g. `DownloadOptions::default().with_overwrite(true)`.
+///
+/// # Fields
+/// - `overwrite`: When `true`, downloads the file again even if it already exists, replacing the existing file.
+/// - `layout`: The `WorkspaceLayout` with the subfolder where the files are downloaded.
+/// - `source_overrides`: The `SourceOverride` of each data type whose source page or link match has to be replaced,
+///   e.g. to work around a change in the structure of the source site. Data types not present use their built-in details.
+/// - `cancel_flag`: An optional `CancelFlag` shared by the downloads of a batch. When it is set, the ongoing downloads
+///   stop and the pending ones are skipped; a failed download of `download_files_with_options` sets it.
+/// - `aggregate_progress`: An optional `AggregateProgress` where `download_files_with_options` sums the bytes downloaded
+///   and the total size of all the files, for a single progress bar covering every download.
+/// - `max_bytes`: An optional maximum size, in bytes, of each download. A download announcing or receiving more bytes
+///   is aborted, its partial file is deleted, and a `CallbackType::Error` update is sent. Guards unattended runs against
+///   runaway responses.
+///
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    /// Downloads the file again even if it already exists in the download folder.
+    pub overwrite: bool,
+    /// The subfolders used inside the workspace.
+    pub layout: WorkspaceLayout,
+    /// Overrides the source of the data types present in the map.
+    pub source_overrides: HashMap<MameDataType, SourceOverride>,
+    /// Cancels the downloads sharing the flag when set.
+    pub cancel_flag: Option<CancelFlag>,
+    /// Sums the progress of all the downloads of a batch.
+    pub aggregate_progress: Option<AggregateProgress>,
+    /// Aborts the downloads larger than this number of bytes.
+    pub max_bytes: Option<u64>,
+}
+
+impl DownloadOptions {
+    /// Sets `overwrite`, to download the files that already exist again.
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Sets the `WorkspaceLayout` with the subfolder where the files are downloaded.
+    pub fn with_layout(mut self, layout: WorkspaceLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Adds the `SourceOverride` of a data type to `source_overrides`, replacing any previous one.
+    pub fn with_source_override(
+        mut self,
+        data_type: MameDataType,
+        source_override: SourceOverride,
+    ) -> Self {
+        self.source_overrides.insert(data_type, source_override);
+        self
+    }
+
+    /// Sets the `CancelFlag` shared by the downloads.
+    pub fn with_cancel_flag(mut self, cancel_flag: CancelFlag) -> Self {
+        self.cancel_flag = Some(cancel_flag);
+        self
+    }
+
+    /// Sets the `AggregateProgress` summing the progress of the downloads of a batch.
+    pub fn with_aggregate_progress(mut self, aggregate_progress: AggregateProgress) -> Self {
+        self.aggregate_progress = Some(aggregate_progress);
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of each download.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+}
+
+/// Represents the replacement of the built-in source of a data type.
+///
+/// The `SourceOverride` struct is used in the `source_overrides` of `DownloadOptions`.
+/// Each field left as `None` keeps the built-in value from the details of the data type.
+///
+/// # Fields
+/// - `source`: The URL of the page where the download link is searched.
+/// - `source_match`: The substring used to find the download link in the page.
+///
+#[derive(Debug, Clone, Default)]
+pub struct SourceOverride {
+    /// The URL of the page where the download link is searched.
+    pub source: Option<String>,
+    /// The substring used to find the download link in the page.
+    pub source_match: Option<String>,
+}
+
 /// Downloads a file from the given URL and saves it to the specified destination folder.
 ///
 /// This function fetches the content from the provided URL, saves it to the given destination folder,
 /// and optionally provides progress updates via a callback function. The function is designed to handle
 /// large files by streaming the data in chunks and supports tracking download progress.
+/// The data is written to a temporary `.part` file which is renamed once the download completes, so an
+/// interrupted download never leaves a partial file under the final name.
 ///
 /// # Parameters
 /// - `url`: A string slice (`&str`) representing the URL of the file to download. For example:
@@ -200,39 +547,303 @@ pub fn download_files(
 fn download(
     url: &str,
     destination_folder: &Path,
+    options: &DownloadOptions,
     progress_callback: ProgressCallback,
 ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     let file_name = get_file_name_from_url(url);
 
-    let mut response = Client::new().get(url).send()?;
-    let total_size = response.content_length().unwrap_or(0);
-    let mut downloaded: u64 = 0;
-    let mut buffer = [0; 4096];
-
     let file_path = destination_folder.join(file_name.clone());
-    let mut file = File::create(&file_path)?;
+    let temp_file_path = destination_folder.join(format!("{}.part", file_name));
+    let mut file = File::create(&temp_file_path)?;
 
-    while let Ok(bytes_read) = response.read(&mut buffer) {
-        if bytes_read == 0 {
-            break;
+    let downloaded = match stream_download(url, &file_name, &mut file, options, &progress_callback)
+    {
+        Ok(downloaded) => downloaded,
+        Err(err) => {
+            drop(file);
+            let _ = fs::remove_file(&temp_file_path);
+            return Err(err);
         }
-        file.write_all(&buffer[..bytes_read])?;
-        downloaded += bytes_read as u64;
+    };
+    drop(file);
 
-        progress_callback(ProgressInfo {
-            progress: downloaded,
-            total: total_size,
-            message: String::from(""),
-            callback_type: CallbackType::Progress,
-        });
-    }
+    // Replaces the existing file only once the download is complete
+    fs::rename(&temp_file_path, &file_path)?;
 
     progress_callback(ProgressInfo {
-        progress: downloaded,
-        total: downloaded,
         message: format!("{} downloaded successfully", file_name),
-        callback_type: CallbackType::Progress,
+        ..ProgressInfo::progress(downloaded, downloaded)
     });
 
     Ok(file_path)
 }
+
+/// Streams the body of the given URL into a writer, returning the number of bytes written.
+///
+/// Progress is reported after each chunk. The download stops when the cancel flag of the options is set,
+/// fails when more bytes than the `max_bytes` of the options are announced or received, and fails
+/// when fewer bytes than the announced content length are received.
+/// Responses with a `gzip` or `deflate` `Content-Encoding` are decoded, so the raw archive bytes are written.
+fn stream_download(
+    url: &str,
+    file_name: &str,
+    out: &mut dyn Write,
+    options: &DownloadOptions,
+    progress_callback: &ProgressCallback,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    // The decompression of gzip and deflate encoded responses is explicitly enabled, so the content length
+    // isn't known for them and only the decoded bytes are counted
+    let mut response = http_client()?.get(url).send()?;
+    let content_length = response.content_length();
+
+    if let (Some(max_bytes), Some(content_length)) = (options.max_bytes, content_length) {
+        if content_length > max_bytes {
+            return Err(max_bytes_exceeded(file_name, max_bytes, progress_callback));
+        }
+    }
+
+    copy_download(
+        &mut response,
+        content_length,
+        file_name,
+        out,
+        options,
+        progress_callback,
+    )
+}
+
+/// Copies a download body into a writer, returning the number of bytes written.
+///
+/// # Parameters
+/// - `body`: The reader of the download body.
+/// - `content_length`: The announced length of the body, if known.
+/// - `file_name`: The name of the downloaded file, used in the error messages.
+/// - `out`: The writer receiving the body.
+/// - `options`: The `DownloadOptions` with the cancel flag and the maximum size of the download.
+/// - `progress_callback`: A reference to the callback receiving the progress updates.
+///
+/// # Returns
+/// Returns the number of bytes written, or an error if reading the body fails, the download is cancelled,
+/// exceeds `max_bytes` or ends before the announced content length.
+fn copy_download(
+    body: &mut dyn Read,
+    content_length: Option<u64>,
+    file_name: &str,
+    out: &mut dyn Write,
+    options: &DownloadOptions,
+    progress_callback: &ProgressCallback,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    let total_size = content_length.unwrap_or(0);
+    let mut downloaded: u64 = 0;
+    let mut buffer = [0; 4096];
+
+    loop {
+        // A read error is not the end of the body: without a content length, it would go unnoticed
+        let bytes_read = match body.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => bytes_read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                let message = format!("Download of {} failed: {}", file_name, err);
+                progress_callback(ProgressInfo::error(message.clone()));
+                return Err(message.into());
+            }
+        };
+        if is_cancelled(&options.cancel_flag) {
+            return Err(format!("Download of {} cancelled", file_name).into());
+        }
+        downloaded += bytes_read as u64;
+        if let Some(max_bytes) = options.max_bytes {
+            if downloaded > max_bytes {
+                return Err(max_bytes_exceeded(file_name, max_bytes, progress_callback));
+            }
+        }
+        out.write_all(&buffer[..bytes_read])?;
+
+        progress_callback(ProgressInfo::progress(downloaded, total_size));
+    }
+
+    out.flush()?;
+
+    // Checks the download was not silently truncated by the server
+    if let Some(expected_size) = content_length {
+        if downloaded != expected_size {
+            let message = format!(
+                "Download of {} is incomplete: received {} of {} bytes",
+                file_name, downloaded, expected_size
+            );
+            progress_callback(ProgressInfo::error(message.clone()));
+            return Err(message.into());
+        }
+    }
+
+    Ok(downloaded)
+}
+
+/// Reports that a download exceeds the maximum size allowed, returning the error to return.
+fn max_bytes_exceeded(
+    file_name: &str,
+    max_bytes: u64,
+    progress_callback: &ProgressCallback,
+) -> Box<dyn Error + Send + Sync> {
+    let message = format!(
+        "Download of {} aborted: it exceeds the maximum size of {} bytes",
+        file_name, max_bytes
+    );
+    progress_callback(ProgressInfo::error(message.clone()));
+
+    message.into()
+}
+
+/// Resolves the download URL of a data type, using the source overridden in the options if any.
+fn resolve_download_url(
+    data_type: MameDataType,
+    options: &DownloadOptions,
+    progress_callback: &ProgressCallback,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    // Retrieves the details for a given `MameDataType`
+    let data_type_details = get_data_type_details(data_type);
+
+    // Retrieves the URL for the data type.
+    progress_callback(get_progress_info(
+        format!("Searching URL for {}", data_type_details.name).as_str(),
+    ));
+
+    // Uses the source overridden by the caller, if any
+    let source_override = options.source_overrides.get(&data_type);
+    let source = source_override
+        .and_then(|source_override| source_override.source.as_deref())
+        .unwrap_or(data_type_details.source);
+    let source_match = source_override
+        .and_then(|source_override| source_override.source_match.as_deref())
+        .unwrap_or(data_type_details.source_match);
+
+    match get_data_source(source, source_match) {
+        Ok(url) => Ok(url),
+        Err(err) => {
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't find URL for {}",
+                data_type_details.name
+            )));
+
+            Err(err.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_get_local_version() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_local_version");
+        let layout = WorkspaceLayout::default();
+        let download_folder = workspace_path.join(&layout.download_path);
+        let extract_folder = workspace_path.join(&layout.extract_path).join("mame");
+        fs::create_dir_all(&download_folder).unwrap();
+        fs::create_dir_all(&extract_folder).unwrap();
+
+        assert_eq!(
+            get_local_version(&workspace_path, MameDataType::Mame, &layout),
+            None
+        );
+
+        // The highest archive is used, not the first one found
+        for file_name in ["MAME_Dats_99.7z", "MAME_Dats_100.7z", "MAME_Dats_0098.7z"] {
+            fs::write(download_folder.join(file_name), b"").unwrap();
+        }
+        assert_eq!(
+            get_local_version(&workspace_path, MameDataType::Mame, &layout).as_deref(),
+            Some("100")
+        );
+
+        // The unpacked data file takes precedence over the archives
+        fs::write(extract_folder.join("MAME 0.99.dat"), b"").unwrap();
+        assert_eq!(
+            get_local_version(&workspace_path, MameDataType::Mame, &layout).as_deref(),
+            Some("99")
+        );
+
+        fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_copy_download_fails_on_read_error() {
+        // Returns a chunk, then fails as a connection reset in the middle of a chunked response
+        struct FailingReader {
+            chunks_left: usize,
+        }
+
+        impl Read for FailingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.chunks_left == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionReset,
+                        "connection reset",
+                    ));
+                }
+                self.chunks_left -= 1;
+                buf[..4].copy_from_slice(b"PK\x03\x04");
+                Ok(4)
+            }
+        }
+
+        let mut out = Vec::new();
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let result = copy_download(
+            &mut FailingReader { chunks_left: 2 },
+            None,
+            "pS_Languages_268.zip",
+            &mut out,
+            &DownloadOptions::default(),
+            &progress_callback,
+        );
+
+        assert!(result.unwrap_err().to_string().contains("connection reset"));
+        assert_eq!(out.len(), 8);
+    }
+
+    #[test]
+    fn test_stream_download_decodes_gzip_content_encoding() {
+        // Raw bytes of a ZIP archive, served gzipped by the mirror
+        let archive = b"PK\x03\x04 raw archive bytes".to_vec();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&archive).unwrap();
+        let body = encoder.finish().unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/pS_Languages_268.zip",
+            listener.local_addr().unwrap()
+        );
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(&body).unwrap();
+        });
+
+        let mut out = Vec::new();
+        let downloaded = stream_download(
+            &url,
+            "pS_Languages_268.zip",
+            &mut out,
+            &DownloadOptions::default(),
+            &(Box::new(|_| {}) as ProgressCallback),
+        )
+        .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(out, archive);
+        assert_eq!(downloaded, archive.len() as u64);
+    }
+}
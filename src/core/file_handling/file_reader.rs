@@ -1,17 +1,27 @@
-use crate::helpers::file_system_helpers::{find_file_with_pattern, WORKSPACE_PATHS};
+use crate::helpers::file_system_helpers::{
+    find_file_with_pattern, find_files_with_pattern, WorkspaceLayout, DEFAULT_BUFFER_SIZE,
+    MIN_BUFFER_SIZE,
+};
 use crate::{
     core::models::{
-        callback_progress::{CallbackType, ProgressCallback, ProgressInfo, SharedProgressCallback},
+        callback_progress::{ProgressCallback, ProgressInfo, SharedProgressCallback},
+        cancellation::{run_unless_cancelled, with_cancel_flag, CancelFlag},
         core_models::Machine,
         mame_data_types::{get_data_type_details, MameDataType},
     },
+    core::readers::{mame_reader, software_list_reader},
     helpers::callback_progress_helper::get_progress_info,
 };
+use std::any::Any;
 use std::collections::HashMap;
 use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 /// Reads and processes a specific MAME data file based on the provided data type.
 ///
@@ -20,6 +30,9 @@ use std::thread;
 /// data file is present in the expected location and then reads the file using a specialized function
 /// for the provided `MameDataType`. Progress updates and messages are provided via a callback function.
 ///
+/// The software lists are shipped as one file per list (`hash/nes.xml`, `hash/snes.xml`, ...), so for
+/// `MameDataType::Software` every software list file of the extract folder is read, and their titles are merged.
+///
 /// # Parameters
 /// - `data_type`: The `MameDataType` that specifies which type of MAME data file to read (e.g., ROMs, DAT files).
 /// - `workspace_path`: A reference to a `Path` representing the base directory where the data file is located.
@@ -51,12 +64,43 @@ pub fn read_file(
     data_type: MameDataType,
     workspace_path: &Path,
     progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    read_file_with_options(
+        data_type,
+        workspace_path,
+        &ReadOptions::default(),
+        progress_callback,
+    )
+}
+
+/// Reads and processes a specific MAME data file using the provided `ReadOptions`.
+///
+/// This function behaves like `read_file`, but allows the reading process to be customized.
+/// For example, `options.layout` sets the subfolder where the data file is searched.
+///
+/// # Parameters
+/// - `data_type`: The `MameDataType` that specifies which type of MAME data file to read (e.g., ROMs, DAT files).
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the data file is located.
+/// - `options`: A reference to the `ReadOptions` that control the reading process.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains a `HashMap` where the keys are machine names and the values are `Machine` structs representing detailed information about each MAME machine.
+/// - On failure: Contains an error if the data file is not found, cannot be read, or if there are issues accessing the file system.
+///
+pub fn read_file_with_options(
+    data_type: MameDataType,
+    workspace_path: &Path,
+    options: &ReadOptions,
+    progress_callback: ProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
     // Retrieves the details for a given `MameDataType`
     let data_type_details = get_data_type_details(data_type);
     // Set path where data file is located
     let extract_folder = workspace_path
-        .join(WORKSPACE_PATHS.extract_path)
+        .join(&options.layout.extract_path)
         .join(data_type_details.name.to_lowercase());
 
     // Checks if data file is present in the extract folder
@@ -74,23 +118,98 @@ pub fn read_file(
     );
 
     if let Err(err) = existing_data_file {
-        progress_callback(ProgressInfo {
-            progress: 0,
-            total: 0,
-            message: format!("Data file for {} not found", data_type_details.name),
-            callback_type: CallbackType::Error,
-        });
+        progress_callback(ProgressInfo::error(format!(
+            "Data file for {} not found",
+            data_type_details.name
+        )));
 
         return Err(err.into());
     }
 
     let file_path = existing_data_file.unwrap();
 
-    let machines = (data_type_details.read_function)(&file_path, progress_callback)?;
+    // Smaller buffers would be read as an empty file
+    let buffer_size = options.buffer_size.max(MIN_BUFFER_SIZE);
+
+    // The readers stop at their next check once the cancel flag is set
+    let mut machines = with_cancel_flag(&options.cancel_flag, || {
+        if data_type == MameDataType::Software {
+            // The software lists are split into one file per list
+            let file_paths = find_files_with_pattern(
+                extract_folder.to_str().unwrap(),
+                &data_type_details.data_file_pattern,
+            );
+            software_list_reader::read_software_list_files(
+                &file_paths,
+                buffer_size,
+                progress_callback,
+            )
+        } else if options.tolerate_truncation && data_type == MameDataType::Mame {
+            mame_reader::read_mame_file_tolerant(&file_path, buffer_size, progress_callback)
+        } else {
+            (data_type_details.read_function)(&file_path, buffer_size, progress_callback)
+        }
+    })?;
+
+    if options.normalize_hashes {
+        for machine in machines.values_mut() {
+            machine.normalize_hashes();
+        }
+    }
+
+    if options.lowercase_names {
+        return Ok(lowercase_machine_names(machines));
+    }
 
     Ok(machines)
 }
 
+/// Reads and processes a data file for a specific `MameDataType`, returning the time spent reading it.
+///
+/// This function behaves like `read_file_with_options`, and measures the time taken to find and parse the data file,
+/// to profile a reader on its own. Use `read_files_timed` to measure every reader of a batch. The crate ships no
+/// benchmark target: these functions are the supported way to measure the readers on real data files.
+///
+/// # Parameters
+/// - `data_type`: The `MameDataType` that specifies the type of data file to read.
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the data file is located.
+/// - `options`: A reference to the `ReadOptions` that control the reading process.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<ReadOutcome, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the `ReadOutcome` with the machines read and the time spent reading them.
+/// - On failure: Contains an error like `read_file_with_options`.
+///
+pub fn read_file_timed(
+    data_type: MameDataType,
+    workspace_path: &Path,
+    options: &ReadOptions,
+    progress_callback: ProgressCallback,
+) -> Result<ReadOutcome, Box<dyn Error + Send + Sync>> {
+    let start = Instant::now();
+    let machines = read_file_with_options(data_type, workspace_path, options, progress_callback)?;
+
+    Ok(ReadOutcome {
+        machines,
+        duration: start.elapsed(),
+    })
+}
+
+/// Represents the result of a timed read of a data file, as returned by `read_file_timed`.
+///
+/// # Fields
+/// - `machines`: The machines read from the data file, by name.
+/// - `duration`: The time spent finding and parsing the data file.
+///
+#[derive(Debug, Clone)]
+pub struct ReadOutcome {
+    /// The machines read from the data file.
+    pub machines: HashMap<String, Machine>,
+    /// The time spent reading the data file.
+    pub duration: Duration,
+}
+
 /// Reads and processes all MAME data files available for the specified workspace path.
 ///
 /// This function manages the concurrent reading of multiple MAME data files. For each `MameDataType`,
@@ -98,6 +217,9 @@ pub fn read_file(
 /// to complete and then combines the results into a single `HashMap` of machine details. Progress updates
 /// and messages are provided via a shared callback function.
 ///
+/// `MameDataType::Software` is not part of `MameDataType::all_variants`, as software lists contain software titles
+/// rather than machines. Use `read_file` to read them separately.
+///
 /// # Parameters
 /// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
 /// - `progress_callback`: A shared callback function of type `SharedProgressCallback` that tracks progress and provides status updates.
@@ -106,18 +228,22 @@ pub fn read_file(
 /// # Returns
 /// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
 /// - On success: Contains a `HashMap` where the keys are machine names and the values are `Machine` structs representing detailed information about each MAME machine.
-/// - On failure: Contains an error if any data file cannot be read, or if there are issues joining the threads.
+/// - On failure: Contains the first read error, or an error if the thread reading any data file panicked.
 ///
 /// # Errors
 /// This function will return an error if:
-/// - Any thread fails to complete successfully or panics.
+/// - Any data file is missing or cannot be read, as the default `ErrorPolicy` is `ErrorPolicy::FailFast`.
+///   Use `read_files_with_errors` with `ErrorPolicy::ContinueLossy` to read the available data files and
+///   get the errors of the others.
+/// - Any thread panics. The error names the data types whose reader panicked, which are also reported
+///   as `CallbackType::Error` updates.
 /// - There are issues reading any data file due to permission problems, file corruption, or missing files.
 /// - There are errors during the merging of machine data, such as data inconsistencies.
 ///
 /// # Concurrency
 /// This function uses multiple threads to read MAME data files concurrently. Each thread handles the reading of a specific
-/// data type file (`MameDataType`). The function waits for all threads to complete using `join()`, and any errors encountered
-/// are captured and logged. The shared progress callback is used to provide real-time updates across all threads.
+/// data type file (`MameDataType`). The first read error is returned as soon as it happens and cancels the other
+/// readers, and panics are returned as an error once every thread has been joined. The shared progress callback is used to provide real-time updates across all threads.
 ///
 /// # Callback
 /// The shared progress callback function provides real-time updates on the reading process for each data type and other status information. It receives:
@@ -133,48 +259,982 @@ pub fn read_files(
     workspace_path: &Path,
     progress_callback: SharedProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    read_files_with_options(workspace_path, &ReadOptions::default(), progress_callback)
+}
+
+/// Reads and processes all MAME data files available for the specified workspace path using the provided `ReadOptions`.
+///
+/// This function behaves like `read_files`, but allows the reading process to be customized.
+/// For example, enabling `options.normalize_languages` normalizes the languages of every machine once
+/// the data files have been merged.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
+/// - `options`: A reference to the `ReadOptions` that control the reading process.
+/// - `progress_callback`: A shared callback function of type `SharedProgressCallback` that tracks progress and provides status updates.
+///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains a `HashMap` where the keys are machine names and the values are `Machine` structs representing detailed information about each MAME machine.
+/// - On failure: Contains an error if any data file cannot be read with `ErrorPolicy::FailFast`, or if there are issues joining the threads.
+///
+pub fn read_files_with_options(
+    workspace_path: &Path,
+    options: &ReadOptions,
+    progress_callback: SharedProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    read_files_with_errors(workspace_path, options, progress_callback).map(|(machines, _)| machines)
+}
+
+/// Reads and processes all MAME data files available for the specified workspace path, returning the read errors
+/// along with the machines.
+///
+/// This function behaves like `read_files_with_options`. With `ErrorPolicy::ContinueLossy`, the data files that
+/// cannot be read are skipped and their errors are returned alongside the merged machines, so the caller can tell
+/// a complete dataset from a partial one. With `ErrorPolicy::FailFast`, the returned list of errors is always empty.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
+/// - `options`: A reference to the `ReadOptions` that control the reading process.
+/// - `progress_callback`: A shared callback function of type `SharedProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<(HashMap<String, Machine>, ReadErrors), Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the merged machines, and the data types that could not be read along with their errors.
+/// - On failure: Contains the first read error with `ErrorPolicy::FailFast`, or an error if any reader thread panicked.
+///
+pub fn read_files_with_errors(
+    workspace_path: &Path,
+    options: &ReadOptions,
+    progress_callback: SharedProgressCallback,
+) -> Result<(HashMap<String, Machine>, ReadErrors), Box<dyn Error + Send + Sync>> {
+    read_files_concurrently(workspace_path, options, progress_callback)
+        .map(|(machines, errors, _)| (machines, errors))
+}
+
+/// Reads and processes all MAME data files available for the specified workspace path, returning the time
+/// spent reading each data file along with the machines.
+///
+/// This function behaves like `read_files_with_options`, and measures the time each reader takes to find and
+/// parse its data file. As the data files are read concurrently, the durations overlap: the slowest data file
+/// bounds the total reading time, which makes it the first candidate to skip. Use `read_file_timed` to time a
+/// single reader without the others competing for the disk and the CPU.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
+/// - `options`: A reference to the `ReadOptions` that control the reading process.
+/// - `progress_callback`: A shared callback function of type `SharedProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<(HashMap<String, Machine>, ReadDurations), Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the merged machines, and the reading time of every data type whose reader ran,
+///   including the ones that failed.
+/// - On failure: Contains the first read error with `ErrorPolicy::FailFast`, or an error if any reader thread panicked.
+///
+pub fn read_files_timed(
+    workspace_path: &Path,
+    options: &ReadOptions,
+    progress_callback: SharedProgressCallback,
+) -> Result<(HashMap<String, Machine>, ReadDurations), Box<dyn Error + Send + Sync>> {
+    read_files_concurrently(workspace_path, options, progress_callback)
+        .map(|(machines, _, durations)| (machines, durations))
+}
+
+/// The time spent reading the data file of each data type.
+pub type ReadDurations = HashMap<MameDataType, Duration>;
+
+/// The merged machines, the errors of the skipped data files, and the reading time of each data type.
+type ConcurrentReadResult = (HashMap<String, Machine>, ReadErrors, ReadDurations);
+
+/// Reads all the data files concurrently, one thread per data type, for the batch reading functions.
+///
+/// # Returns
+/// Returns the merged machines, the errors of the skipped data files, and the reading time of each data type.
+fn read_files_concurrently(
+    workspace_path: &Path,
+    options: &ReadOptions,
+    progress_callback: SharedProgressCallback,
+) -> Result<ConcurrentReadResult, Box<dyn Error + Send + Sync>> {
+    let options = &fail_fast_options(options);
     let progress_callback = Arc::clone(&progress_callback);
+    let durations: Arc<Mutex<ReadDurations>> = Arc::default();
+    let (sender, receiver) = mpsc::channel();
+
+    let data_types: Vec<_> = machine_data_types().collect();
+    for &data_type in &data_types {
+        let workspace_path = workspace_path.to_path_buf();
+        let options = options.clone();
+        let progress_callback = Arc::clone(&progress_callback);
+        let durations = Arc::clone(&durations);
+        let sender = sender.clone();
+
+        thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                run_unless_cancelled(&options.cancel_flag, || {
+                    let start = Instant::now();
+                    let result = read_file_with_options(
+                        data_type,
+                        &workspace_path,
+                        &options,
+                        Box::new(move |progress_info| {
+                            progress_callback(data_type, progress_info);
+                        }),
+                    );
+
+                    if let Ok(mut durations) = durations.lock() {
+                        durations.insert(data_type, start.elapsed());
+                    }
+
+                    result
+                })
+            }));
+
+            // The receiver is gone once a failing reader has been returned
+            let _ = sender.send((data_type, result));
+        });
+    }
+
+    let (mut combined_machines, errors) =
+        join_readers(receiver, data_types.len(), options, &progress_callback)?;
+
+    finish_reading(&mut combined_machines, options);
+
+    let durations = durations
+        .lock()
+        .map(|durations| durations.clone())
+        .unwrap_or_default();
+
+    Ok((combined_machines, errors, durations))
+}
+
+/// The result of a thread reading a data file, or the payload of its panic.
+type ReaderResult = thread::Result<Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>>;
+
+/// The data types whose data file could not be read, along with their errors.
+pub type ReadErrors = Vec<(MameDataType, Box<dyn Error + Send + Sync>)>;
+
+/// Returns the options with a cancel flag when they fail fast, so a failing reader cancels the running ones.
+fn fail_fast_options(options: &ReadOptions) -> ReadOptions {
+    let mut options = options.clone();
+
+    if options.on_error == ErrorPolicy::FailFast && options.cancel_flag.is_none() {
+        options.cancel_flag = Some(CancelFlag::default());
+    }
+
+    options
+}
+
+/// Handles a data file that could not be read, according to the `ErrorPolicy`.
+///
+/// With `ErrorPolicy::FailFast`, the error is returned. With `ErrorPolicy::ContinueLossy`, the error is reported
+/// as a `CallbackType::Error` update and added to `errors`.
+fn handle_read_error(
+    data_type: MameDataType,
+    err: Box<dyn Error + Send + Sync>,
+    on_error: ErrorPolicy,
+    errors: &mut ReadErrors,
+    progress_callback: &SharedProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    match on_error {
+        ErrorPolicy::FailFast => Err(err),
+        ErrorPolicy::ContinueLossy => {
+            progress_callback(
+                data_type,
+                ProgressInfo::error(format!(
+                    "Couldn't read {} data file: {}",
+                    get_data_type_details(data_type).name,
+                    err
+                )),
+            );
+            errors.push((data_type, err));
+
+            Ok(())
+        }
+    }
+}
+
+/// Receives the results of the reader threads of `read_files_with_options` and merges the machines they read.
+///
+/// The results are handled as the readers finish. A data file that cannot be read is handled according to
+/// `options.on_error`: with `ErrorPolicy::FailFast`, the first read error is returned right away and the cancel
+/// flag of the options is set, so the readers still running stop at their next check. With
+/// `ErrorPolicy::ContinueLossy`, the data file is skipped and its error is collected, and the cancel flag is left
+/// untouched. A reader thread that panics is reported through the progress callback, and makes this function return
+/// an error listing the data types that panicked once every result has been received, so no data is silently missing.
+///
+/// # Parameters
+/// - `receiver`: The `Receiver` of the data type read by each thread, along with its result.
+/// - `reader_count`: The number of reader threads sending their result.
+/// - `options`: A reference to the `ReadOptions` of the readers, with their `on_error` policy and `cancel_flag`.
+/// - `progress_callback`: A reference to the `SharedProgressCallback` where the errors and panics are reported.
+///
+/// # Returns
+/// Returns a `Result<(HashMap<String, Machine>, ReadErrors), Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the merged machines of the readers, and the errors of the skipped data files.
+/// - On failure: Contains an error if any reader thread panicked, or the first read error with `ErrorPolicy::FailFast`.
+fn join_readers(
+    receiver: Receiver<(MameDataType, ReaderResult)>,
+    reader_count: usize,
+    options: &ReadOptions,
+    progress_callback: &SharedProgressCallback,
+) -> Result<(HashMap<String, Machine>, ReadErrors), Box<dyn Error + Send + Sync>> {
+    let mut combined_machines: HashMap<String, Machine> = HashMap::new();
+    let mut errors: ReadErrors = Vec::new();
+    let mut panics: Vec<String> = Vec::new();
+
+    for (data_type, result) in receiver.iter().take(reader_count) {
+        match result {
+            Ok(Ok(machines)) => merge_machines(&mut combined_machines, machines),
+            Ok(Err(err)) => {
+                if options.on_error == ErrorPolicy::FailFast {
+                    if let Some(cancel_flag) = &options.cancel_flag {
+                        cancel_flag.store(true, Ordering::SeqCst);
+                    }
+                }
+                handle_read_error(
+                    data_type,
+                    err,
+                    options.on_error,
+                    &mut errors,
+                    progress_callback,
+                )?;
+            }
+            Err(payload) => {
+                let message = format!(
+                    "Reader for {} panicked: {}",
+                    get_data_type_details(data_type).name,
+                    panic_message(payload.as_ref())
+                );
+                progress_callback(data_type, ProgressInfo::error(message.clone()));
+                panics.push(message);
+            }
+        }
+    }
+
+    if !panics.is_empty() {
+        return Err(panics.join("; ").into());
+    }
+
+    Ok((combined_machines, errors))
+}
+
+/// Returns the message of a panic payload, when it is a string.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Reads and processes all MAME data files available for the specified workspace path, one after the other.
+///
+/// This function behaves like `read_files`, but reads each `MameDataType` in order on the calling thread
+/// instead of spawning a thread per data type. The results are combined the same way. This is useful for
+/// deterministic debugging, for profiling each reader separately, and for environments where spawning
+/// threads is restricted.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
+/// - `progress_callback`: A shared callback function of type `SharedProgressCallback` that tracks progress and provides status updates.
+///   The callback receives the `MameDataType` being read and a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains a `HashMap` where the keys are machine names and the values are `Machine` structs representing detailed information about each MAME machine.
+/// - On failure: Contains the first error of a data file that is missing or cannot be read.
+///
+pub fn read_files_sequential(
+    workspace_path: &Path,
+    progress_callback: SharedProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    read_files_sequential_with_options(workspace_path, &ReadOptions::default(), progress_callback)
+}
+
+/// Reads and processes all MAME data files available for the specified workspace path, one after the other,
+/// using the provided `ReadOptions`.
+///
+/// This function behaves like `read_files_sequential`, but allows the reading process to be customized
+/// as in `read_files_with_options`.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
+/// - `options`: A reference to the `ReadOptions` that control the reading process.
+/// - `progress_callback`: A shared callback function of type `SharedProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains a `HashMap` where the keys are machine names and the values are `Machine` structs representing detailed information about each MAME machine.
+/// - On failure: Contains an error if any data file cannot be read with `ErrorPolicy::FailFast`.
+///
+pub fn read_files_sequential_with_options(
+    workspace_path: &Path,
+    options: &ReadOptions,
+    progress_callback: SharedProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut combined_machines: HashMap<String, Machine> = HashMap::new();
+    let mut errors: ReadErrors = Vec::new();
+
+    for data_type in machine_data_types() {
+        let reader_callback = Arc::clone(&progress_callback);
 
-    let handles: Vec<_> = MameDataType::all_variants()
+        let result = run_unless_cancelled(&options.cancel_flag, || {
+            read_file_with_options(
+                data_type,
+                workspace_path,
+                options,
+                Box::new(move |progress_info| {
+                    reader_callback(data_type, progress_info);
+                }),
+            )
+        });
+
+        match result {
+            Ok(machines) => merge_machines(&mut combined_machines, machines),
+            Err(err) => handle_read_error(
+                data_type,
+                err,
+                options.on_error,
+                &mut errors,
+                &progress_callback,
+            )?,
+        }
+    }
+
+    finish_reading(&mut combined_machines, options);
+
+    Ok(combined_machines)
+}
+
+/// Returns the data types whose data files are merged into the machines.
+///
+/// Software lists describe software titles instead of machines, so they are not merged.
+fn machine_data_types() -> impl Iterator<Item = MameDataType> {
+    MameDataType::all_variants()
         .iter()
-        .map(|&data_type| {
-            let workspace_path = workspace_path.to_path_buf();
-            let progress_callback = Arc::clone(&progress_callback);
+        .copied()
+        .filter(|&data_type| data_type != MameDataType::Software)
+}
+
+/// Merges the machines read from a data file into the combined machines.
+///
+/// # Parameters
+/// - `combined_machines`: A mutable reference to the `HashMap` with the machines read so far.
+/// - `machines`: The `HashMap` with the machines read from a data file, combined by name with the existing ones.
+fn merge_machines(
+    combined_machines: &mut HashMap<String, Machine>,
+    machines: HashMap<String, Machine>,
+) {
+    for (key, new_machine) in machines {
+        combined_machines
+            .entry(key)
+            .and_modify(|existing_machine: &mut Machine| existing_machine.combine(&new_machine))
+            .or_insert(new_machine);
+    }
+}
+
+/// Lowercases the names of the machines read from a data file and their references to other machines.
+///
+/// Machines whose names only differ in case are combined into one.
+fn lowercase_machine_names(machines: HashMap<String, Machine>) -> HashMap<String, Machine> {
+    let mut lowercased_machines: HashMap<String, Machine> = HashMap::with_capacity(machines.len());
+
+    for (key, mut machine) in machines {
+        machine.name = machine.name.to_lowercase();
+        for name in [
+            &mut machine.clone_of,
+            &mut machine.rom_of,
+            &mut machine.sample_of,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *name = name.to_lowercase();
+        }
+
+        lowercased_machines
+            .entry(key.to_lowercase())
+            .and_modify(|existing_machine: &mut Machine| existing_machine.combine(&machine))
+            .or_insert(machine);
+    }
+
+    lowercased_machines
+}
+
+/// Applies the `ReadOptions` processed once all the data files have been merged.
+///
+/// # Parameters
+/// - `combined_machines`: A mutable reference to the `HashMap` with all the merged machines.
+/// - `options`: A reference to the `ReadOptions` that control the reading process.
+fn finish_reading(combined_machines: &mut HashMap<String, Machine>, options: &ReadOptions) {
+    if options.normalize_languages {
+        for machine in combined_machines.values_mut() {
+            machine.normalize_languages();
+        }
+    }
+}
+
+/// Represents the options that control how data files are read.
+///
+/// The `ReadOptions` struct is passed to `read_files_with_options` to customize the reading process.
+/// The default options reproduce the behavior of `read_files`. The options can be set field by field,
+/// or with the `with_*` methods, e.g. `ReadOptions::default().with_buffer_size(64 * 1024)`.
+///
+/// # Fields
+/// - `normalize_languages`: When `true`, the languages of every machine are normalized with `Machine::normalize_languages` after merging.
+/// - `layout`: The `WorkspaceLayout` with the subfolder where the data files are unpacked.
+/// - `cancel_flag`: An optional `CancelFlag` shared by the reading operations. When it is set, the pending files are
+///   skipped and the readers stop within the next 1000 entries. With `ErrorPolicy::FailFast`, a file that fails to be
+///   read by `read_files_with_options` sets it; with `ErrorPolicy::ContinueLossy`, it is only set by the caller.
+/// - `buffer_size`: The size, in bytes, of the read buffers used by the readers. Defaults to 8 KB;
+///   larger buffers can improve the throughput of the large data files on slow disks. Sizes below 1 KB,
+///   including `0`, are raised to 1 KB, as smaller buffers cannot hold the start of the data files.
+/// - `lowercase_names`: When `true`, the machine names (the keys of the map and `Machine.name`) and the
+///   `clone_of`, `rom_of` and `sample_of` references are lowercased as each data file is read, so data files
+///   with inconsistent casing are merged by name. Machines whose names only differ in case are combined.
+///   The references are lowercased as well so `build_rom_sets`, `classify_parent_relationship` and
+///   `recompute_parent_flags` keep resolving; names from other sources have to be lowercased before being looked up. Defaults to
+///   `false`, which keeps the canonical casing of MAME.
+/// - `on_error`: The `ErrorPolicy` applied by `read_files_with_options` and `read_files_sequential_with_options`
+///   to the data files that cannot be read. Defaults to `ErrorPolicy::FailFast`, so a missing or unreadable data file
+///   is never silently dropped; use `ErrorPolicy::ContinueLossy` with `read_files_with_errors` to read a partial workspace.
+/// - `normalize_hashes`: When `true`, the CRC and SHA-1 hashes of the ROMs, disks and resources are normalized
+///   with `Machine::normalize_hashes` as each data file is read. Defaults to `false`.
+/// - `tolerate_truncation`: When `true`, an XML parse error in the MAME data file (e.g. a file truncated by a partial
+///   download) keeps the machines read before the error instead of failing. The partial results and the approximate
+///   position of the error are reported as a `CallbackType::Error` update. Defaults to `false`, which returns the error.
+///
+#[derive(Debug, Clone)]
+pub struct ReadOptions {
+    /// Normalizes and deduplicates the languages of every machine after merging.
+    pub normalize_languages: bool,
+    /// The subfolders used inside the workspace.
+    pub layout: WorkspaceLayout,
+    /// Skips the reading operations sharing the flag when set.
+    pub cancel_flag: Option<CancelFlag>,
+    /// The size of the read buffers, in bytes. Sizes below 1 KB are raised to 1 KB.
+    pub buffer_size: usize,
+    /// Lowercases the machine names and the references to other machines.
+    pub lowercase_names: bool,
+    /// How the data files that cannot be read are handled.
+    pub on_error: ErrorPolicy,
+    /// Normalizes the hashes of the ROMs, disks and resources.
+    pub normalize_hashes: bool,
+    /// Keeps the machines read before an XML parse error in the MAME data file.
+    pub tolerate_truncation: bool,
+}
+
+/// Represents how the batch reading functions handle a data file that cannot be read.
+///
+/// # Variants
+/// - `ContinueLossy`: The data file is skipped and the machines of the other data files are returned. The error
+///   is reported as a `CallbackType::Error` update, and returned by `read_files_with_errors`.
+/// - `FailFast`: The first read error is returned, and the pending data files are cancelled.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Skips the data files that cannot be read, reporting their errors.
+    ContinueLossy,
+    /// Returns the first read error.
+    #[default]
+    FailFast,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        ReadOptions {
+            normalize_languages: false,
+            layout: WorkspaceLayout::default(),
+            cancel_flag: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            lowercase_names: false,
+            on_error: ErrorPolicy::default(),
+            normalize_hashes: false,
+            tolerate_truncation: false,
+        }
+    }
+}
+
+impl ReadOptions {
+    /// Sets `normalize_languages`, to normalize the languages of every machine after merging.
+    pub fn with_normalize_languages(mut self, normalize_languages: bool) -> Self {
+        self.normalize_languages = normalize_languages;
+        self
+    }
+
+    /// Sets the `WorkspaceLayout` with the subfolder where the data files are unpacked.
+    pub fn with_layout(mut self, layout: WorkspaceLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the `CancelFlag` shared by the reading operations.
+    pub fn with_cancel_flag(mut self, cancel_flag: CancelFlag) -> Self {
+        self.cancel_flag = Some(cancel_flag);
+        self
+    }
+
+    /// Sets the size of the read buffers, in bytes. Sizes below 1 KB are raised to 1 KB when reading.
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Sets `lowercase_names`, to lowercase the machine names and the references to other machines.
+    pub fn with_lowercase_names(mut self, lowercase_names: bool) -> Self {
+        self.lowercase_names = lowercase_names;
+        self
+    }
+
+    /// Sets the `ErrorPolicy` applied to the data files that cannot be read.
+    pub fn with_on_error(mut self, on_error: ErrorPolicy) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Sets `normalize_hashes`, to normalize the hashes of the ROMs, disks and resources.
+    pub fn with_normalize_hashes(mut self, normalize_hashes: bool) -> Self {
+        self.normalize_hashes = normalize_hashes;
+        self
+    }
+
+    /// Sets `tolerate_truncation`, to keep the machines read before an XML parse error in the MAME data file.
+    pub fn with_tolerate_truncation(mut self, tolerate_truncation: bool) -> Self {
+        self.tolerate_truncation = tolerate_truncation;
+        self
+    }
+}
+
+/// Estimates the total number of entries of every data file available in the workspace.
+///
+/// This function runs only the counting pre-pass of each reader, without processing the entries,
+/// so the totals can be known before calling `read_files` (e.g., to display an aggregated progress bar).
+/// The data files are counted concurrently, one thread per `MameDataType` merged into the machines.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
+///
+/// # Returns
+/// Returns a `HashMap<MameDataType, usize>` with the total number of entries of each data file.
+/// Data types whose data file is not present or cannot be counted are not included.
+///
+pub fn estimate_total_entries(workspace_path: &Path) -> HashMap<MameDataType, usize> {
+    estimate_total_entries_with_options(workspace_path, &ReadOptions::default())
+}
+
+/// Estimates the total number of entries of every data file available in the workspace, using the provided `ReadOptions`.
+///
+/// This function behaves like `estimate_total_entries`, but searches the data files in the subfolder
+/// set by `options.layout`, so the totals match the files read by `read_files_with_options`.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where all data files are located.
+/// - `options`: A reference to the `ReadOptions` whose `layout` sets where the data files are searched.
+///
+/// # Returns
+/// Returns a `HashMap<MameDataType, usize>` with the total number of entries of each data file.
+/// Data types whose data file is not present or cannot be counted are not included.
+///
+pub fn estimate_total_entries_with_options(
+    workspace_path: &Path,
+    options: &ReadOptions,
+) -> HashMap<MameDataType, usize> {
+    let handles: Vec<_> = machine_data_types()
+        .map(|data_type| {
+            let extract_path = workspace_path.join(&options.layout.extract_path);
 
             thread::spawn(move || {
-                read_file(
-                    data_type,
-                    &workspace_path,
-                    Box::new(move |progress_info| {
-                        progress_callback(data_type, progress_info);
-                    }),
+                let data_type_details = get_data_type_details(data_type);
+                let extract_folder = extract_path.join(data_type_details.name.to_lowercase());
+
+                let file_path = find_file_with_pattern(
+                    extract_folder.to_str().unwrap(),
+                    &data_type_details.data_file_pattern,
                 )
+                .ok()?;
+
+                (data_type_details.count_function)(&file_path)
+                    .ok()
+                    .map(|total| (data_type, total))
             })
         })
         .collect();
 
-    let mut combined_machines = HashMap::new();
-
-    for handle in handles {
-        match handle.join() {
-            Ok(Ok(machines)) => {
-                for (key, new_machine) in machines {
-                    combined_machines
-                        .entry(key)
-                        .and_modify(|existing_machine: &mut Machine| {
-                            existing_machine.combine(&new_machine)
-                        })
-                        .or_insert(new_machine);
-                }
-            }
-            Ok(Err(err)) => {
-                eprintln!("Error reading file: {:?}", err);
-            }
-            Err(err) => {
-                eprintln!("Error joining thread: {:?}", err);
-            }
+    handles
+        .into_iter()
+        .filter_map(|handle| handle.join().ok().flatten())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::file_system_helpers::WORKSPACE_PATHS;
+
+    #[test]
+    fn test_read_options_builder() {
+        let options = ReadOptions::default()
+            .with_buffer_size(64 * 1024)
+            .with_on_error(ErrorPolicy::ContinueLossy)
+            .with_normalize_hashes(true);
+
+        assert_eq!(options.buffer_size, 64 * 1024);
+        assert_eq!(options.on_error, ErrorPolicy::ContinueLossy);
+        assert!(options.normalize_hashes);
+        assert!(!options.normalize_languages);
+        assert!(options.cancel_flag.is_none());
+    }
+
+    #[test]
+    fn test_read_file_with_tiny_buffer_size() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_read_tiny_buffer");
+        let catver_path = workspace_path
+            .join(WORKSPACE_PATHS.extract_path)
+            .join("catver");
+        std::fs::create_dir_all(&catver_path).unwrap();
+        std::fs::write(
+            catver_path.join("catver.ini"),
+            "\u{feff}[Category]\npacman=Maze / Collect\n",
+        )
+        .unwrap();
+
+        for buffer_size in [0, 1, 2] {
+            let machines = read_file_with_options(
+                MameDataType::Catver,
+                &workspace_path,
+                &ReadOptions::default().with_buffer_size(buffer_size),
+                Box::new(|_| {}),
+            )
+            .unwrap();
+
+            assert_eq!(machines.len(), 1);
+            assert_eq!(machines["pacman"].category.as_deref(), Some("Maze"));
+        }
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_estimate_total_entries_with_layout() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_estimate_layout");
+        let layout = WorkspaceLayout {
+            extract_path: "unpacked".into(),
+            ..WorkspaceLayout::default()
+        };
+        let catver_path = workspace_path.join(&layout.extract_path).join("catver");
+        std::fs::create_dir_all(&catver_path).unwrap();
+        std::fs::write(
+            catver_path.join("catver.ini"),
+            "[Category]\npacman=Maze / Collect\ngalaga=Shooter / Flying Vertical\n",
+        )
+        .unwrap();
+
+        assert!(estimate_total_entries(&workspace_path).is_empty());
+
+        let totals = estimate_total_entries_with_options(
+            &workspace_path,
+            &ReadOptions::default().with_layout(layout),
+        );
+        assert_eq!(totals.len(), 1);
+        assert!(totals[&MameDataType::Catver] > 0);
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_read_file_software_lists() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_read_software_lists");
+        let hash_path = workspace_path
+            .join(WORKSPACE_PATHS.extract_path)
+            .join("software")
+            .join("hash");
+        std::fs::create_dir_all(&hash_path).unwrap();
+        for (list_name, software_name) in [("nes", "smb"), ("snes", "smw")] {
+            std::fs::write(
+                hash_path.join(format!("{}.xml", list_name)),
+                format!(
+                    r#"<?xml version="1.0"?>
+<softwarelist name="{}">
+    <software name="{}">
+        <description>Super Mario</description>
+    </software>
+</softwarelist>"#,
+                    list_name, software_name
+                ),
+            )
+            .unwrap();
         }
+
+        let software_titles =
+            read_file(MameDataType::Software, &workspace_path, Box::new(|_| {})).unwrap();
+
+        let mut keys: Vec<_> = software_titles.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["nes:smb", "snes:smw"]);
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
     }
 
-    Ok(combined_machines)
+    #[test]
+    fn test_read_files_sequential() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_read_sequential");
+        let extract_path = workspace_path.join(WORKSPACE_PATHS.extract_path);
+        std::fs::create_dir_all(extract_path.join("catver")).unwrap();
+        std::fs::create_dir_all(extract_path.join("series")).unwrap();
+        std::fs::write(
+            extract_path.join("catver").join("catver.ini"),
+            "[Category]\npacman=Maze / Collect\ngalaga=Shooter / Flying Vertical\n",
+        )
+        .unwrap();
+        std::fs::write(
+            extract_path.join("series").join("series.ini"),
+            "[Pac-Man]\npacman\n",
+        )
+        .unwrap();
+
+        // The other data files are missing
+        let options = ReadOptions::default().with_on_error(ErrorPolicy::ContinueLossy);
+        let progress_callback: SharedProgressCallback = Arc::new(|_, _| {});
+        let machines = read_files_sequential_with_options(
+            &workspace_path,
+            &options,
+            Arc::clone(&progress_callback),
+        )
+        .unwrap();
+
+        assert_eq!(machines.len(), 2);
+        let pacman = &machines["pacman"];
+        assert_eq!(pacman.category.as_deref(), Some("Maze"));
+        assert_eq!(pacman.series.as_deref(), Some("Pac-Man"));
+
+        let threaded_machines =
+            read_files_with_options(&workspace_path, &options, progress_callback).unwrap();
+        assert_eq!(threaded_machines.len(), machines.len());
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_read_files_timed() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_read_timed");
+        let extract_path = workspace_path.join(WORKSPACE_PATHS.extract_path);
+        std::fs::create_dir_all(extract_path.join("series")).unwrap();
+        std::fs::write(
+            extract_path.join("series").join("series.ini"),
+            "[Pac-Man]\npacman\n",
+        )
+        .unwrap();
+
+        let progress_callback: SharedProgressCallback = Arc::new(|_, _| {});
+        let options = ReadOptions::default().with_on_error(ErrorPolicy::ContinueLossy);
+        let (machines, durations) =
+            read_files_timed(&workspace_path, &options, progress_callback).unwrap();
+
+        assert_eq!(machines.len(), 1);
+        assert_eq!(durations.len(), machine_data_types().count());
+        assert!(durations.contains_key(&MameDataType::Series));
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_read_file_timed() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_read_file_timed");
+        let series_path = workspace_path
+            .join(WORKSPACE_PATHS.extract_path)
+            .join("series");
+        std::fs::create_dir_all(&series_path).unwrap();
+        std::fs::write(series_path.join("series.ini"), "[Pac-Man]\npacman\n").unwrap();
+
+        let outcome = read_file_timed(
+            MameDataType::Series,
+            &workspace_path,
+            &ReadOptions::default(),
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.machines.len(), 1);
+        assert!(outcome.duration > Duration::ZERO);
+        assert!(read_file_timed(
+            MameDataType::Catver,
+            &workspace_path,
+            &ReadOptions::default(),
+            Box::new(|_| {}),
+        )
+        .is_err());
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_read_files_error_policy() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_read_error_policy");
+        let extract_path = workspace_path.join(WORKSPACE_PATHS.extract_path);
+        std::fs::create_dir_all(extract_path.join("catver")).unwrap();
+        std::fs::write(
+            extract_path.join("catver").join("catver.ini"),
+            "[Category]\npacman=Maze / Collect\n",
+        )
+        .unwrap();
+
+        let progress_callback: SharedProgressCallback = Arc::new(|_, _| {});
+        let (machines, errors) = read_files_with_errors(
+            &workspace_path,
+            &ReadOptions::default().with_on_error(ErrorPolicy::ContinueLossy),
+            Arc::clone(&progress_callback),
+        )
+        .unwrap();
+
+        assert_eq!(machines.len(), 1);
+        assert_eq!(errors.len(), machine_data_types().count() - 1);
+        assert!(errors
+            .iter()
+            .all(|(data_type, _)| *data_type != MameDataType::Catver));
+
+        // The missing data files are not silently skipped by default
+        let options = ReadOptions::default();
+        assert_eq!(options.on_error, ErrorPolicy::FailFast);
+        assert!(read_files(&workspace_path, Arc::clone(&progress_callback)).is_err());
+        assert!(
+            read_files_with_options(&workspace_path, &options, Arc::clone(&progress_callback))
+                .is_err()
+        );
+        assert!(
+            read_files_sequential_with_options(&workspace_path, &options, progress_callback)
+                .is_err()
+        );
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_read_files_with_lowercase_names() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_read_lowercase_names");
+        let extract_path = workspace_path.join(WORKSPACE_PATHS.extract_path);
+        std::fs::create_dir_all(extract_path.join("mame")).unwrap();
+        std::fs::create_dir_all(extract_path.join("catver")).unwrap();
+        std::fs::write(
+            extract_path.join("mame").join("MAME 0.250.dat"),
+            r#"<mame>
+                <machine name="PacMan"><description>Pac-Man</description></machine>
+                <machine name="PuckMan" cloneof="PacMan" romof="PacMan"><description>Puck Man</description></machine>
+            </mame>"#,
+        )
+        .unwrap();
+        std::fs::write(
+            extract_path.join("catver").join("catver.ini"),
+            "[Category]\npacman=Maze / Collect\npuckman=Maze / Collect\n",
+        )
+        .unwrap();
+
+        let options = ReadOptions {
+            lowercase_names: true,
+            on_error: ErrorPolicy::ContinueLossy,
+            ..ReadOptions::default()
+        };
+        let progress_callback: SharedProgressCallback = Arc::new(|_, _| {});
+        let machines =
+            read_files_sequential_with_options(&workspace_path, &options, progress_callback)
+                .unwrap();
+
+        assert_eq!(machines.len(), 2);
+        for (key, machine) in &machines {
+            assert_eq!(key, &machine.name);
+            assert_eq!(machine.category.as_deref(), Some("Maze"));
+        }
+        assert_eq!(machines["pacman"].description.as_deref(), Some("Pac-Man"));
+        let puckman = &machines["puckman"];
+        assert_eq!(puckman.clone_of.as_deref(), Some("pacman"));
+        assert_eq!(puckman.rom_of.as_deref(), Some("pacman"));
+        assert!(machines.contains_key(puckman.clone_of.as_deref().unwrap()));
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_join_readers_with_panic() {
+        let (result_sender, result_receiver) = mpsc::channel();
+        result_sender
+            .send((
+                MameDataType::Catver,
+                Ok(Ok(HashMap::from([(
+                    "pacman".to_string(),
+                    Machine::builder("pacman").build(),
+                )]))),
+            ))
+            .unwrap();
+        let panic_payload = thread::spawn(|| panic!("malformed entry"))
+            .join()
+            .unwrap_err();
+        result_sender
+            .send((MameDataType::Series, Err(panic_payload)))
+            .unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sender = std::sync::Mutex::new(sender);
+        let progress_callback: SharedProgressCallback =
+            Arc::new(move |data_type, progress_info| {
+                let _ = sender.lock().unwrap().send((data_type, progress_info));
+            });
+
+        let err = join_readers(
+            result_receiver,
+            2,
+            &ReadOptions::default(),
+            &progress_callback,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Series"));
+        assert!(err.to_string().contains("malformed entry"));
+
+        let (data_type, progress_info) = receiver.try_recv().unwrap();
+        assert_eq!(data_type, MameDataType::Series);
+        assert!(matches!(
+            progress_info.callback_type,
+            crate::progress::CallbackType::Error
+        ));
+    }
+
+    #[test]
+    fn test_join_readers_fail_fast() {
+        let (result_sender, result_receiver) = mpsc::channel();
+        // The error of a fast reader is returned without waiting for the slower readers
+        result_sender
+            .send((
+                MameDataType::NPlayers,
+                Ok(Err("Broken nplayers.ini".into())),
+            ))
+            .unwrap();
+
+        let cancel_flag = CancelFlag::default();
+        let options = ReadOptions::default()
+            .with_on_error(ErrorPolicy::FailFast)
+            .with_cancel_flag(Arc::clone(&cancel_flag));
+        let progress_callback: SharedProgressCallback = Arc::new(|_, _| {});
+
+        let err = join_readers(result_receiver, 2, &options, &progress_callback).unwrap_err();
+        assert_eq!(err.to_string(), "Broken nplayers.ini");
+        assert!(cancel_flag.load(Ordering::SeqCst));
+
+        // A caller-supplied flag is left untouched when the read errors are tolerated
+        let (result_sender, result_receiver) = mpsc::channel();
+        result_sender
+            .send((
+                MameDataType::NPlayers,
+                Ok(Err("Broken nplayers.ini".into())),
+            ))
+            .unwrap();
+        let cancel_flag = CancelFlag::default();
+        let options = ReadOptions::default()
+            .with_on_error(ErrorPolicy::ContinueLossy)
+            .with_cancel_flag(Arc::clone(&cancel_flag));
+
+        let (_, errors) = join_readers(result_receiver, 1, &options, &progress_callback).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(!cancel_flag.load(Ordering::SeqCst));
+    }
 }
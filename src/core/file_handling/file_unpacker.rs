@@ -1,26 +1,35 @@
 use crate::helpers::file_system_helpers::{
-    ensure_folder_exists, find_file_with_pattern, WORKSPACE_PATHS,
+    ensure_folder_exists, find_file_with_pattern, WorkspaceLayout,
 };
 use crate::{
     core::models::{
-        callback_progress::{CallbackType, ProgressCallback, ProgressInfo, SharedProgressCallback},
+        callback_progress::{ProgressCallback, ProgressInfo, SharedProgressCallback},
+        cancellation::{check_cancelled, run_cancellable, with_cancel_flag, CancelFlag},
         mame_data_types::{get_data_type_details, MameDataType},
     },
     helpers::callback_progress_helper::get_progress_info,
 };
+use flate2::read::GzDecoder;
+use regex::Regex;
 use sevenz_rust::Password;
 use std::error::Error;
+use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
-use std::{fs::File, io::Write};
+use std::{
+    fs::File,
+    io::{Read, Write},
+};
 use zip::ZipArchive;
 
 /// Unpacks a data file for a specific `MameDataType` into a designated workspace folder.
 ///
 /// This function checks if the required data file for the specified `MameDataType` is already unpacked.
 /// If not, it searches for the corresponding ZIP file in the download directory, and if found,
-/// unpacks it into the appropriate folder. Progress updates during the process can be provided via a callback function.
+/// unpacks it into the appropriate folder. An archive saved with a wrong or missing extension (e.g. `pS_CatVer_262.bin`)
+/// is also found, as long as its name without extension matches the data type and its content is a supported archive. Progress updates during the process can be provided via a callback function.
 ///
 /// # Parameters
 /// - `data_type`: The `MameDataType` that specifies the type of data file to unpack (e.g., Series, Categories).
@@ -54,13 +63,54 @@ pub fn unpack_file(
     data_type: MameDataType,
     workspace_path: &Path,
     progress_callback: ProgressCallback,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    unpack_file_with_options(
+        data_type,
+        workspace_path,
+        &UnpackOptions::default(),
+        progress_callback,
+    )
+}
+
+/// Unpacks a data file for a specific `MameDataType` using the provided `UnpackOptions`.
+///
+/// This function behaves like `unpack_file`, but allows the unpacking process to be customized.
+/// When `options.force` is enabled, the "already unpacked" check is skipped: the extract folder for the
+/// data type is cleared and the archive is extracted again. The folder is only cleared once the archive is
+/// found, so the unpacked files are kept when there is nothing to extract them from. This is useful when an
+/// upstream archive is revised without changing the name of the data file it contains.
+///
+/// # Parameters
+/// - `data_type`: The `MameDataType` that specifies the type of data file to unpack (e.g., Series, Categories).
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the data file will be unpacked.
+/// - `options`: A reference to the `UnpackOptions` that control the unpacking process.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the unpacking process.
+///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<PathBuf, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the path where the unpacked file is located.
+/// - On failure: Contains an error if the file cannot be unpacked, if the ZIP file is not found,
+///   or if there are issues creating or clearing the destination folder.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The destination folder cannot be created or, when forcing, cleared.
+/// - The required ZIP file is not found in the download folder.
+/// - The unpacking process fails due to reading or writing errors.
+///
+pub fn unpack_file_with_options(
+    data_type: MameDataType,
+    workspace_path: &Path,
+    options: &UnpackOptions,
+    progress_callback: ProgressCallback,
 ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     // Retrieves the details for a given `MameDataType`
     let data_type_details = get_data_type_details(data_type);
 
     // Creates a folder if it does not exist.
     let extract_folder = workspace_path
-        .join(WORKSPACE_PATHS.extract_path)
+        .join(&options.layout.extract_path)
         .join(data_type_details.name.to_lowercase());
 
     let folder_created = ensure_folder_exists(&extract_folder);
@@ -69,26 +119,26 @@ pub fn unpack_file(
     }
 
     // Checks if file already unpacked
-    progress_callback(get_progress_info(
-        format!(
-            "Checking if {} file already unpacked",
-            data_type_details.name
-        )
-        .as_str(),
-    ));
-
-    if let Ok(existing_data_file) = find_file_with_pattern(
-        &extract_folder.to_str().unwrap(),
-        &data_type_details.data_file_pattern,
-    ) {
-        progress_callback(ProgressInfo {
-            progress: 0,
-            total: 0,
-            message: format!("{} file already unpacked", data_type_details.name),
-            callback_type: CallbackType::Finish,
-        });
-
-        return Ok(existing_data_file.into());
+    if !options.force {
+        progress_callback(get_progress_info(
+            format!(
+                "Checking if {} file already unpacked",
+                data_type_details.name
+            )
+            .as_str(),
+        ));
+
+        if let Ok(existing_data_file) = find_file_with_pattern(
+            &extract_folder.to_str().unwrap(),
+            &data_type_details.data_file_pattern,
+        ) {
+            progress_callback(ProgressInfo::finish(format!(
+                "{} file already unpacked",
+                data_type_details.name
+            )));
+
+            return Ok(existing_data_file.into());
+        }
     }
 
     // Checks if zip file is present
@@ -96,22 +146,43 @@ pub fn unpack_file(
         format!("Checking if {} zip file exists", data_type_details.name).as_str(),
     ));
 
-    let download_folder = workspace_path.join(WORKSPACE_PATHS.download_path);
-    let zip_file_path = find_file_with_pattern(
-        &download_folder.to_str().unwrap(),
-        &data_type_details.zip_file_pattern,
-    );
+    let download_folder = workspace_path.join(&options.layout.download_path);
+    let zip_file_path = find_archive(&download_folder, &data_type_details.zip_file_pattern);
 
     match zip_file_path {
         // Unpack the file
         Ok(zip_file_path) => {
+            // Clears the previously unpacked files when forcing a re-extraction, only once
+            // an archive to extract again has been found
+            if options.force {
+                progress_callback(get_progress_info(
+                    format!("Clearing unpacked {} files", data_type_details.name).as_str(),
+                ));
+
+                fs::remove_dir_all(&extract_folder)?;
+                ensure_folder_exists(&extract_folder)?;
+            }
+
             let zip_file = zip_file_path.split('/').last().unwrap();
 
             progress_callback(get_progress_info(
                 format!("Unpacking {}", zip_file).as_str(),
             ));
 
-            let unpack_result = unpack(&zip_file_path, &extract_folder, &progress_callback);
+            // Extracts only the data file when requested
+            let entry_filter = options
+                .only_data_file
+                .then_some(&data_type_details.data_file_pattern);
+
+            // The extraction stops at the next entry once the cancel flag is set
+            let unpack_result = with_cancel_flag(&options.cancel_flag, || {
+                unpack(
+                    &zip_file_path,
+                    &extract_folder,
+                    entry_filter,
+                    &progress_callback,
+                )
+            });
 
             // Check if unpacking was successful
             match unpack_result {
@@ -126,12 +197,7 @@ pub fn unpack_file(
                             "{} data file not present after unpacking",
                             data_type_details.name
                         );
-                        progress_callback(ProgressInfo {
-                            progress: 0,
-                            total: 0,
-                            message: message.clone(),
-                            callback_type: CallbackType::Error,
-                        });
+                        progress_callback(ProgressInfo::error(message.clone()));
 
                         return Err(message.into());
                     }
@@ -144,12 +210,7 @@ pub fn unpack_file(
         Err(err) => {
             let message = format!("{} zip file not found", data_type_details.name);
 
-            progress_callback(ProgressInfo {
-                progress: 0,
-                total: 0,
-                message: message.clone(),
-                callback_type: CallbackType::Error,
-            });
+            progress_callback(ProgressInfo::error(message.clone()));
 
             return Err(err.into());
         }
@@ -194,6 +255,31 @@ pub fn unpack_file(
 pub fn unpack_files(
     workspace_path: &Path,
     progress_callback: SharedProgressCallback,
+) -> Vec<thread::JoinHandle<Result<PathBuf, Box<dyn Error + Send + Sync>>>> {
+    unpack_files_with_options(workspace_path, &UnpackOptions::default(), progress_callback)
+}
+
+/// Unpacks multiple data files concurrently for all `MameDataType` variants using the provided `UnpackOptions`.
+///
+/// This function behaves like `unpack_files`, passing the given options to `unpack_file_with_options`
+/// for every data type. For example, enabling `options.force` re-extracts every archive.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the data files will be unpacked.
+/// - `options`: A reference to the `UnpackOptions` applied to every unpacking operation.
+/// - `progress_callback`: A shared callback function of type `SharedProgressCallback` that tracks the progress of each file unpacking operation.
+///   The callback receives the `data_type` and a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Vec<thread::JoinHandle<Result<PathBuf, Box<dyn Error + Send + Sync>>>>`:
+/// - Each handle represents a thread responsible for unpacking a specific file. The result of the unpacking can be accessed by joining the thread handle.
+/// - On success: Each thread handle contains the path where the unpacked file is located.
+/// - On failure: Each thread handle contains an error if the unpacking fails or if there are issues accessing or creating the destination folder.
+///
+pub fn unpack_files_with_options(
+    workspace_path: &Path,
+    options: &UnpackOptions,
+    progress_callback: SharedProgressCallback,
 ) -> Vec<thread::JoinHandle<Result<PathBuf, Box<dyn Error + Send + Sync>>>> {
     let progress_callback = Arc::new(progress_callback);
 
@@ -201,31 +287,172 @@ pub fn unpack_files(
         .iter()
         .map(|&data_type| {
             let workspace_path = workspace_path.to_path_buf();
+            let options = options.clone();
             let progress_callback = Arc::clone(&progress_callback);
 
             thread::spawn(move || {
-                unpack_file(
-                    data_type,
-                    &workspace_path,
-                    Box::new(move |progress_info| {
-                        progress_callback(data_type, progress_info);
-                    }),
-                )
+                run_cancellable(&options.cancel_flag, || {
+                    unpack_file_with_options(
+                        data_type,
+                        &workspace_path,
+                        &options,
+                        Box::new(move |progress_info| {
+                            progress_callback(data_type, progress_info);
+                        }),
+                    )
+                })
             })
         })
         .collect()
 }
 
-/// Unpacks an archive file (ZIP or 7z) to the specified destination folder.
+/// Represents the options that control how data files are unpacked.
+///
+/// The `UnpackOptions` struct is passed to `unpack_file_with_options` and `unpack_files_with_options`
+/// to customize the unpacking process. The default options reproduce the behavior of `unpack_file`.
+///
+/// # Fields
+/// - `force`: When `true`, skips the "already unpacked" check, clears the extract folder and extracts the archive again.
+///   The extract folder is kept if the archive is not found.
+/// - `layout`: The `WorkspaceLayout` with the subfolders where the archives are found and unpacked.
+/// - `cancel_flag`: An optional `CancelFlag` shared by the unpacking operations of a batch. When it is set, the pending
+///   archives are skipped and the running extractions stop before their next entry; a failed operation of
+///   `unpack_files_with_options` sets it. A gzip file holds a single entry, so its decompression is not interrupted.
+/// - `only_data_file`: When `true`, only the archive entries whose file name matches the `data_file_pattern` of the
+///   data type are extracted, skipping the documentation or images bundled with the data file (ZIP and 7z).
+///
+#[derive(Debug, Clone, Default)]
+pub struct UnpackOptions {
+    /// Re-extracts the archive even if the data file is already unpacked.
+    pub force: bool,
+    /// The subfolders used inside the workspace.
+    pub layout: WorkspaceLayout,
+    /// Skips the unpacking operations sharing the flag when set.
+    pub cancel_flag: Option<CancelFlag>,
+    /// Extracts only the data file instead of every entry of the archive.
+    pub only_data_file: bool,
+}
+
+/// Finds the archive of a data type in the download folder.
+///
+/// The archive is first searched by its file name with `zip_file_pattern`. When no file matches, the files saved
+/// with a wrong or missing extension are considered: a file is selected if its name without extension, either alone
+/// or followed by `.zip` or `.7z`, matches the pattern, and its first bytes are the signature of a supported archive.
+///
+/// # Parameters
+/// - `download_folder`: A reference to the `Path` of the folder where the archives are downloaded.
+/// - `zip_file_pattern`: The `Regex` matching the file name of the archive of the data type.
+///
+/// # Returns
+/// Returns a `Result<String, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the path to the archive.
+/// - On failure: Contains the error of the search by file name if no archive is found.
+fn find_archive(
+    download_folder: &Path,
+    zip_file_pattern: &Regex,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let not_found =
+        match find_file_with_pattern(download_folder.to_str().unwrap(), zip_file_pattern) {
+            Ok(zip_file_path) => return Ok(zip_file_path),
+            Err(err) => err,
+        };
+
+    for entry in walkdir::WalkDir::new(download_folder)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+    {
+        let Some(stem) = entry.path().file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let name_matches = [
+            stem.to_string(),
+            format!("{}.zip", stem),
+            format!("{}.7z", stem),
+        ]
+        .iter()
+        .any(|name| zip_file_pattern.is_match(name));
+
+        if name_matches && matches!(detect_archive_type_by_content(entry.path()), Ok(Some(_))) {
+            return Ok(entry.path().to_string_lossy().into_owned());
+        }
+    }
+
+    Err(not_found)
+}
+
+/// Represents the archive formats that can be unpacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveType {
+    /// A ZIP archive.
+    Zip,
+    /// A 7z archive.
+    SevenZip,
+    /// A gzip-compressed file.
+    Gzip,
+}
+
+/// Detects the type of an archive file.
 ///
-/// This function determines the type of archive file based on its extension (`.zip` or `.7z`)
+/// The type is first determined by the extension of the file (`.zip`, `.7z` or `.gz`). When the extension
+/// doesn't match a known type (e.g. a download saved under a generic name), the first bytes of the file are
+/// checked against the signatures of the supported formats: `PK\x03\x04` for ZIP, `7z\xBC\xAF` for 7z and
+/// `\x1F\x8B` for gzip.
+///
+/// # Parameters
+/// - `archive_path`: A string slice (`&str`) representing the path to the archive file.
+///
+/// # Returns
+/// Returns a `Result<Option<ArchiveType>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the `ArchiveType` of the file, or `None` if neither its extension nor its content match a supported format.
+/// - On failure: Contains an error if the file cannot be opened or read.
+fn detect_archive_type(
+    archive_path: &str,
+) -> Result<Option<ArchiveType>, Box<dyn Error + Send + Sync>> {
+    let extension = Path::new(archive_path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+
+    match extension.as_deref() {
+        Some("zip") => return Ok(Some(ArchiveType::Zip)),
+        Some("7z") => return Ok(Some(ArchiveType::SevenZip)),
+        Some("gz") => return Ok(Some(ArchiveType::Gzip)),
+        _ => (),
+    }
+
+    detect_archive_type_by_content(Path::new(archive_path))
+}
+
+/// Detects the type of an archive file from the signature in its first bytes.
+fn detect_archive_type_by_content(
+    archive_path: &Path,
+) -> Result<Option<ArchiveType>, Box<dyn Error + Send + Sync>> {
+    let mut magic_bytes = Vec::with_capacity(4);
+    File::open(archive_path)?
+        .take(4)
+        .read_to_end(&mut magic_bytes)?;
+
+    let archive_type = match magic_bytes.as_slice() {
+        [0x50, 0x4B, 0x03, 0x04] => Some(ArchiveType::Zip),
+        [0x37, 0x7A, 0xBC, 0xAF] => Some(ArchiveType::SevenZip),
+        [0x1F, 0x8B, ..] => Some(ArchiveType::Gzip),
+        _ => None,
+    };
+
+    Ok(archive_type)
+}
+
+/// Unpacks an archive file (ZIP, 7z or gzip) to the specified destination folder.
+///
+/// This function determines the type of archive file with `detect_archive_type`, from its extension or else from its content,
 /// and calls the appropriate extraction function to unpack its contents into the provided folder.
 /// Progress updates during the unpacking process can be provided via a callback function.
 ///
 /// # Parameters
 /// - `zip_file_path`: A string slice (`&str`) representing the path to the archive file to be unpacked.
-///   The file must have a `.zip` or `.7z` extension.
 /// - `extract_folder`: A reference to a `Path` representing the destination folder where the contents of the archive will be extracted.
+/// - `entry_filter`: An optional `Regex` matched against the file name of each entry; when given, only the matching entries are extracted.
 /// - `progress_callback`: A reference to a callback function of type `ProgressCallback` that provides progress updates during the unpacking process.
 ///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
 ///
@@ -236,33 +463,74 @@ pub fn unpack_files(
 ///
 /// # Errors
 /// This function will return an error if:
-/// - The archive format is unsupported (i.e., the file does not have a `.zip` or `.7z` extension).
+/// - The archive format is unsupported (i.e., neither the extension nor the content of the file match a supported format).
 /// - The destination folder is invalid or inaccessible.
 /// - The extraction process fails due to reading or writing errors.
 fn unpack(
     zip_file_path: &str,
     extract_folder: &Path,
+    entry_filter: Option<&Regex>,
     progress_callback: &ProgressCallback,
 ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
-    match zip_file_path {
-        path if path.ends_with(".zip") => {
-            return extract_zip(
-                zip_file_path,
-                extract_folder.to_str().unwrap(),
-                progress_callback,
-            );
-        }
-        path if path.ends_with(".7z") => {
-            return extract_7zip(
-                zip_file_path,
-                extract_folder.to_str().unwrap(),
-                progress_callback,
-            );
-        }
-        _ => return Err("Unsupported archive format".into()),
+    match detect_archive_type(zip_file_path)? {
+        Some(ArchiveType::Zip) => extract_zip(
+            zip_file_path,
+            extract_folder.to_str().unwrap(),
+            entry_filter,
+            progress_callback,
+        ),
+        Some(ArchiveType::SevenZip) => extract_7zip(
+            zip_file_path,
+            extract_folder.to_str().unwrap(),
+            entry_filter,
+            progress_callback,
+        ),
+        Some(ArchiveType::Gzip) => extract_gzip(zip_file_path, extract_folder, progress_callback),
+        None => Err("Unsupported archive format".into()),
     }
 }
 
+/// Decompresses a gzip file to the specified destination folder.
+///
+/// A gzip file holds a single file, which is written to the destination folder under the name of the
+/// archive without its `.gz` extension (or with its extension removed, for files detected by their content).
+///
+/// # Parameters
+/// - `archive_path`: A string slice (`&str`) representing the path to the gzip file to be decompressed.
+/// - `destination_folder`: A reference to a `Path` representing the destination folder.
+/// - `progress_callback`: A reference to a callback function of type `ProgressCallback` that provides progress updates.
+///
+/// # Returns
+/// Returns a `Result<PathBuf, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the path to the folder where the file was decompressed.
+/// - On failure: Contains an error if the gzip file cannot be read or decompressed, or if the destination file cannot be written.
+fn extract_gzip(
+    archive_path: &str,
+    destination_folder: &Path,
+    progress_callback: &ProgressCallback,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    let archive_file = Path::new(archive_path);
+    let file_name = archive_file.file_stem().ok_or("Invalid gzip file name")?;
+
+    fs::create_dir_all(destination_folder)?;
+    let mut decoder = GzDecoder::new(File::open(archive_file)?);
+    let mut file = File::create(destination_folder.join(file_name))?;
+    io::copy(&mut decoder, &mut file)?;
+
+    progress_callback(
+        ProgressInfo::finish(format!(
+            "{} unpacked successfully",
+            archive_file
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+        ))
+        .with_progress(1, 1),
+    );
+
+    Ok(destination_folder.into())
+}
+
 /// Extracts the contents of a ZIP archive to the specified destination folder.
 ///
 /// This function opens a ZIP file, iterates over its contents, and extracts each file or directory
@@ -272,6 +540,7 @@ fn unpack(
 /// # Parameters
 /// - `archive_path`: A string slice (`&str`) representing the path to the ZIP archive file to be extracted.
 /// - `destination_folder`: A string slice (`&str`) representing the destination folder where the contents of the archive will be extracted.
+/// - `entry_filter`: An optional `Regex` matched against the file name of each entry; when given, the other entries are skipped.
 /// - `progress_callback`: A reference to a callback function of type `ProgressCallback` that provides progress updates during the extraction process.
 ///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
 ///
@@ -288,6 +557,7 @@ fn unpack(
 fn extract_zip(
     archive_path: &str,
     destination_folder: &str,
+    entry_filter: Option<&Regex>,
     progress_callback: &ProgressCallback,
 ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     let file = File::open(archive_path)?;
@@ -297,9 +567,18 @@ fn extract_zip(
     let mut progress: u64 = 0;
 
     for i in 0..archive.len() {
+        check_cancelled()?;
+
         let mut file = archive.by_index(i)?;
         let output_path = Path::new(destination_folder).join(file.name());
 
+        if !is_entry_selected(file.name(), entry_filter) {
+            // Skips the entries not matching the filter
+            progress += 1;
+            progress_callback(ProgressInfo::progress(progress, total_files));
+            continue;
+        }
+
         if (file.name()).ends_with('/') {
             std::fs::create_dir_all(&output_path)?;
         } else {
@@ -314,21 +593,14 @@ fn extract_zip(
 
         progress += 1;
 
-        progress_callback(ProgressInfo {
-            progress,
-            total: total_files,
-            message: String::from(""),
-            callback_type: CallbackType::Progress,
-        });
+        progress_callback(ProgressInfo::progress(progress, total_files));
     }
 
     let zip_file = archive_path.split('/').last().unwrap();
-    progress_callback(ProgressInfo {
-        progress,
-        total: progress,
-        message: format!("{} unpacked successfully", zip_file),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} unpacked successfully", zip_file))
+            .with_progress(progress, progress),
+    );
 
     Ok(destination_folder.into())
 }
@@ -342,6 +614,8 @@ fn extract_zip(
 /// # Parameters
 /// - `archive_path`: A string slice (`&str`) representing the path to the 7z archive file to be extracted.
 /// - `destination_folder`: A string slice (`&str`) representing the destination folder where the contents of the archive will be extracted.
+/// - `entry_filter`: An optional `Regex` matched against the file name of each entry; when given, the other entries are skipped.
+///   As the entries of a solid 7z archive are compressed together, the skipped entries are still decompressed, but not written.
 /// - `progress_callback`: A reference to a callback function of type `ProgressCallback` that provides progress updates during the extraction process.
 ///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
 ///
@@ -355,13 +629,14 @@ fn extract_zip(
 /// - The 7z archive cannot be opened or read.
 /// - The destination folder cannot be created or is invalid.
 /// - There are errors during file extraction, such as reading from the archive or writing to the disk.
-/// - The provided 7z archive format is unsupported or corrupted.
+/// - The provided 7z archive format is unsupported or corrupted (e.g. a truncated download).
 fn extract_7zip(
     archive_path: &str,
     destination_folder: &str,
+    entry_filter: Option<&Regex>,
     progress_callback: &ProgressCallback,
 ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
-    let mut sz = sevenz_rust::SevenZReader::open(archive_path, Password::empty()).unwrap();
+    let mut sz = sevenz_rust::SevenZReader::open(archive_path, Password::empty())?;
 
     let total_files = sz.archive().files.len();
     let mut progress_entries: u64 = 0;
@@ -369,40 +644,285 @@ fn extract_7zip(
     let dest = PathBuf::from(destination_folder);
 
     sz.for_each_entries(|entry, reader| {
+        // Stops the extraction, the cancellation is reported below
+        if check_cancelled().is_err() {
+            return Ok(false);
+        }
         let mut buf = [0u8; 1024];
         let path = dest.join(entry.name());
+        if !is_entry_selected(entry.name(), entry_filter) {
+            // Skips the entry, reading its data as the next entries may depend on it
+            io::copy(reader, &mut io::sink())?;
+            progress_entries += 1;
+            progress_callback(ProgressInfo::progress(progress_entries, total_files as u64));
+            return Ok(true);
+        }
         if entry.is_directory() {
-            std::fs::create_dir_all(path).unwrap();
+            std::fs::create_dir_all(path)?;
             return Ok(true);
         }
-        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
-        let mut file = File::create(path).unwrap();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(path)?;
         loop {
             let read_size = reader.read(&mut buf)?;
             if read_size == 0 {
                 progress_entries += 1;
 
-                progress_callback(ProgressInfo {
-                    progress: progress_entries,
-                    total: total_files as u64,
-                    message: String::from(""),
-                    callback_type: CallbackType::Progress,
-                });
+                progress_callback(ProgressInfo::progress(progress_entries, total_files as u64));
 
                 break Ok(true);
             }
             file.write_all(&buf[..read_size])?;
         }
-    })
-    .unwrap();
+    })?;
+    check_cancelled()?;
 
     let zip_file = archive_path.split('/').last().unwrap();
-    progress_callback(ProgressInfo {
-        progress: progress_entries,
-        total: progress_entries,
-        message: format!("{} unpacked successfully", zip_file),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} unpacked successfully", zip_file))
+            .with_progress(progress_entries, progress_entries),
+    );
 
     Ok(destination_folder.into())
 }
+
+/// Determines if an archive entry is extracted.
+///
+/// # Parameters
+/// - `entry_name`: The name of the entry in the archive, which may include folders.
+/// - `entry_filter`: An optional `Regex` matched against the file name of the entry.
+///
+/// # Returns
+/// Returns `true` if there is no filter or if the file name of the entry matches it; `false` otherwise.
+fn is_entry_selected(entry_name: &str, entry_filter: Option<&Regex>) -> bool {
+    match entry_filter {
+        Some(entry_filter) => Path::new(entry_name)
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .is_some_and(|file_name| entry_filter.is_match(file_name)),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_7zip_truncated_archive_returns_error() {
+        let test_folder = std::env::temp_dir().join("mame_parser_truncated_7z");
+        fs::create_dir_all(&test_folder).unwrap();
+
+        // 7z signature header followed by garbage, as left by an interrupted download
+        let archive_path = test_folder.join("truncated.7z");
+        fs::write(
+            &archive_path,
+            [0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C, 0x00, 0x04, 0x01, 0x02],
+        )
+        .unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let result = extract_7zip(
+            archive_path.to_str().unwrap(),
+            test_folder.join("out").to_str().unwrap(),
+            None,
+            &progress_callback,
+        );
+
+        assert!(result.is_err());
+
+        fs::remove_dir_all(&test_folder).unwrap();
+    }
+
+    #[test]
+    fn test_unpack_detects_zip_by_content() {
+        use zip::write::FileOptions;
+
+        let test_folder = std::env::temp_dir().join("mame_parser_unpack_magic_bytes");
+        fs::create_dir_all(&test_folder).unwrap();
+
+        // A ZIP archive saved by a mirror under a generic name
+        let archive_path = test_folder.join("download.bin");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        writer
+            .start_file("languages.ini", FileOptions::default())
+            .unwrap();
+        writer.write_all(b"[English]\npacman\n").unwrap();
+        writer.finish().unwrap();
+
+        let archive_path = archive_path.to_str().unwrap();
+        assert_eq!(
+            detect_archive_type(archive_path).unwrap(),
+            Some(ArchiveType::Zip)
+        );
+
+        let output_folder = test_folder.join("out");
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        unpack(archive_path, &output_folder, None, &progress_callback).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(output_folder.join("languages.ini")).unwrap(),
+            "[English]\npacman\n"
+        );
+
+        fs::remove_dir_all(&test_folder).unwrap();
+    }
+
+    #[test]
+    fn test_extract_zip_only_data_file() {
+        use zip::write::FileOptions;
+
+        let test_folder = std::env::temp_dir().join("mame_parser_only_data_file");
+        fs::create_dir_all(&test_folder).unwrap();
+
+        let archive_path = test_folder.join("languages.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        for name in ["docs/readme.txt", "folders/languages.ini", "icon.png"] {
+            writer.start_file(name, FileOptions::default()).unwrap();
+            writer.write_all(b"[English]\npacman\n").unwrap();
+        }
+        writer.finish().unwrap();
+
+        let output_folder = test_folder.join("out");
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        extract_zip(
+            archive_path.to_str().unwrap(),
+            output_folder.to_str().unwrap(),
+            Some(&Regex::new(r"languages.ini").unwrap()),
+            &progress_callback,
+        )
+        .unwrap();
+
+        assert!(output_folder.join("folders/languages.ini").exists());
+        assert!(!output_folder.join("docs/readme.txt").exists());
+        assert!(!output_folder.join("icon.png").exists());
+
+        fs::remove_dir_all(&test_folder).unwrap();
+    }
+
+    #[test]
+    fn test_extract_zip_cancelled() {
+        use std::sync::atomic::AtomicBool;
+        use zip::write::FileOptions;
+
+        let test_folder = std::env::temp_dir().join("mame_parser_extract_cancelled");
+        fs::create_dir_all(&test_folder).unwrap();
+
+        let archive_path = test_folder.join("catver.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        writer
+            .start_file("catver.ini", FileOptions::default())
+            .unwrap();
+        writer
+            .write_all(b"[Category]\npacman=Maze / Collect\n")
+            .unwrap();
+        writer.finish().unwrap();
+
+        let output_folder = test_folder.join("out");
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let cancel_flag: CancelFlag = Arc::new(AtomicBool::new(true));
+        let result = with_cancel_flag(&Some(cancel_flag), || {
+            extract_zip(
+                archive_path.to_str().unwrap(),
+                output_folder.to_str().unwrap(),
+                None,
+                &progress_callback,
+            )
+        });
+
+        assert_eq!(result.unwrap_err().to_string(), "Operation cancelled");
+        assert!(!output_folder.join("catver.ini").exists());
+
+        fs::remove_dir_all(&test_folder).unwrap();
+    }
+
+    #[test]
+    fn test_unpack_file_force() {
+        use zip::write::FileOptions;
+
+        let workspace_path = std::env::temp_dir().join("mame_parser_unpack_force");
+        let layout = WorkspaceLayout::default();
+        let download_folder = workspace_path.join(&layout.download_path);
+        let extract_folder = workspace_path.join(&layout.extract_path).join("catver");
+        fs::create_dir_all(&download_folder).unwrap();
+        fs::create_dir_all(&extract_folder).unwrap();
+        fs::write(extract_folder.join("catver.ini"), b"[Category]\nold=Old\n").unwrap();
+
+        let options = UnpackOptions {
+            force: true,
+            ..UnpackOptions::default()
+        };
+
+        // Without an archive, the unpacked files are kept
+        let result = unpack_file_with_options(
+            MameDataType::Catver,
+            &workspace_path,
+            &options,
+            Box::new(|_| {}),
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(extract_folder.join("catver.ini")).unwrap(),
+            "[Category]\nold=Old\n"
+        );
+
+        // With an archive, the unpacked files are replaced
+        let mut writer =
+            zip::ZipWriter::new(File::create(download_folder.join("pS_CatVer_262.zip")).unwrap());
+        writer
+            .start_file("catver.ini", FileOptions::default())
+            .unwrap();
+        writer
+            .write_all(b"[Category]\npacman=Maze / Collect\n")
+            .unwrap();
+        writer.finish().unwrap();
+        fs::write(extract_folder.join("stale.txt"), b"stale").unwrap();
+
+        let data_file_path = unpack_file_with_options(
+            MameDataType::Catver,
+            &workspace_path,
+            &options,
+            Box::new(|_| {}),
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(data_file_path).unwrap(),
+            "[Category]\npacman=Maze / Collect\n"
+        );
+        assert!(!extract_folder.join("stale.txt").exists());
+
+        fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_unpack_file_finds_archive_with_wrong_extension() {
+        use zip::write::FileOptions;
+
+        let workspace_path = std::env::temp_dir().join("mame_parser_unpack_wrong_extension");
+        let download_folder = workspace_path.join(WorkspaceLayout::default().download_path);
+        fs::create_dir_all(&download_folder).unwrap();
+
+        // A ZIP archive saved under the right name, but with a generic extension
+        let archive_path = download_folder.join("pS_CatVer_262.bin");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        writer
+            .start_file("catver.ini", FileOptions::default())
+            .unwrap();
+        writer
+            .write_all(b"[Category]\npacman=Maze / Collect\n")
+            .unwrap();
+        writer.finish().unwrap();
+
+        // A file of another data type that is not an archive is ignored
+        fs::write(download_folder.join("pS_Series_262.bin"), b"not an archive").unwrap();
+
+        let data_file_path =
+            unpack_file(MameDataType::Catver, &workspace_path, Box::new(|_| {})).unwrap();
+        assert!(data_file_path.ends_with("catver.ini"));
+        assert!(unpack_file(MameDataType::Series, &workspace_path, Box::new(|_| {})).is_err());
+
+        fs::remove_dir_all(&workspace_path).unwrap();
+    }
+}
@@ -1,15 +1,24 @@
 use crate::{
     core::writers::{csv_writer, json_writer, sqlite_writer},
-    helpers::file_system_helpers::{ensure_folder_exists, WORKSPACE_PATHS},
-    models::Machine,
-    progress::ProgressCallback,
+    helpers::file_system_helpers::{ensure_folder_exists, WorkspaceLayout},
+    models::{Machine, Resource},
+    progress::{CallbackType, ProgressCallback, ProgressInfo},
 };
 use std::{
     collections::HashMap,
     error::Error,
     fmt,
+    fs::{self, File},
+    io,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
 };
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+/// The progress span of each phase of an export, as the phases count their progress in different units.
+const PHASE_PROGRESS_SPAN: u64 = 1000;
 
 /// Writes machine data to the specified export file type.
 ///
@@ -61,9 +70,100 @@ pub fn write_files(
     workspace_path: &Path,
     machines: &HashMap<String, Machine>,
     progress_callback: ProgressCallback,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    write_files_with_options(
+        export_file_type,
+        workspace_path,
+        machines,
+        &WriteOptions::default(),
+        progress_callback,
+    )
+}
+
+/// Writes machine data to the specified export file type using the provided `WriteOptions`.
+///
+/// This function behaves like `write_files`, but allows the export to be customized. Each option only
+/// applies to the export file types listed in its documentation and is ignored by the others.
+///
+/// # Parameters
+/// - `export_file_type`: An `ExportFileType` enum specifying the format for data export.
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the exported files will be stored.
+/// - `machines`: A reference to a `HashMap` where keys are machine names and values are `Machine` structs containing
+///   detailed information about each MAME machine.
+/// - `options`: A reference to the `WriteOptions` that control the export.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides status updates and progress
+///   information during the export process. The callback receives a `ProgressInfo` struct containing `progress`, `total`,
+///   `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<PathBuf, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains a `PathBuf` representing the path to the folder where the export files are stored,
+///   or the path to the ZIP archive with `options.zip_output`.
+/// - On failure: Contains an error if the export folder cannot be created or if there is an issue during the writing process.
+///
+pub fn write_files_with_options(
+    export_file_type: ExportFileType,
+    workspace_path: &Path,
+    machines: &HashMap<String, Machine>,
+    options: &WriteOptions,
+    progress_callback: ProgressCallback,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    if !options.zip_output {
+        return write_export_files(
+            export_file_type,
+            workspace_path,
+            machines,
+            options,
+            progress_callback,
+        );
+    }
+
+    // Scales the progress of the export and the archive to a phase each, and reports the
+    // end of the export as info so that only the end of the archive is a finish.
+    let progress_callback = Arc::new(Mutex::new(progress_callback));
+    let writer_callback = Arc::clone(&progress_callback);
+    let export_folder = write_export_files(
+        export_file_type,
+        workspace_path,
+        machines,
+        options,
+        Box::new(move |mut progress_info| {
+            scale_to_phase(&mut progress_info, 0, 2);
+            if let CallbackType::Finish = progress_info.callback_type {
+                progress_info.callback_type = CallbackType::Info;
+            }
+            if let Ok(callback) = writer_callback.lock() {
+                callback(progress_info);
+            }
+        }),
+    )?;
+
+    let zip_path = export_folder.with_extension("zip");
+    zip_export_folder(
+        &export_folder,
+        &zip_path,
+        options.remove_zipped_files,
+        &|mut progress_info| {
+            scale_to_phase(&mut progress_info, 1, 2);
+            if let Ok(callback) = progress_callback.lock() {
+                callback(progress_info);
+            }
+        },
+    )?;
+
+    Ok(zip_path)
+}
+
+/// Writes the export files of an export file type to its folder, as `write_files_with_options` does before zipping them.
+fn write_export_files(
+    export_file_type: ExportFileType,
+    workspace_path: &Path,
+    machines: &HashMap<String, Machine>,
+    options: &WriteOptions,
+    progress_callback: ProgressCallback,
 ) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
     let export_folder = workspace_path
-        .join(WORKSPACE_PATHS.export_path)
+        .join(&options.layout.export_path)
         .join(export_file_type.to_string().to_lowercase());
 
     let folder_created = ensure_folder_exists(&export_folder);
@@ -77,6 +177,7 @@ pub fn write_files(
             sqlite_writer::write_sqlite(
                 &data_base_path.to_string_lossy(),
                 &machines,
+                options,
                 progress_callback,
             )?;
         }
@@ -84,6 +185,7 @@ pub fn write_files(
             json_writer::write_json(
                 &export_folder.to_string_lossy(),
                 &machines,
+                options,
                 progress_callback,
             )?;
         }
@@ -91,6 +193,7 @@ pub fn write_files(
             csv_writer::write_csv(
                 &export_folder.to_string_lossy(),
                 &machines,
+                options,
                 progress_callback,
             )?;
         }
@@ -99,6 +202,245 @@ pub fn write_files(
     Ok(export_folder)
 }
 
+/// Scales the progress of a phase of an export to the combined progress of all its phases.
+///
+/// Each phase is given the same span of `PHASE_PROGRESS_SPAN`, so the combined progress never goes backwards,
+/// whatever the unit (machines, rows or files) each phase counts its progress in. Updates without a total are kept.
+///
+/// # Parameters
+/// - `progress_info`: The `ProgressInfo` reported by the phase, updated in place.
+/// - `phase`: The index of the phase, starting at `0`.
+/// - `phases`: The number of phases of the export.
+fn scale_to_phase(progress_info: &mut ProgressInfo, phase: u64, phases: u64) {
+    if progress_info.total > 0 {
+        let progress = progress_info.progress.min(progress_info.total);
+        progress_info.progress =
+            phase * PHASE_PROGRESS_SPAN + progress * PHASE_PROGRESS_SPAN / progress_info.total;
+        progress_info.total = phases * PHASE_PROGRESS_SPAN;
+    }
+}
+
+/// Packages the files of an export folder into a single ZIP archive.
+///
+/// The files are stored with their path relative to the export folder, compressed with deflate,
+/// and a progress update is sent after each file.
+///
+/// # Parameters
+/// - `export_folder`: A reference to the `Path` of the folder with the export files.
+/// - `zip_path`: A reference to the `Path` of the ZIP archive to create. An existing archive is replaced.
+/// - `remove_files`: When `true`, the export folder is removed once the archive is complete.
+/// - `progress_callback`: A callback receiving the progress updates of the archive.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after writing the archive.
+/// - On failure: Returns an error if the export files cannot be read or the archive cannot be written.
+fn zip_export_folder(
+    export_folder: &Path,
+    zip_path: &Path,
+    remove_files: bool,
+    progress_callback: &dyn Fn(ProgressInfo),
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    progress_callback(ProgressInfo::info(format!(
+        "Zipping {}",
+        export_folder.display()
+    )));
+
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(export_folder)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+
+    let total_files = files.len() as u64;
+    let mut writer = ZipWriter::new(File::create(zip_path)?);
+    let file_options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (i, file) in files.iter().enumerate() {
+        let name = file
+            .strip_prefix(export_folder)?
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        writer.start_file(name.as_str(), file_options)?;
+        io::copy(&mut File::open(file)?, &mut writer)?;
+
+        progress_callback(ProgressInfo {
+            message: format!("Zipped {}", name),
+            ..ProgressInfo::progress((i + 1) as u64, total_files)
+        });
+    }
+
+    writer.finish()?;
+
+    if remove_files {
+        fs::remove_dir_all(export_folder)?;
+    }
+
+    progress_callback(
+        ProgressInfo::finish(format!("{} written successfully", zip_path.display()))
+            .with_progress(total_files, total_files),
+    );
+
+    Ok(())
+}
+
+/// Writes a compact index of the machines, with only their name, description, year and manufacturer.
+///
+/// The index is far smaller than the full export and is meant for search and autocomplete backends.
+/// The machines are written sorted by name to `index.json` or `index.csv`, without their ROMs, history or resources.
+///
+/// # Parameters
+/// - `export_path`: A reference to a `Path` representing the directory where the index file will be written.
+///   It is created if it does not exist.
+/// - `machines`: A reference to a `HashMap` where keys are machine names and values are `Machine` structs containing
+///   detailed information about each MAME machine.
+/// - `format`: The `ExportFileType` of the index. Only `ExportFileType::Json` and `ExportFileType::Csv` are supported.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides status updates and progress
+///   information during the export.
+///
+/// # Returns
+/// Returns a `Result<PathBuf, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the path to the index file.
+/// - On failure: Contains an error if the format is `ExportFileType::Sqlite`, if no machines are given,
+///   or if the file cannot be written.
+///
+pub fn write_index(
+    export_path: &Path,
+    machines: &HashMap<String, Machine>,
+    format: ExportFileType,
+    progress_callback: ProgressCallback,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    ensure_folder_exists(export_path)?;
+    let export_folder = export_path.to_string_lossy();
+
+    match format {
+        ExportFileType::Sqlite => {
+            return Err("The index can only be exported to JSON or CSV.".into());
+        }
+        ExportFileType::Json => {
+            json_writer::write_json_index(&export_folder, machines, progress_callback)?;
+        }
+        ExportFileType::Csv => {
+            csv_writer::write_csv_index(&export_folder, machines, progress_callback)?;
+        }
+    }
+
+    Ok(export_path.join(format!("index.{}", format.to_string().to_lowercase())))
+}
+
+/// Writes machine data to several export file types in one call.
+///
+/// This function calls `write_files` for each of the given export file types against the same borrowed
+/// `machines`. The formats are written sequentially, in the given order, on the calling thread; the export
+/// stops at the first format that fails.
+///
+/// # Parameters
+/// - `export_file_types`: A slice of `ExportFileType` enums specifying the formats for data export.
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the exported files will be stored.
+/// - `machines`: A reference to a `HashMap` where keys are machine names and values are `Machine` structs containing
+///   detailed information about each MAME machine.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides status updates and combined progress
+///   information for all the formats. The `progress` and `total` of the `CallbackType::Progress` updates cover all the
+///   formats, each format counting for 1000 of the `total`, and a single `CallbackType::Finish` update is sent once
+///   every format has been written.
+///
+/// # Returns
+/// Returns a `Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the paths to the folders where the export files are stored, in the order of `export_file_types`.
+/// - On failure: Contains an error if no export file type is given or if any of the exports fails.
+///
+pub fn write_files_multi(
+    export_file_types: &[ExportFileType],
+    workspace_path: &Path,
+    machines: &HashMap<String, Machine>,
+    progress_callback: ProgressCallback,
+) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    write_files_multi_with_options(
+        export_file_types,
+        workspace_path,
+        machines,
+        &WriteOptions::default(),
+        progress_callback,
+    )
+}
+
+/// Writes machine data to several export file types in one call using the provided `WriteOptions`.
+///
+/// This function behaves like `write_files_multi`, passing the same `WriteOptions` to every format.
+///
+/// # Parameters
+/// - `export_file_types`: A slice of `ExportFileType` enums specifying the formats for data export.
+/// - `workspace_path`: A reference to a `Path` representing the base directory where the exported files will be stored.
+/// - `machines`: A reference to a `HashMap` where keys are machine names and values are `Machine` structs containing
+///   detailed information about each MAME machine.
+/// - `options`: A reference to the `WriteOptions` that control the exports.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides status updates and combined progress
+///   information for all the formats.
+///
+/// # Returns
+/// Returns a `Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the paths to the folders where the export files are stored, in the order of `export_file_types`.
+/// - On failure: Contains an error if no export file type is given or if any of the exports fails.
+///
+pub fn write_files_multi_with_options(
+    export_file_types: &[ExportFileType],
+    workspace_path: &Path,
+    machines: &HashMap<String, Machine>,
+    options: &WriteOptions,
+    progress_callback: ProgressCallback,
+) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+    if export_file_types.is_empty() {
+        return Err("No export file types given.".into());
+    }
+
+    let formats = export_file_types.len() as u64;
+    let progress_callback = Arc::new(Mutex::new(progress_callback));
+    let mut export_folders = Vec::with_capacity(export_file_types.len());
+
+    for (index, export_file_type) in export_file_types.iter().enumerate() {
+        let index = index as u64;
+        let format_callback = Arc::clone(&progress_callback);
+
+        // Scales the progress of each format to a phase of the combined progress, and reports
+        // the end of each format as info so that only the last update is a finish.
+        let export_folder = write_files_with_options(
+            *export_file_type,
+            workspace_path,
+            machines,
+            options,
+            Box::new(move |mut progress_info| {
+                scale_to_phase(&mut progress_info, index, formats);
+                if let CallbackType::Finish = progress_info.callback_type {
+                    progress_info.callback_type = CallbackType::Info;
+                }
+                if let Ok(callback) = format_callback.lock() {
+                    callback(progress_info);
+                }
+            }),
+        )?;
+
+        export_folders.push(export_folder);
+    }
+
+    if let Ok(callback) = progress_callback.lock() {
+        callback(ProgressInfo::finish(format!(
+            "{} export file types written successfully",
+            formats
+        )));
+    }
+
+    Ok(export_folders)
+}
+
 /// Represents the file type to be used for data export.
 ///
 /// The `ExportFileType` enum defines the different formats supported for exporting data,
@@ -121,6 +463,265 @@ pub enum ExportFileType {
     Csv,
 }
 
+/// Represents the options that control how machine data is exported.
+///
+/// The `WriteOptions` struct is passed to `write_files_with_options` to customize the export.
+/// The default options reproduce the behavior of `write_files`. The options can be set field by field,
+/// or with the `with_*` methods, e.g. `WriteOptions::default().with_pretty(false).with_zip_output(true)`.
+///
+/// # Fields
+/// - `bom`: When `true`, each CSV file starts with a UTF-8 byte order mark, so Excel detects the encoding (CSV only).
+/// - `pretty`: When `true`, the JSON files are pretty-printed; when `false`, they are written in compact form (JSON only).
+/// - `bool_encoding`: The `BoolEncoding` used for the boolean fields (SQLite and CSV).
+/// - `id_map`: When `true`, a `machine_ids.json` file maps each machine name to its row id in the database (SQLite only).
+/// - `raw_manufacturers`: When `true`, a `raw_manufacturers` table maps each original manufacturer string
+///   to its normalized manufacturer, so both forms can be queried (SQLite only).
+/// - `shards`: The number of files the machines are split into, by hashing the machine name (JSON only).
+///   With more than one shard, `machines_0.json` to `machines_<shards - 1>.json` are written instead of `machines.json`,
+///   along with a `machines_manifest.json` file mapping each machine name to its shard.
+/// - `resource_paths`: The `ResourcePaths` form in which the resource names are written (SQLite, JSON and CSV).
+/// - `csv_newline_placeholder`: When set, the newlines of the history texts are replaced with this placeholder (CSV only).
+/// - `csv_threads`: The number of CSV files written at the same time, each on its own thread (CSV only).
+///   Defaults to the available parallelism of the machine. The rows of every file are written in the same order
+///   whatever the number of threads.
+/// - `debug_fields`: When `true`, the raw description, manufacturer, players and year the extended data was normalized
+///   from are written next to it, as the `name_raw`, `manufacturer_raw`, `players_raw` and `year_raw` keys of the
+///   extended data (JSON) or the `extended_*_raw` columns (CSV). Useful to tune the name normalization against real data.
+/// - `nested_clones`: When `true`, only the parent machines are written at the top level of the machines JSON files,
+///   each with a `clones` array holding its clone machines, including the clones of its clones. Clones whose parent is not exported are written at the
+///   top level, with an empty `clones` array (JSON only).
+/// - `table_prefix`: When set, this prefix is prepended to the name of every table (e.g. `mame_machines`), to merge
+///   the export into a database with tables of the same name. `verify_sqlite_schema` expects unprefixed tables (SQLite only).
+/// - `zip_output`: When `true`, the export files are packaged into a single ZIP archive next to the export folder
+///   (e.g. `export/json.zip`), and `write_files_with_options` returns the path to the archive. The progress of the
+///   export and of the archive then count for 1000 each of a `total` of 2000.
+/// - `remove_zipped_files`: When `true`, the export folder is removed once the ZIP archive is written (with `zip_output` only).
+/// - `layout`: The `WorkspaceLayout` with the subfolder where the data is exported.
+///
+#[derive(Debug, Clone)]
+pub struct WriteOptions {
+    /// Starts each CSV file with a UTF-8 byte order mark (CSV only).
+    pub bom: bool,
+    /// Pretty-prints the JSON files instead of writing them in compact form (JSON only).
+    pub pretty: bool,
+    /// The encoding of the boolean fields (SQLite and CSV).
+    pub bool_encoding: BoolEncoding,
+    /// Writes a `machine_ids.json` file with the row id of each machine (SQLite only).
+    pub id_map: bool,
+    /// Writes a `raw_manufacturers` table with the original manufacturer strings (SQLite only).
+    pub raw_manufacturers: bool,
+    /// The number of files the machines are split into (JSON only).
+    pub shards: usize,
+    /// The form of the resource names (SQLite, JSON and CSV).
+    pub resource_paths: ResourcePaths,
+    /// Replaces the newlines of the history texts with this placeholder, for importers that cannot read
+    /// multi-line fields (CSV only). `None` keeps the newlines, which is valid CSV.
+    pub csv_newline_placeholder: Option<String>,
+    /// The number of CSV files written at the same time (CSV only).
+    pub csv_threads: usize,
+    /// Writes the raw values next to the normalized extended data, to debug the normalization (JSON and CSV).
+    pub debug_fields: bool,
+    /// Nests the clones under their parent in the machines JSON files (JSON only).
+    pub nested_clones: bool,
+    /// The prefix of the table names (SQLite only). `None` keeps the unprefixed names.
+    pub table_prefix: Option<String>,
+    /// Packages the export files into a single ZIP archive.
+    pub zip_output: bool,
+    /// Removes the export folder once it has been zipped.
+    pub remove_zipped_files: bool,
+    /// The subfolders used inside the workspace.
+    pub layout: WorkspaceLayout,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            bom: false,
+            pretty: true,
+            bool_encoding: BoolEncoding::default(),
+            id_map: false,
+            raw_manufacturers: false,
+            shards: 1,
+            resource_paths: ResourcePaths::default(),
+            csv_newline_placeholder: None,
+            csv_threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            debug_fields: false,
+            nested_clones: false,
+            table_prefix: None,
+            zip_output: false,
+            remove_zipped_files: false,
+            layout: WorkspaceLayout::default(),
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Sets `bom`, to start each CSV file with a UTF-8 byte order mark.
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Sets `pretty`, to pretty-print the JSON files.
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Sets the `BoolEncoding` used for the boolean fields.
+    pub fn with_bool_encoding(mut self, bool_encoding: BoolEncoding) -> Self {
+        self.bool_encoding = bool_encoding;
+        self
+    }
+
+    /// Sets `id_map`, to write the id of each machine to a `machine_ids.json` file.
+    pub fn with_id_map(mut self, id_map: bool) -> Self {
+        self.id_map = id_map;
+        self
+    }
+
+    /// Sets `raw_manufacturers`, to write the original manufacturers to a `raw_manufacturers` table.
+    pub fn with_raw_manufacturers(mut self, raw_manufacturers: bool) -> Self {
+        self.raw_manufacturers = raw_manufacturers;
+        self
+    }
+
+    /// Sets the number of files the JSON machines are split into.
+    pub fn with_shards(mut self, shards: usize) -> Self {
+        self.shards = shards;
+        self
+    }
+
+    /// Sets the `ResourcePaths` form in which the resource names are written.
+    pub fn with_resource_paths(mut self, resource_paths: ResourcePaths) -> Self {
+        self.resource_paths = resource_paths;
+        self
+    }
+
+    /// Sets the placeholder replacing the newlines of the history texts in the CSV files.
+    pub fn with_csv_newline_placeholder(
+        mut self,
+        csv_newline_placeholder: impl Into<String>,
+    ) -> Self {
+        self.csv_newline_placeholder = Some(csv_newline_placeholder.into());
+        self
+    }
+
+    /// Sets the number of CSV files written at the same time.
+    pub fn with_csv_threads(mut self, csv_threads: usize) -> Self {
+        self.csv_threads = csv_threads;
+        self
+    }
+
+    /// Sets `debug_fields`, to write the raw values of the extended data next to the normalized ones.
+    pub fn with_debug_fields(mut self, debug_fields: bool) -> Self {
+        self.debug_fields = debug_fields;
+        self
+    }
+
+    /// Sets `nested_clones`, to nest the clones under their parent in `machines.json`.
+    pub fn with_nested_clones(mut self, nested_clones: bool) -> Self {
+        self.nested_clones = nested_clones;
+        self
+    }
+
+    /// Sets the prefix of the SQLite table names.
+    pub fn with_table_prefix(mut self, table_prefix: impl Into<String>) -> Self {
+        self.table_prefix = Some(table_prefix.into());
+        self
+    }
+
+    /// Sets `zip_output`, to package the export files into a single ZIP archive.
+    pub fn with_zip_output(mut self, zip_output: bool) -> Self {
+        self.zip_output = zip_output;
+        self
+    }
+
+    /// Sets `remove_zipped_files`, to remove the export folder once it has been zipped.
+    pub fn with_remove_zipped_files(mut self, remove_zipped_files: bool) -> Self {
+        self.remove_zipped_files = remove_zipped_files;
+        self
+    }
+
+    /// Sets the `WorkspaceLayout` with the subfolder where the data is exported.
+    pub fn with_layout(mut self, layout: WorkspaceLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+}
+
+/// Represents how the boolean fields of the machines are encoded in the SQLite and CSV exports.
+///
+/// The boolean fields of a machine (e.g. `is_bios` or `runnable`) are tri-state: `Some(true)`, `Some(false)`,
+/// or `None` when the value is unknown. Both exporters encode `true` as `1` and `false` as `0`, so the
+/// values read the same across formats; this enum controls how unknown values are written.
+///
+/// # Variants
+/// - `IntNullable`: Unknown values are written as SQL `NULL` in SQLite and as an empty field in CSV.
+/// - `IntNonNull`: Unknown values are written as `0`, the same as `false`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoolEncoding {
+    /// Writes unknown values as `NULL` (SQLite) or an empty field (CSV).
+    #[default]
+    IntNullable,
+    /// Writes unknown values as `0`.
+    IntNonNull,
+}
+
+impl BoolEncoding {
+    /// Encodes a tri-state boolean field, returning `None` when it has to be written as `NULL`.
+    pub(crate) fn encode(&self, value: Option<bool>) -> Option<bool> {
+        match self {
+            BoolEncoding::IntNullable => value,
+            BoolEncoding::IntNonNull => Some(value.unwrap_or(false)),
+        }
+    }
+
+    /// Encodes a tri-state boolean field as a CSV field.
+    pub(crate) fn encode_str(&self, value: Option<bool>) -> &'static str {
+        match self.encode(value) {
+            Some(true) => "1",
+            Some(false) => "0",
+            None => "",
+        }
+    }
+}
+
+/// Represents how the names of the resources are written in the exports.
+///
+/// The resource names are read as the raw paths of the resources dat, using backslashes and starting with the
+/// folder of the resource type (e.g. `snap\\puckman.png`). This enum allows them to be written in a form that is
+/// easier to use from other platforms and from the web.
+///
+/// # Variants
+/// - `Raw`: The names are written as read (e.g. `snap\\puckman.png`).
+/// - `Normalized`: The names are written with forward slashes (e.g. `snap/puckman.png`), see `Resource::normalized_name`.
+/// - `Relative`: The names are written with forward slashes and without the folder of the resource type
+///   (e.g. `puckman.png`), see `Resource::relative_name`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResourcePaths {
+    /// Writes the names as read.
+    #[default]
+    Raw,
+    /// Writes the names with forward slashes.
+    Normalized,
+    /// Writes the names with forward slashes, relative to the folder of the resource type.
+    Relative,
+}
+
+impl ResourcePaths {
+    /// Returns the name of the resource in this form.
+    pub(crate) fn name_of(&self, resource: &Resource) -> String {
+        match self {
+            ResourcePaths::Raw => resource.name.clone(),
+            ResourcePaths::Normalized => resource.normalized_name(),
+            ResourcePaths::Relative => resource.relative_name(),
+        }
+    }
+}
+
 /// Implements the `fmt::Display` trait for `ExportFileType`.
 ///
 /// This allows instances of `ExportFileType` to be formatted as strings,
@@ -137,3 +738,167 @@ impl fmt::Display for ExportFileType {
         write!(f, "{}", as_str)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_files_zip_output() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_write_zip_output");
+        let machines: HashMap<String, Machine> = ["pacman", "galaga"]
+            .into_iter()
+            .map(|name| (name.to_string(), Machine::builder(name).build()))
+            .collect();
+
+        let options = WriteOptions {
+            zip_output: true,
+            remove_zipped_files: true,
+            ..WriteOptions::default()
+        };
+        let zip_path = write_files_with_options(
+            ExportFileType::Csv,
+            &workspace_path,
+            &machines,
+            &options,
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        assert_eq!(zip_path.file_name().unwrap(), "csv.zip");
+        assert!(!zip_path.with_extension("").exists());
+
+        let mut archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        assert!(archive.by_name("machines.csv").is_ok());
+
+        fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_bool_encoding() {
+        let nullable = BoolEncoding::IntNullable;
+        assert_eq!(nullable.encode(None), None);
+        assert_eq!(nullable.encode_str(Some(true)), "1");
+        assert_eq!(nullable.encode_str(Some(false)), "0");
+        assert_eq!(nullable.encode_str(None), "");
+
+        let non_null = BoolEncoding::IntNonNull;
+        assert_eq!(non_null.encode(None), Some(false));
+        assert_eq!(non_null.encode_str(Some(true)), "1");
+        assert_eq!(non_null.encode_str(None), "0");
+    }
+
+    #[test]
+    fn test_write_files_multi() {
+        let machines: HashMap<String, Machine> = ["pacman", "puckman"]
+            .into_iter()
+            .map(|name| (name.to_string(), Machine::builder(name).build()))
+            .collect();
+
+        let workspace_path = std::env::temp_dir().join("mame_parser_write_multi");
+        let (progress_callback, receiver) = crate::progress::channel_callback();
+
+        let export_folders = write_files_multi(
+            &[ExportFileType::Json, ExportFileType::Csv],
+            &workspace_path,
+            &machines,
+            progress_callback,
+        )
+        .unwrap();
+
+        assert_eq!(export_folders.len(), 2);
+        assert!(export_folders[0].join("machines.json").exists());
+        assert!(export_folders[1].join("machines.csv").exists());
+
+        let updates: Vec<ProgressInfo> = receiver.try_iter().collect();
+        let finished = updates
+            .iter()
+            .filter(|update| matches!(update.callback_type, CallbackType::Finish))
+            .count();
+        assert_eq!(finished, 1);
+        assert!(matches!(
+            updates.last().unwrap().callback_type,
+            CallbackType::Finish
+        ));
+        assert!(updates.iter().all(|update| update.progress <= update.total));
+
+        // The combined progress never goes backwards, whatever the unit of each format and phase
+        let (progress_callback, receiver) = crate::progress::channel_callback();
+        write_files_multi_with_options(
+            &[ExportFileType::Csv, ExportFileType::Json],
+            &workspace_path,
+            &machines,
+            &WriteOptions::default().with_zip_output(true),
+            progress_callback,
+        )
+        .unwrap();
+
+        let progress: Vec<u64> = receiver
+            .try_iter()
+            .filter(|update| matches!(update.callback_type, CallbackType::Progress))
+            .map(|update| {
+                assert_eq!(update.total, 2 * PHASE_PROGRESS_SPAN);
+                update.progress
+            })
+            .collect();
+        assert!(!progress.is_empty());
+        assert!(progress.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        assert!(write_files_multi(&[], &workspace_path, &machines, Box::new(|_| {})).is_err());
+
+        std::fs::remove_dir_all(&workspace_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_index() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("puckman")
+                .description("Puck Man (Japan set 1)")
+                .year("1980")
+                .manufacturer("Namco")
+                .build(),
+            Machine::builder("pacman").description("Pac-Man").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_write_index");
+
+        let json_path = write_index(
+            &export_path,
+            &machines,
+            ExportFileType::Json,
+            Box::new(|_| {}),
+        )
+        .unwrap();
+        let index: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(index[0]["name"], "pacman");
+        assert_eq!(index[0]["year"], serde_json::Value::Null);
+        assert_eq!(index[1]["manufacturer"], "Namco");
+        assert_eq!(index[1].as_object().unwrap().len(), 4);
+
+        let csv_path = write_index(
+            &export_path,
+            &machines,
+            ExportFileType::Csv,
+            Box::new(|_| {}),
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&csv_path).unwrap(),
+            "name,description,year,manufacturer\npacman,Pac-Man,,\npuckman,Puck Man (Japan set 1),1980,Namco\n"
+        );
+
+        assert!(write_index(
+            &export_path,
+            &machines,
+            ExportFileType::Sqlite,
+            Box::new(|_| {})
+        )
+        .is_err());
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+}
@@ -1,4 +1,6 @@
+pub mod export_verification;
 pub mod file_downloader;
 pub mod file_reader;
 pub mod file_unpacker;
 pub mod file_writer;
+pub mod workspace_status;
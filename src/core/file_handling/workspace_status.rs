@@ -0,0 +1,168 @@
+use crate::{
+    core::models::mame_data_types::{get_data_type_details, MameDataType},
+    helpers::file_system_helpers::{find_file_with_pattern, WorkspaceLayout},
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Represents the state of the files of a `MameDataType` in the workspace.
+///
+/// The `DataTypeStatus` struct is returned by `workspace_status` for each data type.
+///
+/// # Fields
+/// - `downloaded`: `true` if the archive of the data type is present in the download folder.
+/// - `unpacked`: `true` if the data file of the data type is present in its extract folder.
+/// - `archive_path`: The path to the downloaded archive, if any.
+/// - `data_file_path`: The path to the unpacked data file, if any.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataTypeStatus {
+    /// Whether the archive is downloaded.
+    pub downloaded: bool,
+    /// Whether the data file is unpacked.
+    pub unpacked: bool,
+    /// The path to the downloaded archive.
+    pub archive_path: Option<PathBuf>,
+    /// The path to the unpacked data file.
+    pub data_file_path: Option<PathBuf>,
+}
+
+/// Returns which data types are already downloaded and unpacked in the workspace.
+///
+/// This function looks for the archive and the data file of every `MameDataType` using the same
+/// patterns as `unpack_file` and `read_file`. It is read-only: nothing is downloaded, extracted or created.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory of the workspace.
+///
+/// # Returns
+/// Returns a `HashMap<MameDataType, DataTypeStatus>` with the status of every data type.
+///
+pub fn workspace_status(workspace_path: &Path) -> HashMap<MameDataType, DataTypeStatus> {
+    workspace_status_with_layout(workspace_path, &WorkspaceLayout::default())
+}
+
+/// Returns which data types are already downloaded and unpacked in a workspace using a custom `WorkspaceLayout`.
+///
+/// This function behaves like `workspace_status`, looking for the files in the subfolders of the given layout.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory of the workspace.
+/// - `layout`: A reference to the `WorkspaceLayout` with the subfolders where the archives are downloaded and unpacked.
+///
+/// # Returns
+/// Returns a `HashMap<MameDataType, DataTypeStatus>` with the status of every data type.
+///
+pub fn workspace_status_with_layout(
+    workspace_path: &Path,
+    layout: &WorkspaceLayout,
+) -> HashMap<MameDataType, DataTypeStatus> {
+    let download_folder = workspace_path.join(&layout.download_path);
+
+    MameDataType::all_variants()
+        .iter()
+        .map(|&data_type| {
+            let data_type_details = get_data_type_details(data_type);
+
+            let archive_path = find_file_with_pattern(
+                &download_folder.to_string_lossy(),
+                &data_type_details.zip_file_pattern,
+            )
+            .ok()
+            .map(PathBuf::from);
+            let data_file_path = data_file_path_with_layout(workspace_path, data_type, layout);
+
+            let status = DataTypeStatus {
+                downloaded: archive_path.is_some(),
+                unpacked: data_file_path.is_some(),
+                archive_path,
+                data_file_path,
+            };
+
+            (data_type, status)
+        })
+        .collect()
+}
+
+/// Returns the path to the unpacked data file of a data type in the workspace.
+///
+/// This function looks for the data file with the same pattern as `read_file`, so callers can locate
+/// the files produced by `unpack_file` without knowing the layout of the workspace.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory of the workspace.
+/// - `data_type`: The `MameDataType` whose data file is looked for.
+///
+/// # Returns
+/// Returns an `Option<PathBuf>` with the path to the data file, or `None` if it hasn't been unpacked.
+///
+pub fn data_file_path(workspace_path: &Path, data_type: MameDataType) -> Option<PathBuf> {
+    data_file_path_with_layout(workspace_path, data_type, &WorkspaceLayout::default())
+}
+
+/// Returns the path to the unpacked data file of a data type in a workspace using a custom `WorkspaceLayout`.
+///
+/// This function behaves like `data_file_path`, looking for the data file in the extract subfolder of the given layout.
+///
+/// # Parameters
+/// - `workspace_path`: A reference to a `Path` representing the base directory of the workspace.
+/// - `data_type`: The `MameDataType` whose data file is looked for.
+/// - `layout`: A reference to the `WorkspaceLayout` with the subfolder where the archives are unpacked.
+///
+/// # Returns
+/// Returns an `Option<PathBuf>` with the path to the data file, or `None` if it hasn't been unpacked.
+///
+pub fn data_file_path_with_layout(
+    workspace_path: &Path,
+    data_type: MameDataType,
+    layout: &WorkspaceLayout,
+) -> Option<PathBuf> {
+    let data_type_details = get_data_type_details(data_type);
+    let extract_folder = workspace_path
+        .join(&layout.extract_path)
+        .join(data_type_details.name.to_lowercase());
+
+    find_file_with_pattern(
+        &extract_folder.to_string_lossy(),
+        &data_type_details.data_file_pattern,
+    )
+    .ok()
+    .map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_workspace_status() {
+        let workspace_path = std::env::temp_dir().join("mame_parser_workspace_status");
+        let layout = WorkspaceLayout::default();
+        let download_folder = workspace_path.join(&layout.download_path);
+        let extract_folder = workspace_path.join(&layout.extract_path).join("languages");
+        fs::create_dir_all(&download_folder).unwrap();
+        fs::create_dir_all(&extract_folder).unwrap();
+        fs::write(download_folder.join("pS_Languages_268.zip"), b"").unwrap();
+        fs::write(extract_folder.join("languages.ini"), b"").unwrap();
+
+        let status = workspace_status(&workspace_path);
+
+        assert_eq!(status.len(), MameDataType::all_variants().len());
+        let languages = &status[&MameDataType::Languages];
+        assert!(languages.downloaded);
+        assert!(languages.unpacked);
+        assert_eq!(
+            languages.data_file_path.as_deref(),
+            Some(extract_folder.join("languages.ini").as_path())
+        );
+        assert_eq!(status[&MameDataType::Mame], DataTypeStatus::default());
+        assert_eq!(
+            data_file_path(&workspace_path, MameDataType::Languages),
+            languages.data_file_path
+        );
+        assert_eq!(data_file_path(&workspace_path, MameDataType::Mame), None);
+
+        fs::remove_dir_all(&workspace_path).unwrap();
+    }
+}
@@ -1,5 +1,6 @@
 pub mod data_cleanup;
 pub mod file_handling;
 pub mod models;
+pub mod queries;
 pub mod readers;
 pub mod writers;
@@ -1,5 +1,11 @@
 use crate::core::models::mame_data_types::MameDataType;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{
+    mpsc::{channel, Receiver},
+    Arc, Mutex,
+};
 
 /// Represents the type of callback being invoked during an operation.
 ///
@@ -13,7 +19,8 @@ use std::sync::Arc;
 /// - `Finish`: Indicates that an operation has completed successfully, providing a final status message.
 /// - `Error`: Indicates that an error has occurred and provides details related to the issue.
 ///
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CallbackType {
     /// Conveys a general informational message.
     Info,
@@ -43,6 +50,7 @@ pub enum CallbackType {
 /// `ProgressInfo` is typically used in callback functions to report the status of an operation in real-time,
 /// allowing the caller to monitor progress, handle errors, or perform additional actions based on the state
 /// of the ongoing process.
+#[derive(Serialize, Deserialize)]
 pub struct ProgressInfo {
     /// The current progress value.
     pub progress: u64,
@@ -54,6 +62,49 @@ pub struct ProgressInfo {
     pub callback_type: CallbackType,
 }
 
+impl ProgressInfo {
+    /// Creates a `CallbackType::Progress` update with the amount of work done and the total amount of work.
+    pub fn progress(progress: u64, total: u64) -> Self {
+        ProgressInfo {
+            progress,
+            total,
+            message: String::new(),
+            callback_type: CallbackType::Progress,
+        }
+    }
+
+    /// Creates a `CallbackType::Info` update with the given message.
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::with_message(message, CallbackType::Info)
+    }
+
+    /// Creates a `CallbackType::Finish` update with the given message.
+    pub fn finish(message: impl Into<String>) -> Self {
+        Self::with_message(message, CallbackType::Finish)
+    }
+
+    /// Creates a `CallbackType::Error` update with the given message.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::with_message(message, CallbackType::Error)
+    }
+
+    /// Sets the progress and total values of the update, e.g. to report the final count on `CallbackType::Finish`.
+    pub fn with_progress(mut self, progress: u64, total: u64) -> Self {
+        self.progress = progress;
+        self.total = total;
+        self
+    }
+
+    fn with_message(message: impl Into<String>, callback_type: CallbackType) -> Self {
+        ProgressInfo {
+            progress: 0,
+            total: 0,
+            message: message.into(),
+            callback_type,
+        }
+    }
+}
+
 /// Type alias for a progress callback function used to report progress updates during long-running operations.
 ///
 /// `ProgressCallback` is a boxed function trait object that accepts a `ProgressInfo` struct and is used to provide
@@ -98,3 +149,278 @@ pub type ProgressCallback = Box<dyn Fn(ProgressInfo) + Send + 'static>;
 /// and a single, shared callback is needed to handle progress updates. The `Arc` wrapper allows multiple ownership of the callback,
 /// ensuring it remains valid and accessible across all threads involved in the operation.
 pub type SharedProgressCallback = Arc<dyn Fn(MameDataType, ProgressInfo) + Send + Sync + 'static>;
+
+/// Represents the combined progress of several concurrent operations, such as the downloads of `download_files`.
+///
+/// `AggregateProgress` is a cheap to clone handle to shared state: every clone reads and updates the same
+/// counters. It keeps the last `progress` and `total` reported for each `MameDataType` and sums them, so a
+/// single progress bar can be displayed for all the files (e.g., "X of Y bytes downloaded across all files").
+/// It is set in `DownloadOptions::aggregate_progress` and queried from any thread while the operations run.
+///
+/// The total only includes the files whose size is already known, so it can grow while the downloads start.
+///
+#[derive(Debug, Clone, Default)]
+pub struct AggregateProgress {
+    entries: Arc<Mutex<HashMap<MameDataType, (u64, u64)>>>,
+}
+
+impl AggregateProgress {
+    /// Creates an empty `AggregateProgress`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the sum of the progress reported for every data type.
+    pub fn progress(&self) -> u64 {
+        self.snapshot().0
+    }
+
+    /// Returns the sum of the totals reported for every data type.
+    pub fn total(&self) -> u64 {
+        self.snapshot().1
+    }
+
+    /// Returns the sum of the progress and the sum of the totals, read at the same time.
+    pub fn snapshot(&self) -> (u64, u64) {
+        match self.entries.lock() {
+            Ok(entries) => entries.values().fold(
+                (0, 0),
+                |(progress, total), (entry_progress, entry_total)| {
+                    (progress + entry_progress, total + entry_total)
+                },
+            ),
+            Err(_) => (0, 0),
+        }
+    }
+
+    /// Records a progress update of the given data type, replacing its previous values.
+    ///
+    /// Only the `CallbackType::Progress` and `CallbackType::Finish` updates with a known total are recorded.
+    pub(crate) fn update(&self, data_type: MameDataType, progress_info: &ProgressInfo) {
+        let has_progress = matches!(
+            progress_info.callback_type,
+            CallbackType::Progress | CallbackType::Finish
+        );
+
+        if has_progress && progress_info.total > 0 {
+            if let Ok(mut entries) = self.entries.lock() {
+                entries.insert(data_type, (progress_info.progress, progress_info.total));
+            }
+        }
+    }
+}
+
+/// Creates a progress callback that forwards every progress update to a channel.
+///
+/// This is useful when the operation runs on a worker thread and the progress updates need to be
+/// handled on another thread (e.g., the UI thread) without sharing state with the callback.
+/// Updates sent after the receiver has been dropped are discarded.
+///
+/// # Returns
+/// A tuple containing:
+/// - A `ProgressCallback` that sends each `ProgressInfo` to the channel.
+/// - The `Receiver<ProgressInfo>` end of the channel.
+///
+pub fn channel_callback() -> (ProgressCallback, Receiver<ProgressInfo>) {
+    let (sender, receiver) = channel();
+
+    let callback: ProgressCallback = Box::new(move |progress_info| {
+        let _ = sender.send(progress_info);
+    });
+
+    (callback, receiver)
+}
+
+/// Creates a shared progress callback that forwards every progress update to a channel.
+///
+/// This is the `SharedProgressCallback` variant of `channel_callback`, to be used with the functions
+/// processing several data types concurrently. Each update is sent along with its `MameDataType`.
+/// Updates sent after the receiver has been dropped are discarded.
+///
+/// # Returns
+/// A tuple containing:
+/// - A `SharedProgressCallback` that sends each `(MameDataType, ProgressInfo)` pair to the channel.
+/// - The `Receiver<(MameDataType, ProgressInfo)>` end of the channel.
+///
+pub fn shared_channel_callback() -> (
+    SharedProgressCallback,
+    Receiver<(MameDataType, ProgressInfo)>,
+) {
+    let (sender, receiver) = channel();
+
+    let callback: SharedProgressCallback = Arc::new(move |data_type, progress_info| {
+        let _ = sender.send((data_type, progress_info));
+    });
+
+    (callback, receiver)
+}
+
+/// Creates a progress callback that writes every progress update as a line of JSON.
+///
+/// Each `ProgressInfo` is serialized as one JSON object per line (JSON Lines), with the
+/// `progress`, `total`, `message` and `callback_type` keys, which makes the updates easy to
+/// ship to log aggregators or to parse from scripts. Write errors are ignored so that a
+/// failing log destination does not interrupt the operation being tracked.
+///
+/// # Parameters
+/// - `out`: The writer receiving the JSON lines (e.g., `std::io::stderr()` or a `File`).
+///
+/// # Returns
+/// A `ProgressCallback` that serializes each `ProgressInfo` to `out`.
+///
+pub fn json_log_callback(out: impl Write + Send + 'static) -> ProgressCallback {
+    let out = Mutex::new(out);
+
+    Box::new(move |progress_info| {
+        if let Ok(mut out) = out.lock() {
+            write_json_line(&mut *out, &progress_info);
+        }
+    })
+}
+
+/// Creates a shared progress callback that writes every progress update as a line of JSON.
+///
+/// This is the `SharedProgressCallback` variant of `json_log_callback`, to be used with the functions
+/// processing several data types concurrently. Each JSON object also includes a `data_type` key with
+/// the `MameDataType` the update belongs to, in lowercase like the `callback_type` (e.g. `"series"`). Lines written from different threads are never interleaved.
+///
+/// # Parameters
+/// - `out`: The writer receiving the JSON lines (e.g., `std::io::stderr()` or a `File`).
+///
+/// # Returns
+/// A `SharedProgressCallback` that serializes each `(MameDataType, ProgressInfo)` pair to `out`.
+///
+pub fn shared_json_log_callback(out: impl Write + Send + 'static) -> SharedProgressCallback {
+    let out = Mutex::new(out);
+
+    Arc::new(move |data_type, progress_info| {
+        let entry = SharedLogEntry {
+            data_type,
+            progress_info: &progress_info,
+        };
+
+        if let Ok(mut out) = out.lock() {
+            write_json_line(&mut *out, &entry);
+        }
+    })
+}
+
+#[derive(Serialize)]
+struct SharedLogEntry<'a> {
+    data_type: MameDataType,
+    #[serde(flatten)]
+    progress_info: &'a ProgressInfo,
+}
+
+fn write_json_line<T: Serialize>(out: &mut dyn Write, value: &T) {
+    if serde_json::to_writer(&mut *out, value).is_ok() {
+        let _ = out.write_all(b"\n");
+        let _ = out.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_log_callbacks_write_json_lines() {
+        let buffer = SharedBuffer::default();
+
+        let callback = json_log_callback(buffer.clone());
+        callback(ProgressInfo::progress(1, 2));
+        callback(ProgressInfo::error("Failed"));
+
+        let shared_callback = shared_json_log_callback(buffer.clone());
+        shared_callback(MameDataType::Series, ProgressInfo::info("Reading"));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["progress"], 1);
+        assert_eq!(lines[0]["total"], 2);
+        assert_eq!(lines[0]["callback_type"], "progress");
+        assert_eq!(lines[1]["message"], "Failed");
+        assert_eq!(lines[1]["callback_type"], "error");
+        assert_eq!(lines[2]["data_type"], "series");
+        assert_eq!(lines[2]["message"], "Reading");
+    }
+
+    #[test]
+    fn test_progress_info_round_trips_through_json() {
+        let json = serde_json::to_string(&ProgressInfo::finish("Done")).unwrap();
+        let progress_info: ProgressInfo = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(progress_info.message, "Done");
+        assert!(matches!(progress_info.callback_type, CallbackType::Finish));
+
+        for callback_type in ["info", "progress", "finish", "error"] {
+            let json = format!("\"{}\"", callback_type);
+            let parsed: CallbackType = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_channel_callback_forwards_progress() {
+        let (callback, receiver) = channel_callback();
+
+        thread::spawn(move || {
+            callback(ProgressInfo::progress(1, 2));
+            callback(ProgressInfo::finish("Done"));
+        })
+        .join()
+        .unwrap();
+
+        let updates: Vec<ProgressInfo> = receiver.iter().collect();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].progress, 1);
+        assert_eq!(updates[1].message, "Done");
+    }
+
+    #[test]
+    fn test_aggregate_progress_sums_data_types() {
+        let aggregate_progress = AggregateProgress::new();
+        let shared = aggregate_progress.clone();
+
+        shared.update(MameDataType::Mame, &ProgressInfo::progress(10, 100));
+        shared.update(MameDataType::Series, &ProgressInfo::progress(5, 50));
+        shared.update(MameDataType::Mame, &ProgressInfo::progress(40, 100));
+        shared.update(MameDataType::Catver, &ProgressInfo::info("Downloading"));
+
+        assert_eq!(aggregate_progress.snapshot(), (45, 150));
+        assert_eq!(aggregate_progress.progress(), 45);
+        assert_eq!(aggregate_progress.total(), 150);
+    }
+
+    #[test]
+    fn test_shared_channel_callback_forwards_data_type() {
+        let (callback, receiver) = shared_channel_callback();
+
+        callback(MameDataType::Series, ProgressInfo::info("Reading"));
+        drop(callback);
+
+        let (data_type, progress_info) = receiver.recv().unwrap();
+        assert_eq!(data_type, MameDataType::Series);
+        assert_eq!(progress_info.message, "Reading");
+        assert!(receiver.recv().is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    cell::RefCell,
+    error::Error,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+};
+
+/// Shared flag used to cancel the remaining operations of a batch.
+///
+/// The flag is shared between the workers of the batch functions (`download_files_with_options`,
+/// `unpack_files_with_options` and `read_files_with_options`) through the `cancel_flag` of their options.
+/// Once set to `true`, the workers stop at their next check and return an error: the downloads check it
+/// between chunks, the archives between entries, and the readers every `CANCEL_CHECK_INTERVAL` entries.
+pub type CancelFlag = Arc<AtomicBool>;
+
+/// The number of entries a reader processes between two checks of its cancel flag.
+pub(crate) const CANCEL_CHECK_INTERVAL: usize = 1000;
+
+thread_local! {
+    /// The cancel flag of the read or unpacking running on the current thread, checked by `check_cancelled`.
+    ///
+    /// The readers are called through function pointers of a fixed signature, so their cancel flag is
+    /// set for the thread by `with_cancel_flag` instead of being passed as a parameter.
+    static THREAD_CANCEL_FLAG: RefCell<Option<CancelFlag>> = const { RefCell::new(None) };
+}
+
+/// Joins all the thread handles of a batch, cancelling the batch as soon as one operation fails.
+///
+/// Every handle is joined, so no thread is left running. When an operation returns an error or its thread
+/// panics, the `cancel_flag` is set so the workers sharing it stop at their next check.
+///
+/// # Parameters
+/// - `handles`: A vector of `JoinHandle`s, as returned by `download_files_with_options` or `unpack_files_with_options`.
+/// - `cancel_flag`: A reference to the `CancelFlag` shared with the workers through their options.
+///
+/// # Returns
+/// Returns a `Result<Vec<T>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the results of every operation, in the order of the handles.
+/// - On failure: Contains the first error found, in the order of the handles.
+///
+/// # Errors
+/// This function will return an error if any operation returns an error or its thread panics.
+///
+pub fn join_all_or_cancel<T>(
+    handles: Vec<JoinHandle<Result<T, Box<dyn Error + Send + Sync>>>>,
+    cancel_flag: &CancelFlag,
+) -> Result<Vec<T>, Box<dyn Error + Send + Sync>> {
+    let mut results = Vec::with_capacity(handles.len());
+    let mut first_error: Option<Box<dyn Error + Send + Sync>> = None;
+
+    for handle in handles {
+        let result = match handle.join() {
+            Ok(result) => result,
+            Err(_) => Err("Thread panicked".into()),
+        };
+
+        match result {
+            Ok(value) => results.push(value),
+            Err(err) => {
+                cancel_flag.store(true, Ordering::SeqCst);
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    match first_error {
+        Some(err) => Err(err),
+        None => Ok(results),
+    }
+}
+
+/// Checks if the operations sharing the flag have been cancelled.
+pub(crate) fn is_cancelled(cancel_flag: &Option<CancelFlag>) -> bool {
+    cancel_flag
+        .as_ref()
+        .is_some_and(|cancel_flag| cancel_flag.load(Ordering::SeqCst))
+}
+
+/// Runs an operation of a batch, skipping it if the batch was cancelled, without cancelling the batch if it fails.
+///
+/// # Parameters
+/// - `cancel_flag`: The optional `CancelFlag` shared by the operations of the batch.
+/// - `operation`: The operation to run.
+///
+/// # Returns
+/// Returns the result of the operation, or an error if the batch was cancelled before it started.
+pub(crate) fn run_unless_cancelled<T>(
+    cancel_flag: &Option<CancelFlag>,
+    operation: impl FnOnce() -> Result<T, Box<dyn Error + Send + Sync>>,
+) -> Result<T, Box<dyn Error + Send + Sync>> {
+    if is_cancelled(cancel_flag) {
+        return Err("Operation cancelled".into());
+    }
+
+    operation()
+}
+
+/// Runs an operation of a batch, skipping it if the batch was cancelled and cancelling the batch if it fails.
+///
+/// # Parameters
+/// - `cancel_flag`: The optional `CancelFlag` shared by the operations of the batch.
+/// - `operation`: The operation to run.
+///
+/// # Returns
+/// Returns the result of the operation, or an error if the batch was cancelled before it started.
+pub(crate) fn run_cancellable<T>(
+    cancel_flag: &Option<CancelFlag>,
+    operation: impl FnOnce() -> Result<T, Box<dyn Error + Send + Sync>>,
+) -> Result<T, Box<dyn Error + Send + Sync>> {
+    let result = run_unless_cancelled(cancel_flag, operation);
+
+    if let (Err(_), Some(cancel_flag)) = (&result, cancel_flag) {
+        cancel_flag.store(true, Ordering::SeqCst);
+    }
+
+    result
+}
+
+/// Runs a read or an unpacking with a cancel flag, checked with `check_cancelled` on the current thread.
+///
+/// # Parameters
+/// - `cancel_flag`: The optional `CancelFlag` of the operation.
+/// - `operation`: The operation to run.
+///
+/// # Returns
+/// Returns the result of the operation.
+pub(crate) fn with_cancel_flag<T>(
+    cancel_flag: &Option<CancelFlag>,
+    operation: impl FnOnce() -> T,
+) -> T {
+    let previous_flag =
+        THREAD_CANCEL_FLAG.with(|thread_flag| thread_flag.replace(cancel_flag.clone()));
+    let result = operation();
+    THREAD_CANCEL_FLAG.with(|thread_flag| thread_flag.replace(previous_flag));
+
+    result
+}
+
+/// Checks every `CANCEL_CHECK_INTERVAL` entries if the read running on the current thread has been cancelled.
+///
+/// # Parameters
+/// - `processed_count`: The number of entries processed so far by the reader.
+///
+/// # Returns
+/// Returns an error if the read has been cancelled, as set by `with_cancel_flag`.
+pub(crate) fn check_cancelled_at(
+    processed_count: usize,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if processed_count.is_multiple_of(CANCEL_CHECK_INTERVAL) {
+        return check_cancelled();
+    }
+
+    Ok(())
+}
+
+/// Checks if the operation running on the current thread has been cancelled.
+///
+/// # Returns
+/// Returns an error if the operation has been cancelled, as set by `with_cancel_flag`.
+pub(crate) fn check_cancelled() -> Result<(), Box<dyn Error + Send + Sync>> {
+    if THREAD_CANCEL_FLAG.with(|thread_flag| is_cancelled(&thread_flag.borrow())) {
+        return Err("Operation cancelled".into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_join_all_or_cancel() {
+        let cancel_flag: CancelFlag = Arc::new(AtomicBool::new(false));
+
+        let handles: Vec<JoinHandle<Result<u32, Box<dyn Error + Send + Sync>>>> =
+            vec![thread::spawn(|| Ok(1)), thread::spawn(|| Ok(2))];
+        assert_eq!(
+            join_all_or_cancel(handles, &cancel_flag).unwrap(),
+            vec![1, 2]
+        );
+        assert!(!cancel_flag.load(Ordering::SeqCst));
+
+        let handles: Vec<JoinHandle<Result<u32, Box<dyn Error + Send + Sync>>>> = vec![
+            thread::spawn(|| Ok(1)),
+            thread::spawn(|| Err("Download failed".into())),
+        ];
+        let err = join_all_or_cancel(handles, &cancel_flag).unwrap_err();
+        assert_eq!(err.to_string(), "Download failed");
+        assert!(cancel_flag.load(Ordering::SeqCst));
+
+        // Operations started after the cancellation are skipped
+        let skipped = run_cancellable(&Some(Arc::clone(&cancel_flag)), || Ok(3));
+        assert_eq!(skipped.unwrap_err().to_string(), "Operation cancelled");
+    }
+
+    #[test]
+    fn test_check_cancelled_at() {
+        let cancel_flag: CancelFlag = Arc::new(AtomicBool::new(true));
+
+        assert!(check_cancelled_at(CANCEL_CHECK_INTERVAL).is_ok());
+        with_cancel_flag(&Some(Arc::clone(&cancel_flag)), || {
+            assert!(check_cancelled_at(CANCEL_CHECK_INTERVAL - 1).is_ok());
+            assert!(check_cancelled_at(CANCEL_CHECK_INTERVAL).is_err());
+        });
+        assert!(check_cancelled_at(CANCEL_CHECK_INTERVAL).is_ok());
+    }
+}
@@ -119,6 +119,75 @@ pub fn get_subcategories_list(machines: &HashMap<String, Machine>) -> HashMap<St
     subcategories
 }
 
+/// Gets a list of unique source files from the provided machines, counting their occurrences.
+///
+/// # Parameters
+/// - `machines`: A reference to a `HashMap<String, Machine>` representing the collection of machines.
+///
+/// # Returns
+/// A `HashMap<String, usize>` where keys are driver source file names (e.g. "cps1.cpp") and values are their counts.
+pub fn get_source_files_list(machines: &HashMap<String, Machine>) -> HashMap<String, usize> {
+    let mut source_files: HashMap<String, usize> = HashMap::new();
+
+    machines.values().for_each(|machine| {
+        if let Some(source_file) = &machine.source_file {
+            add_item_to_list(&mut source_files, source_file.clone());
+        }
+    });
+
+    source_files
+}
+
+/// Rules used by `group_series_by_base` to collapse series names into a base franchise.
+///
+/// # Fields
+/// - `aliases`: Maps an exact series name to its franchise (e.g. "Vs. Super Mario Bros." to "Super Mario").
+/// - `prefixes`: Franchise names matched at the start of the series names (e.g. "Street Fighter" also
+///   groups "Street Fighter II"). The longest matching prefix wins.
+///
+/// The default rules are empty, so every series is its own franchise.
+#[derive(Debug, Clone, Default)]
+pub struct SeriesRules {
+    /// Series names mapped to their franchise.
+    pub aliases: HashMap<String, String>,
+    /// Franchise names matched as a prefix of the series names.
+    pub prefixes: Vec<String>,
+}
+
+/// Gets a list of franchises from the provided machines, collapsing the series with the given rules.
+///
+/// The series are counted as in `get_series_list`, then each series is mapped to its franchise: its alias
+/// if one is defined, otherwise the longest prefix it starts with, otherwise the series name itself.
+///
+/// # Parameters
+/// - `machines`: A reference to a `HashMap<String, Machine>` representing the collection of machines.
+/// - `rules`: A reference to the `SeriesRules` used to collapse the series names.
+///
+/// # Returns
+/// A `HashMap<String, usize>` where keys are franchise names and values are their machine counts.
+pub fn group_series_by_base(
+    machines: &HashMap<String, Machine>,
+    rules: &SeriesRules,
+) -> HashMap<String, usize> {
+    let mut franchises: HashMap<String, usize> = HashMap::new();
+
+    for (series_name, count) in get_series_list(machines) {
+        let franchise = rules.aliases.get(&series_name).cloned().unwrap_or_else(|| {
+            rules
+                .prefixes
+                .iter()
+                .filter(|prefix| series_name.starts_with(prefix.as_str()))
+                .max_by_key(|prefix| prefix.len())
+                .cloned()
+                .unwrap_or(series_name)
+        });
+
+        *franchises.entry(franchise).or_insert(0) += count;
+    }
+
+    franchises
+}
+
 /// Adds an item to a list stored in a `HashMap`, incrementing its count.
 ///
 /// # Parameters
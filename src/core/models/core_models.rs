@@ -1,4 +1,6 @@
+use crate::core::data_cleanup::name_normalization::{normalize_hash, HashKind};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// MAME machine, including all relevant metadata and resources.
 ///
@@ -55,6 +57,8 @@ pub struct Machine {
     pub subcategory: Option<String>,
     /// Indicates if the machine contains mature content (optional).
     pub is_mature: Option<bool>,
+    /// The MAME version in which the machine was added (optional).
+    pub version_added: Option<String>,
     /// A list of history sections associated with the machine.
     pub history_sections: Vec<HistorySection>,
     /// A list of disk data associated with the machine.
@@ -63,6 +67,9 @@ pub struct Machine {
     pub extended_data: Option<ExtendedData>,
     /// A list of external resources, such as images and videos, associated with the machine.
     pub resources: Vec<Resource>,
+    /// A list of the expansion slots of the machine, such as cartridge slots or controller ports.
+    #[serde(default)]
+    pub slots: Vec<Slot>,
 }
 
 impl Machine {
@@ -93,10 +100,12 @@ impl Machine {
             category: None,
             subcategory: None,
             is_mature: None,
+            version_added: None,
             history_sections: Vec::new(),
             disks: Vec::new(),
             extended_data: Some(Default::default()),
             resources: Vec::new(),
+            slots: Vec::new(),
         }
     }
     /// Combines the metadata of this machine with another machine.
@@ -152,6 +161,9 @@ impl Machine {
         if self.is_mature.is_none() {
             self.is_mature = other.is_mature;
         }
+        if self.version_added.is_none() {
+            self.version_added = other.version_added.clone();
+        }
 
         self.bios_sets.extend(other.bios_sets.clone());
         self.roms.extend(other.roms.clone());
@@ -162,6 +174,7 @@ impl Machine {
         self.history_sections.extend(other.history_sections.clone());
         self.disks.extend(other.disks.clone());
         self.resources.extend(other.resources.clone());
+        self.slots.extend(other.slots.clone());
 
         match (&mut self.extended_data, &other.extended_data) {
             (Some(self_data), Some(other_data)) => {
@@ -173,6 +186,354 @@ impl Machine {
             _ => {}
         }
     }
+    /// Normalizes the languages of this machine.
+    ///
+    /// Each language is trimmed and title-cased (e.g., `" english "` becomes `"English"`),
+    /// then duplicated and empty languages are removed, keeping the first occurrence order.
+    pub fn normalize_languages(&mut self) {
+        let mut normalized_languages: Vec<String> = Vec::with_capacity(self.languages.len());
+
+        for language in &self.languages {
+            let normalized_language = language
+                .split_whitespace()
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => {
+                            first.to_uppercase().collect::<String>()
+                                + &chars.as_str().to_lowercase()
+                        }
+                        None => String::new(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            if !normalized_language.is_empty()
+                && !normalized_languages.contains(&normalized_language)
+            {
+                normalized_languages.push(normalized_language);
+            }
+        }
+
+        self.languages = normalized_languages;
+    }
+
+    /// Normalizes the CRC and SHA-1 hashes of the ROMs, disks and resources of this machine.
+    ///
+    /// Each valid hash is trimmed and lowercased with `normalize_hash`. Invalid hashes are left unchanged,
+    /// so no data is lost; use `normalize_hash` to validate them.
+    pub fn normalize_hashes(&mut self) {
+        fn normalize(hash: &mut String, kind: HashKind) {
+            if let Some(normalized_hash) = normalize_hash(hash, kind) {
+                *hash = normalized_hash;
+            }
+        }
+
+        for rom in &mut self.roms {
+            if let Some(crc) = &mut rom.crc {
+                normalize(crc, HashKind::Crc);
+            }
+            if let Some(sha1) = &mut rom.sha1 {
+                normalize(sha1, HashKind::Sha1);
+            }
+        }
+        for sha1 in self.disks.iter_mut().filter_map(|disk| disk.sha1.as_mut()) {
+            normalize(sha1, HashKind::Sha1);
+        }
+        for resource in &mut self.resources {
+            normalize(&mut resource.crc, HashKind::Crc);
+            normalize(&mut resource.sha1, HashKind::Sha1);
+        }
+    }
+
+    /// Returns `true` if this machine is a parent, the rule behind the `is_parent` flag of the extended data.
+    ///
+    /// A machine is a parent if it references neither a parent (`clone_of`) nor a ROM set (`rom_of`).
+    pub(crate) fn references_no_parent(&self) -> bool {
+        self.clone_of.is_none() && self.rom_of.is_none()
+    }
+
+    /// Returns the best available name to show for this machine.
+    ///
+    /// The normalized name from the extended data is used if present, then the description,
+    /// and finally the machine name. Empty values are skipped.
+    pub fn display_name(&self) -> &str {
+        self.extended_data
+            .as_ref()
+            .and_then(|extended_data| extended_data.name.as_deref())
+            .filter(|name| !name.is_empty())
+            .or_else(|| {
+                self.description
+                    .as_deref()
+                    .filter(|description| !description.is_empty())
+            })
+            .unwrap_or(&self.name)
+    }
+
+    /// Returns a concise one-line summary of this machine, for debugging and logging.
+    ///
+    /// The summary contains the name, year, manufacturer, ROM count and category (with its subcategory),
+    /// e.g. `pacman (1980, Namco (Midway license), 2 ROMs, Maze / Collect)`. Missing values are shown as `?`.
+    pub fn summary(&self) -> String {
+        let category = match (&self.category, &self.subcategory) {
+            (Some(category), Some(subcategory)) => format!("{} / {}", category, subcategory),
+            (Some(category), None) => category.clone(),
+            _ => "?".to_string(),
+        };
+
+        format!(
+            "{} ({}, {}, {} ROM{}, {})",
+            self.name,
+            self.year.as_deref().unwrap_or("?"),
+            self.manufacturer.as_deref().unwrap_or("?"),
+            self.roms.len(),
+            if self.roms.len() == 1 { "" } else { "s" },
+            category
+        )
+    }
+}
+
+impl fmt::Display for Machine {
+    /// Formats the machine as its one-line `summary`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+/// Builder to create `Machine` instances programmatically.
+///
+/// The `MachineBuilder` starts from `Machine::new` and provides fluent setters for every field,
+/// which avoids constructing the whole `Machine` struct field by field (e.g., in tests or when
+/// building machines from other sources).
+///
+/// # Example
+/// ```
+/// use mame_parser::models::{Machine, Rom};
+///
+/// let machine = Machine::builder("pacman")
+///     .description("Pac-Man (Midway)")
+///     .year("1980")
+///     .add_rom(Rom {
+///         name: "pacman.6e".to_string(),
+///         size: 4096,
+///         merge: None,
+///         status: None,
+///         crc: Some("c1e6ab10".to_string()),
+///         sha1: None,
+///         region: Some("maincpu".to_string()),
+///         offset: Some("0".to_string()),
+///         bios: None,
+///     })
+///     .build();
+///
+/// assert_eq!(machine.year.as_deref(), Some("1980"));
+/// assert_eq!(machine.roms.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MachineBuilder {
+    machine: Machine,
+}
+
+impl Machine {
+    /// Creates a `MachineBuilder` for a machine with the specified name.
+    pub fn builder(name: impl Into<String>) -> MachineBuilder {
+        MachineBuilder::new(name)
+    }
+}
+
+impl MachineBuilder {
+    /// Creates a new `MachineBuilder` for a machine with the specified name.
+    pub fn new(name: impl Into<String>) -> Self {
+        MachineBuilder {
+            machine: Machine::new(name.into()),
+        }
+    }
+
+    /// Sets the name of the machine.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.machine.name = name.into();
+        self
+    }
+
+    /// Sets the source file associated with the machine.
+    pub fn source_file(mut self, source_file: impl Into<String>) -> Self {
+        self.machine.source_file = Some(source_file.into());
+        self
+    }
+
+    /// Sets the ROM that this machine is a variant of.
+    pub fn rom_of(mut self, rom_of: impl Into<String>) -> Self {
+        self.machine.rom_of = Some(rom_of.into());
+        self
+    }
+
+    /// Sets the parent machine if this is a clone.
+    pub fn clone_of(mut self, clone_of: impl Into<String>) -> Self {
+        self.machine.clone_of = Some(clone_of.into());
+        self
+    }
+
+    /// Sets the sample set associated with the machine.
+    pub fn sample_of(mut self, sample_of: impl Into<String>) -> Self {
+        self.machine.sample_of = Some(sample_of.into());
+        self
+    }
+
+    /// Sets the description of the machine.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.machine.description = Some(description.into());
+        self
+    }
+
+    /// Sets the year the machine was released.
+    pub fn year(mut self, year: impl Into<String>) -> Self {
+        self.machine.year = Some(year.into());
+        self
+    }
+
+    /// Sets the manufacturer of the machine.
+    pub fn manufacturer(mut self, manufacturer: impl Into<String>) -> Self {
+        self.machine.manufacturer = Some(manufacturer.into());
+        self
+    }
+
+    /// Sets the driver status of the machine.
+    pub fn driver_status(mut self, driver_status: impl Into<String>) -> Self {
+        self.machine.driver_status = Some(driver_status.into());
+        self
+    }
+
+    /// Sets the number of players supported.
+    pub fn players(mut self, players: impl Into<String>) -> Self {
+        self.machine.players = Some(players.into());
+        self
+    }
+
+    /// Sets the series to which the machine belongs.
+    pub fn series(mut self, series: impl Into<String>) -> Self {
+        self.machine.series = Some(series.into());
+        self
+    }
+
+    /// Sets the category of the machine.
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.machine.category = Some(category.into());
+        self
+    }
+
+    /// Sets the subcategory of the machine.
+    pub fn subcategory(mut self, subcategory: impl Into<String>) -> Self {
+        self.machine.subcategory = Some(subcategory.into());
+        self
+    }
+
+    /// Sets whether the machine is a BIOS set.
+    pub fn is_bios(mut self, is_bios: bool) -> Self {
+        self.machine.is_bios = Some(is_bios);
+        self
+    }
+
+    /// Sets whether the machine is a device.
+    pub fn is_device(mut self, is_device: bool) -> Self {
+        self.machine.is_device = Some(is_device);
+        self
+    }
+
+    /// Sets whether the machine is runnable.
+    pub fn runnable(mut self, runnable: bool) -> Self {
+        self.machine.runnable = Some(runnable);
+        self
+    }
+
+    /// Sets whether the machine is mechanical.
+    pub fn is_mechanical(mut self, is_mechanical: bool) -> Self {
+        self.machine.is_mechanical = Some(is_mechanical);
+        self
+    }
+
+    /// Sets whether the machine contains mature content.
+    pub fn is_mature(mut self, is_mature: bool) -> Self {
+        self.machine.is_mature = Some(is_mature);
+        self
+    }
+
+    /// Sets the MAME version in which the machine was added.
+    pub fn version_added(mut self, version_added: impl Into<String>) -> Self {
+        self.machine.version_added = Some(version_added.into());
+        self
+    }
+
+    /// Adds a supported language.
+    pub fn add_language(mut self, language: impl Into<String>) -> Self {
+        self.machine.languages.push(language.into());
+        self
+    }
+
+    /// Adds a BIOS set.
+    pub fn add_bios_set(mut self, bios_set: BiosSet) -> Self {
+        self.machine.bios_sets.push(bios_set);
+        self
+    }
+
+    /// Adds a ROM.
+    pub fn add_rom(mut self, rom: Rom) -> Self {
+        self.machine.roms.push(rom);
+        self
+    }
+
+    /// Adds a device reference.
+    pub fn add_device_ref(mut self, device_ref: DeviceRef) -> Self {
+        self.machine.device_refs.push(device_ref);
+        self
+    }
+
+    /// Adds a software list.
+    pub fn add_software(mut self, software: Software) -> Self {
+        self.machine.software_list.push(software);
+        self
+    }
+
+    /// Adds a sample.
+    pub fn add_sample(mut self, sample: Sample) -> Self {
+        self.machine.samples.push(sample);
+        self
+    }
+
+    /// Adds a history section.
+    pub fn add_history_section(mut self, history_section: HistorySection) -> Self {
+        self.machine.history_sections.push(history_section);
+        self
+    }
+
+    /// Adds a disk.
+    pub fn add_disk(mut self, disk: Disk) -> Self {
+        self.machine.disks.push(disk);
+        self
+    }
+
+    /// Adds a resource.
+    pub fn add_resource(mut self, resource: Resource) -> Self {
+        self.machine.resources.push(resource);
+        self
+    }
+
+    /// Adds a slot.
+    pub fn add_slot(mut self, slot: Slot) -> Self {
+        self.machine.slots.push(slot);
+        self
+    }
+
+    /// Sets the extended data of the machine.
+    pub fn extended_data(mut self, extended_data: ExtendedData) -> Self {
+        self.machine.extended_data = Some(extended_data);
+        self
+    }
+
+    /// Builds the `Machine`.
+    pub fn build(self) -> Machine {
+        self.machine
+    }
 }
 
 /// BIOS set associated with a MAME machine.
@@ -199,6 +560,12 @@ pub struct Rom {
     pub crc: Option<String>,
     /// The SHA-1 hash of the ROM file (optional).
     pub sha1: Option<String>,
+    /// The memory region where the ROM is loaded (optional).
+    pub region: Option<String>,
+    /// The offset where the ROM is loaded in its region (optional).
+    pub offset: Option<String>,
+    /// The name of the BIOS set the ROM belongs to (optional).
+    pub bios: Option<String>,
 }
 
 /// Device reference associated with a MAME machine.
@@ -213,6 +580,9 @@ pub struct DeviceRef {
 pub struct Software {
     /// The name of the software.
     pub name: String,
+    /// The names of the software titles in the list, attached with `attach_software_titles`.
+    #[serde(default)]
+    pub titles: Vec<String>,
 }
 
 /// Sample file associated with a MAME machine.
@@ -237,6 +607,26 @@ pub struct Disk {
     pub region: Option<String>,
 }
 
+/// Expansion slot of a MAME machine, such as a cartridge slot or a controller port.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Slot {
+    /// The name of the slot.
+    pub name: String,
+    /// The devices that can be plugged into the slot.
+    pub options: Vec<SlotOption>,
+}
+
+/// Device that can be plugged into a slot of a MAME machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotOption {
+    /// The name of the option.
+    pub name: String,
+    /// The name of the device machine of the option.
+    pub devname: String,
+    /// Indicates if the option is plugged into the slot by default.
+    pub is_default: bool,
+}
+
 /// Historical section or trivia associated with a MAME machine.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistorySection {
@@ -264,6 +654,15 @@ pub struct ExtendedData {
     pub is_parent: Option<bool>,
     /// Normalized release year (optional).
     pub year: Option<String>,
+    /// Maximum number of players, parsed from the nplayers code (optional).
+    #[serde(default)]
+    pub max_players: Option<u8>,
+    /// Indicates if several players can play simultaneously, parsed from the nplayers code (optional).
+    #[serde(default)]
+    pub players_simultaneous: Option<bool>,
+    /// Alternate titles of the machine, extracted from its description by `extract_alternate_titles`.
+    #[serde(default)]
+    pub alternate_titles: Vec<String>,
 }
 
 impl ExtendedData {
@@ -284,6 +683,15 @@ impl ExtendedData {
         if self.year.is_none() {
             self.year = other.year.clone();
         }
+        if self.max_players.is_none() {
+            self.max_players = other.max_players;
+        }
+        if self.players_simultaneous.is_none() {
+            self.players_simultaneous = other.players_simultaneous;
+        }
+        if self.alternate_titles.is_empty() {
+            self.alternate_titles = other.alternate_titles.clone();
+        }
     }
 }
 
@@ -301,3 +709,141 @@ pub struct Resource {
     /// The SHA-1 hash of the resource.
     pub sha1: String,
 }
+
+impl Resource {
+    /// Returns the name of the resource with forward slashes as path separators (e.g. `snap/puckman.png`).
+    pub fn normalized_name(&self) -> String {
+        self.name.replace('\\', "/")
+    }
+
+    /// Returns the normalized name of the resource without the leading folder of its type (e.g. `puckman.png`),
+    /// so it can be resolved against the folder of the type under a media root.
+    pub fn relative_name(&self) -> String {
+        let name = self.normalized_name();
+        match name.strip_prefix(&format!("{}/", self.type_)) {
+            Some(relative_name) => relative_name.to_string(),
+            None => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_machine_summary() {
+        let machine = Machine::builder("pacman")
+            .year("1980")
+            .manufacturer("Namco (Midway license)")
+            .category("Maze")
+            .subcategory("Collect")
+            .build();
+
+        assert_eq!(
+            machine.summary(),
+            "pacman (1980, Namco (Midway license), 0 ROMs, Maze / Collect)"
+        );
+        assert_eq!(machine.to_string(), machine.summary());
+        assert_eq!(
+            Machine::builder("puckman").build().summary(),
+            "puckman (?, ?, 0 ROMs, ?)"
+        );
+    }
+
+    #[test]
+    fn test_resource_names() {
+        let resource = Resource {
+            type_: "snap".to_string(),
+            name: "snap\\puckman.png".to_string(),
+            size: 0,
+            crc: String::new(),
+            sha1: String::new(),
+        };
+
+        assert_eq!(resource.normalized_name(), "snap/puckman.png");
+        assert_eq!(resource.relative_name(), "puckman.png");
+
+        let other_type = Resource {
+            type_: "titles".to_string(),
+            ..resource
+        };
+        assert_eq!(other_type.relative_name(), "snap/puckman.png");
+    }
+
+    #[test]
+    fn test_normalize_languages() {
+        let mut machine = Machine::builder("pacman")
+            .add_language("English")
+            .add_language("english")
+            .add_language(" English ")
+            .add_language("brazilian  PORTUGUESE")
+            .add_language("")
+            .build();
+
+        machine.normalize_languages();
+
+        assert_eq!(machine.languages, vec!["English", "Brazilian Portuguese"]);
+    }
+
+    #[test]
+    fn test_normalize_hashes() {
+        let mut machine = Machine::builder("pacman")
+            .add_rom(Rom {
+                name: "pacman.6e".to_string(),
+                size: 4096,
+                merge: None,
+                status: None,
+                crc: Some(" C1E6AB10".to_string()),
+                sha1: Some("not a hash".to_string()),
+                region: None,
+                offset: None,
+                bios: None,
+            })
+            .add_resource(Resource {
+                type_: "snap".to_string(),
+                name: "snap\\pacman.png".to_string(),
+                size: 1024,
+                crc: "0C2F7F5A".to_string(),
+                sha1: "E87E059C5E4A6A1D4B0B8B7C0B9E3A5D6F7A8B9C".to_string(),
+            })
+            .build();
+
+        machine.normalize_hashes();
+
+        assert_eq!(machine.roms[0].crc.as_deref(), Some("c1e6ab10"));
+        assert_eq!(machine.roms[0].sha1.as_deref(), Some("not a hash"));
+        assert_eq!(machine.resources[0].crc, "0c2f7f5a");
+        assert_eq!(
+            machine.resources[0].sha1,
+            "e87e059c5e4a6a1d4b0b8b7c0b9e3a5d6f7a8b9c"
+        );
+    }
+
+    #[test]
+    fn test_display_name() {
+        let mut machine = Machine::builder("pacman")
+            .description("Pac-Man (Midway)")
+            .build();
+        assert_eq!(machine.display_name(), "Pac-Man (Midway)");
+
+        machine.extended_data.as_mut().unwrap().name = Some("Pac-Man".to_string());
+        assert_eq!(machine.display_name(), "Pac-Man");
+
+        let machine = Machine::builder("pacman").build();
+        assert_eq!(machine.display_name(), "pacman");
+    }
+
+    #[test]
+    fn test_references_no_parent() {
+        let mut machine = Machine::builder("puckman").build();
+        assert!(machine.references_no_parent());
+
+        machine.rom_of = Some("pacman".to_string());
+        assert!(!machine.references_no_parent());
+
+        machine.rom_of = None;
+        machine.clone_of = Some("pacman".to_string());
+        assert!(!machine.references_no_parent());
+    }
+}
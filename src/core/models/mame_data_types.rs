@@ -2,10 +2,11 @@ use crate::core::{
     models::{callback_progress::ProgressCallback, core_models::Machine},
     readers::{
         catver_reader, history_reader, languages_reader, mame_reader, nplayers_reader,
-        resources_reader, series_reader,
+        resources_reader, series_reader, software_list_reader,
     },
 };
 use regex::Regex;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::error::Error;
 
@@ -23,8 +24,11 @@ use std::error::Error;
 /// - `Series`: Represents data related to game series, grouping related titles together.
 /// - `History`: Represents historical data, trivia, and other contextual information related to games.
 /// - `Resources`: Represents additional resources like images, videos, and other media related to MAME games.
+/// - `Software`: Represents the software lists (cartridges, disks, tapes) that can be loaded by MAME machines.
+///   It is not part of `all_variants`, so it is only handled when requested explicitly.
 ///
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MameDataType {
     /// Represents the core MAME data, including ROM information and basic metadata.
     Mame,
@@ -40,15 +44,22 @@ pub enum MameDataType {
     History,
     /// Represents additional resources like images, videos, and other media related to MAME games.
     Resources,
+    /// Represents the software lists (cartridges, disks, tapes) that can be loaded by MAME machines.
+    Software,
 }
 
-/// Returns a slice containing all variants of the `MameDataType` enum.
+/// Returns a slice containing the variants of the `MameDataType` enum handled by the batch functions.
 ///
-/// This function provides a static reference to an array containing every possible variant of the `MameDataType` enum.
-/// It is useful when you need to iterate over or perform operations on all data types managed by the application.
+/// This function provides a static reference to an array containing the data types downloaded, unpacked, read and
+/// checked by `download_files`, `unpack_files`, `read_files` and `workspace_status`. It is useful when you need to
+/// iterate over or perform operations on all data types managed by the application.
+///
+/// `MameDataType::Software` is not included, as the software list pack is large and describes software titles
+/// rather than machines. It is opt-in: download, unpack and read it explicitly with `download_file`, `unpack_file`
+/// and `read_file`.
 ///
 /// # Returns
-/// A static slice (`&'static [MameDataType]`) containing all `MameDataType` variants.
+/// A static slice (`&'static [MameDataType]`) containing every `MameDataType` variant except `Software`.
 ///
 impl MameDataType {
     pub fn all_variants() -> &'static [MameDataType] {
@@ -76,9 +87,11 @@ impl MameDataType {
 /// - `source_match`: A static string slice (`&'static str`) used as a substring to match the relevant download link.
 /// - `zip_file_pattern`: A `Regex` pattern that matches the specific zip files associated with this data type.
 /// - `data_file_pattern`: A `Regex` pattern that matches the internal files within the downloaded zip files.
-/// - `read_function`: A function pointer of type `fn(&str) -> Result<(), Box<dyn std::error::Error>>`
-///   that is intended to read and process the extracted data file. This can be used to invoke specific parsers or handlers
+/// - `read_function`: A function pointer of type `fn(&str, usize, ProgressCallback) -> Result<HashMap<String, Machine>, Box<dyn std::error::Error + Send + Sync>>`,
+///   taking the file path and the size of the read buffers, that is intended to read and process the extracted data file. This can be used to invoke specific parsers or handlers
 ///   based on the data type.
+/// - `count_function`: A function pointer of type `fn(&str) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>`
+///   that counts the entries of the extracted data file without processing them.
 ///
 pub struct MameDataTypeDetails {
     pub name: &'static str,
@@ -88,8 +101,10 @@ pub struct MameDataTypeDetails {
     pub data_file_pattern: Regex,
     pub read_function: fn(
         file_path: &str,
+        buffer_size: usize,
         progress_callback: ProgressCallback,
     ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>,
+    pub count_function: fn(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>>,
 }
 
 /// Retrieves the details for a given `MameDataType`.
@@ -118,7 +133,8 @@ pub(crate) fn get_data_type_details(data_type: MameDataType) -> MameDataTypeDeta
             source_match: "download/?tipo=dat_mame&file=/dats/MAME/packs/MAME_Dats",
             zip_file_pattern: Regex::new(r"^MAME_Dats_\d+\.7z$").unwrap(),
             data_file_pattern: Regex::new(r"MAME\s+[0-9]*\.[0-9]+\.dat").unwrap(),
-            read_function: mame_reader::read_mame_file,
+            read_function: mame_reader::read_mame_file_with_buffer_size,
+            count_function: mame_reader::count_file_elements,
         },
         MameDataType::Languages => MameDataTypeDetails {
             name: "Languages",
@@ -126,7 +142,8 @@ pub(crate) fn get_data_type_details(data_type: MameDataType) -> MameDataTypeDeta
             source_match: "download",
             zip_file_pattern: Regex::new(r"^pS_Languages_\d+\.zip$").unwrap(),
             data_file_pattern: Regex::new(r"languages.ini").unwrap(),
-            read_function: languages_reader::read_languages_file,
+            read_function: languages_reader::read_languages_file_with_buffer_size,
+            count_function: languages_reader::count_total_elements,
         },
         MameDataType::NPlayers => MameDataTypeDetails {
             name: "NPlayers",
@@ -134,7 +151,8 @@ pub(crate) fn get_data_type_details(data_type: MameDataType) -> MameDataTypeDeta
             source_match: "files",
             zip_file_pattern: Regex::new(r"^nplayers0\d+\.zip$").unwrap(),
             data_file_pattern: Regex::new(r"nplayers.ini").unwrap(),
-            read_function: nplayers_reader::read_nplayers_file,
+            read_function: nplayers_reader::read_nplayers_file_with_buffer_size,
+            count_function: nplayers_reader::count_total_elements,
         },
         MameDataType::Catver => MameDataTypeDetails {
             name: "Catver",
@@ -142,7 +160,8 @@ pub(crate) fn get_data_type_details(data_type: MameDataType) -> MameDataTypeDeta
             source_match: "download",
             zip_file_pattern: Regex::new(r"^pS_CatVer_\d+\.zip$").unwrap(),
             data_file_pattern: Regex::new(r"catver.ini").unwrap(),
-            read_function: catver_reader::read_catver_file,
+            read_function: catver_reader::read_catver_file_with_buffer_size,
+            count_function: catver_reader::count_total_elements,
         },
         MameDataType::Series => MameDataTypeDetails {
             name: "Series",
@@ -150,7 +169,8 @@ pub(crate) fn get_data_type_details(data_type: MameDataType) -> MameDataTypeDeta
             source_match: "download",
             zip_file_pattern: Regex::new(r"^pS_Series_\d+\.zip$").unwrap(),
             data_file_pattern: Regex::new(r"series.ini").unwrap(),
-            read_function: series_reader::read_series_file,
+            read_function: series_reader::read_series_file_with_buffer_size,
+            count_function: series_reader::count_total_elements,
         },
         MameDataType::History => MameDataTypeDetails {
             name: "History",
@@ -158,7 +178,8 @@ pub(crate) fn get_data_type_details(data_type: MameDataType) -> MameDataTypeDeta
             source_match: "dats",
             zip_file_pattern: Regex::new(r"^history\d+\.zip$").unwrap(),
             data_file_pattern: Regex::new(r"history.xml").unwrap(),
-            read_function: history_reader::read_history_file,
+            read_function: history_reader::read_history_file_with_buffer_size,
+            count_function: history_reader::count_file_elements,
         },
         MameDataType::Resources => MameDataTypeDetails {
             name: "Resources",
@@ -166,7 +187,24 @@ pub(crate) fn get_data_type_details(data_type: MameDataType) -> MameDataTypeDeta
             source_match: "download/?tipo=dat_resource&file=/dats/cmdats/pS_AllProject_",
             zip_file_pattern: Regex::new(r"^pS_AllProject_\d{8}_\d+_\([a-zA-Z]+\)\.zip$").unwrap(),
             data_file_pattern: Regex::new(r"^pS_AllProject_\d{8}_\d+_\([a-zA-Z]+\)\.dat$").unwrap(),
-            read_function: resources_reader::read_resources_file,
+            read_function: |file_path, buffer_size, progress_callback| {
+                resources_reader::read_resources_file_with_buffer_size(
+                    file_path,
+                    None,
+                    buffer_size,
+                    progress_callback,
+                )
+            },
+            count_function: resources_reader::count_file_elements,
+        },
+        MameDataType::Software => MameDataTypeDetails {
+            name: "Software",
+            source: "https://www.progettosnaps.net/dats/MAME",
+            source_match: "download/?tipo=dat_mame&file=/dats/MAME/packs/MAME_SWLists",
+            zip_file_pattern: Regex::new(r"^MAME_SWLists_\d+\.7z$").unwrap(),
+            data_file_pattern: Regex::new(r"(?i)^[a-z0-9_\-]+\.xml$").unwrap(),
+            read_function: software_list_reader::read_software_list_file_with_buffer_size,
+            count_function: software_list_reader::count_file_elements,
         },
     }
 }
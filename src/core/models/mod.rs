@@ -1,4 +1,5 @@
 pub mod callback_progress;
+pub mod cancellation;
 pub mod collections_helper;
 pub mod core_models;
 pub mod mame_data_types;
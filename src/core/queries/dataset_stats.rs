@@ -0,0 +1,196 @@
+use crate::core::data_cleanup::name_normalization::{parse_year, YearValue};
+use crate::models::Machine;
+use std::collections::HashMap;
+
+/// The number of manufacturers listed in `DatasetStats::top_manufacturers`.
+const TOP_MANUFACTURERS_COUNT: usize = 10;
+
+/// Represents an overview of a set of machines.
+///
+/// The `DatasetStats` struct is returned by `dataset_stats` and bundles the aggregations
+/// most commonly needed to describe a dataset.
+///
+/// # Fields
+/// - `total_machines`: The number of machines.
+/// - `parents`: The number of machines that are not a clone of another machine.
+/// - `clones`: The number of machines that are a clone of another machine.
+/// - `total_roms`: The number of ROMs of all the machines.
+/// - `min_roms`: The lowest number of ROMs of a machine.
+/// - `max_roms`: The highest number of ROMs of a machine.
+/// - `average_roms`: The average number of ROMs per machine.
+/// - `largest_machine`: The name of the machine with the largest total ROM size, along with that size in bytes.
+/// - `min_year`: The earliest year of a machine, ignoring the unknown or partial years (e.g. `198?`).
+///   The years are interpreted with `parse_year`, and a range of years counts from its first to its last year.
+/// - `max_year`: The latest year of a machine, ignoring the unknown or partial years.
+/// - `top_manufacturers`: The manufacturers with the most machines, with their machine count,
+///   sorted by count in descending order, then by name.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DatasetStats {
+    /// The number of machines.
+    pub total_machines: usize,
+    /// The number of machines that are not a clone of another machine.
+    pub parents: usize,
+    /// The number of machines that are a clone of another machine.
+    pub clones: usize,
+    /// The number of ROMs of all the machines.
+    pub total_roms: usize,
+    /// The lowest number of ROMs of a machine.
+    pub min_roms: usize,
+    /// The highest number of ROMs of a machine.
+    pub max_roms: usize,
+    /// The average number of ROMs per machine.
+    pub average_roms: f64,
+    /// The name and total ROM size, in bytes, of the largest machine.
+    pub largest_machine: Option<(String, u64)>,
+    /// The earliest known year.
+    pub min_year: Option<u32>,
+    /// The latest known year.
+    pub max_year: Option<u32>,
+    /// The manufacturers with the most machines, with their machine count.
+    pub top_manufacturers: Vec<(String, usize)>,
+}
+
+/// Computes an overview of the machines in a single pass over the map.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `DatasetStats` - The statistics of the machines. Every field is zero or empty if `machines` is empty.
+///   Ties for the largest machine are resolved by name, so the result does not depend on the map order.
+///
+pub fn dataset_stats(machines: &HashMap<String, Machine>) -> DatasetStats {
+    let mut stats = DatasetStats {
+        total_machines: machines.len(),
+        min_roms: usize::MAX,
+        ..Default::default()
+    };
+    let mut manufacturers: HashMap<&str, usize> = HashMap::new();
+
+    for machine in machines.values() {
+        if machine.clone_of.is_some() {
+            stats.clones += 1;
+        } else {
+            stats.parents += 1;
+        }
+
+        let rom_count = machine.roms.len();
+        stats.total_roms += rom_count;
+        stats.min_roms = stats.min_roms.min(rom_count);
+        stats.max_roms = stats.max_roms.max(rom_count);
+
+        let rom_size: u64 = machine.roms.iter().map(|rom| rom.size).sum();
+        let is_largest = match &stats.largest_machine {
+            Some((name, size)) => rom_size > *size || (rom_size == *size && machine.name < *name),
+            None => true,
+        };
+        if is_largest {
+            stats.largest_machine = Some((machine.name.clone(), rom_size));
+        }
+
+        let years = match parse_year(machine.year.as_deref().unwrap_or("")) {
+            YearValue::Exact(year) => Some((year, year)),
+            YearValue::Range(start, end) => Some((start, end)),
+            YearValue::Decade(_) | YearValue::Unknown => None,
+        };
+        if let Some((start, end)) = years {
+            let (start, end) = (u32::from(start), u32::from(end));
+            stats.min_year = Some(stats.min_year.map_or(start, |min| min.min(start)));
+            stats.max_year = Some(stats.max_year.map_or(end, |max| max.max(end)));
+        }
+
+        if let Some(manufacturer) = machine
+            .manufacturer
+            .as_deref()
+            .filter(|manufacturer| !manufacturer.is_empty())
+        {
+            *manufacturers.entry(manufacturer).or_default() += 1;
+        }
+    }
+
+    if machines.is_empty() {
+        stats.min_roms = 0;
+    } else {
+        stats.average_roms = stats.total_roms as f64 / machines.len() as f64;
+    }
+
+    let mut top_manufacturers: Vec<(String, usize)> = manufacturers
+        .into_iter()
+        .map(|(manufacturer, count)| (manufacturer.to_string(), count))
+        .collect();
+    top_manufacturers.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_manufacturers.truncate(TOP_MANUFACTURERS_COUNT);
+    stats.top_manufacturers = top_manufacturers;
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Rom;
+
+    fn rom(size: u64) -> Rom {
+        Rom {
+            name: format!("rom_{}", size),
+            size,
+            merge: None,
+            status: None,
+            crc: None,
+            sha1: None,
+            region: None,
+            offset: None,
+            bios: None,
+        }
+    }
+
+    #[test]
+    fn test_dataset_stats() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .year("1980")
+                .manufacturer("Namco")
+                .add_rom(rom(4096))
+                .add_rom(rom(4096))
+                .build(),
+            Machine::builder("puckman")
+                .clone_of("pacman")
+                .year("1980")
+                .manufacturer("Namco")
+                .add_rom(rom(4096))
+                .build(),
+            Machine::builder("dkong")
+                .year("1981")
+                .manufacturer("Nintendo")
+                .add_rom(rom(16384))
+                .build(),
+            Machine::builder("unknown").year("198?").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let stats = dataset_stats(&machines);
+
+        assert_eq!(stats.total_machines, 4);
+        assert_eq!(stats.parents, 3);
+        assert_eq!(stats.clones, 1);
+        assert_eq!(stats.total_roms, 4);
+        assert_eq!(stats.min_roms, 0);
+        assert_eq!(stats.max_roms, 2);
+        assert_eq!(stats.average_roms, 1.0);
+        assert_eq!(stats.largest_machine, Some(("dkong".to_string(), 16384)));
+        assert_eq!(stats.min_year, Some(1980));
+        assert_eq!(stats.max_year, Some(1981));
+        assert_eq!(
+            stats.top_manufacturers,
+            vec![("Namco".to_string(), 2), ("Nintendo".to_string(), 1)]
+        );
+
+        assert_eq!(dataset_stats(&HashMap::new()), DatasetStats::default());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::models::Machine;
+use std::collections::HashMap;
+
+/// Finds the device references that do not point at a device of the dataset.
+///
+/// A device reference of a machine names a device machine (`is_device == Some(true)`) the machine depends on.
+/// A reference is missing if no machine of that name is present in `machines`, or if that machine is not
+/// marked as a device. This allows to detect incomplete datasets before exporting them.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `HashMap<String, Vec<String>>` - A `HashMap` where the key is the name of a machine with missing
+///   device references, and the value is the list of the missing device names, in the order they are
+///   referenced and without duplicates. Machines without missing references are not included.
+///
+pub fn find_missing_device_refs(
+    machines: &HashMap<String, Machine>,
+) -> HashMap<String, Vec<String>> {
+    machines
+        .iter()
+        .filter_map(|(name, machine)| {
+            let mut missing: Vec<String> = Vec::new();
+
+            for device_ref in &machine.device_refs {
+                let is_device = machines
+                    .get(&device_ref.name)
+                    .is_some_and(|device| device.is_device == Some(true));
+
+                if !is_device && !missing.contains(&device_ref.name) {
+                    missing.push(device_ref.name.clone());
+                }
+            }
+
+            if missing.is_empty() {
+                None
+            } else {
+                Some((name.clone(), missing))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DeviceRef;
+
+    fn device_ref(name: &str) -> DeviceRef {
+        DeviceRef {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_missing_device_refs() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .add_device_ref(device_ref("z80"))
+                .add_device_ref(device_ref("namco"))
+                .add_device_ref(device_ref("namco"))
+                .build(),
+            Machine::builder("galaga")
+                .add_device_ref(device_ref("z80"))
+                .add_device_ref(device_ref("dkong"))
+                .build(),
+            Machine::builder("dkong")
+                .add_device_ref(device_ref("z80"))
+                .build(),
+            Machine::builder("z80").is_device(true).build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let missing = find_missing_device_refs(&machines);
+
+        assert_eq!(missing.len(), 2);
+        assert_eq!(missing["pacman"], vec!["namco".to_string()]);
+        assert_eq!(missing["galaga"], vec!["dkong".to_string()]);
+        assert!(!missing.contains_key("dkong"));
+    }
+}
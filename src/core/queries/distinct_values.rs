@@ -0,0 +1,132 @@
+use crate::models::Machine;
+use std::collections::{BTreeSet, HashMap};
+
+/// Represents a text field of the machines whose distinct values can be listed.
+///
+/// # Variants
+///
+/// * `SourceFile` - The driver source file of the machine.
+/// * `Year` - The release year of the machine.
+/// * `Manufacturer` - The manufacturer of the machine.
+/// * `DriverStatus` - The status of the driver of the machine.
+/// * `Players` - The number of players of the machine.
+/// * `Series` - The series the machine belongs to.
+/// * `Category` - The category of the machine.
+/// * `Subcategory` - The subcategory of the machine.
+/// * `Language` - The languages of the machine.
+/// * `VersionAdded` - The MAME version the machine was added in.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MachineField {
+    /// The `source_file` field.
+    SourceFile,
+    /// The `year` field.
+    Year,
+    /// The `manufacturer` field.
+    Manufacturer,
+    /// The `driver_status` field.
+    DriverStatus,
+    /// The `players` field.
+    Players,
+    /// The `series` field.
+    Series,
+    /// The `category` field.
+    Category,
+    /// The `subcategory` field.
+    Subcategory,
+    /// Each entry of the `languages` field.
+    Language,
+    /// The `version_added` field.
+    VersionAdded,
+}
+
+impl MachineField {
+    /// Returns the values of this field for a machine.
+    fn values_of<'a>(&self, machine: &'a Machine) -> Vec<&'a str> {
+        let value = match self {
+            MachineField::SourceFile => &machine.source_file,
+            MachineField::Year => &machine.year,
+            MachineField::Manufacturer => &machine.manufacturer,
+            MachineField::DriverStatus => &machine.driver_status,
+            MachineField::Players => &machine.players,
+            MachineField::Series => &machine.series,
+            MachineField::Category => &machine.category,
+            MachineField::Subcategory => &machine.subcategory,
+            MachineField::VersionAdded => &machine.version_added,
+            MachineField::Language => {
+                return machine.languages.iter().map(String::as_str).collect();
+            }
+        };
+
+        value.as_deref().into_iter().collect()
+    }
+}
+
+/// Lists the distinct values of a field across the machines.
+///
+/// Unlike the `get_*_list` helpers, which count the machines of each value, this only collects
+/// the values, which allows to discover the vocabulary of a dataset before writing filters.
+/// Empty values are ignored.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `field` - The `MachineField` to list the values of.
+///
+/// # Returns
+///
+/// * `BTreeSet<String>` - The sorted set of the distinct non-empty values of the field.
+///
+pub fn distinct_values(
+    machines: &HashMap<String, Machine>,
+    field: MachineField,
+) -> BTreeSet<String> {
+    machines
+        .values()
+        .flat_map(|machine| field.values_of(machine))
+        .filter(|value| !value.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distinct_values() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .driver_status("good")
+                .add_language("English")
+                .build(),
+            Machine::builder("puckman")
+                .driver_status("good")
+                .add_language("Japanese")
+                .add_language("English")
+                .build(),
+            Machine::builder("galaga")
+                .driver_status("imperfect")
+                .build(),
+            Machine::builder("dkong").driver_status("").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let statuses = distinct_values(&machines, MachineField::DriverStatus);
+        assert_eq!(
+            statuses.into_iter().collect::<Vec<_>>(),
+            vec!["good", "imperfect"]
+        );
+
+        let languages = distinct_values(&machines, MachineField::Language);
+        assert_eq!(
+            languages.into_iter().collect::<Vec<_>>(),
+            vec!["English", "Japanese"]
+        );
+
+        assert!(distinct_values(&machines, MachineField::Series).is_empty());
+    }
+}
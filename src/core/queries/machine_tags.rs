@@ -0,0 +1,203 @@
+use crate::{
+    core::data_cleanup::name_normalization::{
+        extract_alternate_titles, normalize_machine_name, normalize_manufacturer_name,
+    },
+    models::Machine,
+};
+use std::collections::HashMap;
+
+/// The regions recognized in the descriptions of the machines, e.g. `Puck Man (Japan set 1)`.
+const REGIONS: &[&str] = &[
+    "World",
+    "USA",
+    "US",
+    "Japan",
+    "Europe",
+    "Asia",
+    "Korea",
+    "Taiwan",
+    "Hong Kong",
+    "China",
+    "Brazil",
+    "Spain",
+    "France",
+    "Germany",
+    "Italy",
+    "UK",
+    "Australia",
+];
+
+/// Reduces a machine to a list of searchable tags.
+///
+/// The tags are made of the words of the normalized name of the machine and of its alternate titles
+/// (see `extract_alternate_titles`), followed by its normalized manufacturer, category, subcategory, series, year, languages, and the regions found between
+/// parentheses in its description. The normalized values of the extended data are used when available.
+/// Every tag is trimmed and lowercased, and empty and duplicated tags are removed.
+///
+/// # Arguments
+///
+/// * `machine` - A reference to the `Machine` to be tagged.
+///
+/// # Returns
+///
+/// * `Vec<String>` - The tags of the machine, in the order described above.
+///
+pub fn machine_tags(machine: &Machine) -> Vec<String> {
+    let extended_data = machine.extended_data.as_ref();
+
+    let name = extended_data
+        .and_then(|extended_data| extended_data.name.clone())
+        .unwrap_or_else(|| normalize_machine_name(&machine.description));
+    let manufacturer = extended_data
+        .and_then(|extended_data| extended_data.manufacturer.clone())
+        .or_else(|| {
+            machine
+                .manufacturer
+                .as_ref()
+                .map(|_| normalize_manufacturer_name(&machine.manufacturer))
+        });
+    let year = extended_data
+        .and_then(|extended_data| extended_data.year.clone())
+        .or_else(|| machine.year.clone());
+    let alternate_titles = extended_data
+        .map(|extended_data| extended_data.alternate_titles.clone())
+        .filter(|alternate_titles| !alternate_titles.is_empty())
+        .unwrap_or_else(|| extract_alternate_titles(machine));
+
+    let mut tags: Vec<String> = Vec::new();
+    let mut push = |tag: &str| {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    };
+
+    name.split(|c: char| !c.is_alphanumeric())
+        .for_each(&mut push);
+    alternate_titles
+        .iter()
+        .flat_map(|title| title.split(|c: char| !c.is_alphanumeric()))
+        .for_each(&mut push);
+    for value in [
+        &manufacturer,
+        &machine.category,
+        &machine.subcategory,
+        &machine.series,
+        &year,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        push(value);
+    }
+    machine.languages.iter().for_each(|language| push(language));
+    regions_of(machine.description.as_deref().unwrap_or_default())
+        .into_iter()
+        .for_each(push);
+
+    tags
+}
+
+/// Builds an index of the machines by tag.
+///
+/// Each machine is tagged with `machine_tags`, and every tag is mapped to the names of the machines having it.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `HashMap<String, Vec<String>>` - A `HashMap` where the key is a tag, and the value is the list
+///   of the names of the machines having it, sorted by name.
+///
+pub fn build_tag_index(machines: &HashMap<String, Machine>) -> HashMap<String, Vec<String>> {
+    let mut index: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (name, machine) in machines {
+        for tag in machine_tags(machine) {
+            index.entry(tag).or_default().push(name.clone());
+        }
+    }
+
+    for names in index.values_mut() {
+        names.sort_unstable();
+    }
+
+    index
+}
+
+/// Finds the known regions mentioned between parentheses in a description.
+fn regions_of(description: &str) -> Vec<&'static str> {
+    description
+        .split('(')
+        .skip(1)
+        .filter_map(|group| group.split(')').next())
+        .flat_map(|group| {
+            REGIONS.iter().copied().filter(move |region| {
+                group.split(',').any(|part| {
+                    part.trim() == *region || part.trim().starts_with(&format!("{} ", region))
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_machine_tags() {
+        let machine = Machine::builder("puckman")
+            .description("Puck Man (Japan set 1)")
+            .manufacturer("Namco")
+            .year("1980")
+            .category("Maze")
+            .subcategory("Collect")
+            .series("Pac-Man")
+            .add_language("Japanese")
+            .build();
+
+        assert_eq!(
+            machine_tags(&machine),
+            vec!["puck", "man", "namco", "maze", "collect", "pac-man", "1980", "japanese", "japan"]
+        );
+    }
+
+    #[test]
+    fn test_build_tag_index() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .description("Pac-Man (Midway)")
+                .category("Maze")
+                .build(),
+            Machine::builder("puckman")
+                .description("Puck Man (Japan set 1)")
+                .category("Maze")
+                .build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let index = build_tag_index(&machines);
+
+        assert_eq!(index["maze"], vec!["pacman", "puckman"]);
+        assert_eq!(index["japan"], vec!["puckman"]);
+        assert!(!index.contains_key("midway"));
+    }
+
+    #[test]
+    fn test_machine_tags_alternate_titles() {
+        let machine = Machine::builder("lrescue")
+            .description("Lunar Rescue / Destination Earth (also known as Moon Rescue)")
+            .build();
+
+        assert_eq!(
+            machine_tags(&machine),
+            vec!["lunar", "rescue", "destination", "earth", "moon"]
+        );
+    }
+}
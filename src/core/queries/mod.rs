@@ -0,0 +1,8 @@
+pub mod dataset_stats;
+pub mod device_refs;
+pub mod distinct_values;
+pub mod machine_tags;
+pub mod parent_relationships;
+pub mod partition;
+pub mod rom_sets;
+pub mod sorting;
@@ -0,0 +1,114 @@
+use crate::models::Machine;
+use std::collections::HashMap;
+
+/// Represents the kind of parent a machine has.
+///
+/// In the MAME data, the `rom_of` attribute may reference a BIOS set instead of a gameplay parent,
+/// e.g. every Neo Geo game has `rom_of="neogeo"`. This enum allows to tell both cases apart.
+///
+/// # Variants
+///
+/// * `GameplayParent` - The machine is a clone of, or uses the ROMs of, another machine that is not a BIOS.
+/// * `BiosParent` - The parent of the machine is a BIOS set.
+/// * `None` - The machine has no parent.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentKind {
+    /// The parent of the machine is a machine that is not a BIOS.
+    GameplayParent,
+    /// The parent of the machine is a BIOS set.
+    BiosParent,
+    /// The machine has no parent.
+    None,
+}
+
+/// Classifies the parent relationship of every machine.
+///
+/// The parent of a machine is its `clone_of` machine, or its `rom_of` machine when it is not a clone.
+/// The relationship is classified as `BiosParent` if the parent is a machine with `is_bios` set to `true`,
+/// and as `GameplayParent` otherwise, including when the parent is not present in `machines`.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `HashMap<String, ParentKind>` - A `HashMap` where the key is the machine name, and the value
+///   is the kind of parent of the machine.
+///
+pub fn classify_parent_relationship(
+    machines: &HashMap<String, Machine>,
+) -> HashMap<String, ParentKind> {
+    machines
+        .iter()
+        .map(|(name, machine)| (name.clone(), parent_kind(machines, machine)))
+        .collect()
+}
+
+/// Gets the kind of parent of a machine.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` containing all the machines.
+/// * `machine` - A reference to the `Machine` whose parent is classified.
+///
+/// # Returns
+///
+/// * `ParentKind` - The kind of parent of the machine.
+///
+fn parent_kind(machines: &HashMap<String, Machine>, machine: &Machine) -> ParentKind {
+    let parent = machine.clone_of.as_deref().or(machine.rom_of.as_deref());
+
+    match parent {
+        Some(parent) => {
+            let is_bios = machines
+                .get(parent)
+                .and_then(|parent| parent.is_bios)
+                .unwrap_or(false);
+
+            if is_bios {
+                ParentKind::BiosParent
+            } else {
+                ParentKind::GameplayParent
+            }
+        }
+        None => ParentKind::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_parent_relationship() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("neogeo").is_bios(true).build(),
+            Machine::builder("mslug").rom_of("neogeo").build(),
+            Machine::builder("pacman").build(),
+            Machine::builder("puckman")
+                .clone_of("pacman")
+                .rom_of("pacman")
+                .build(),
+            Machine::builder("mslugb")
+                .clone_of("mslug")
+                .rom_of("mslug")
+                .build(),
+            Machine::builder("orphan").clone_of("missing").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let relationships = classify_parent_relationship(&machines);
+
+        assert_eq!(relationships["neogeo"], ParentKind::None);
+        assert_eq!(relationships["pacman"], ParentKind::None);
+        assert_eq!(relationships["mslug"], ParentKind::BiosParent);
+        assert_eq!(relationships["puckman"], ParentKind::GameplayParent);
+        assert_eq!(relationships["mslugb"], ParentKind::GameplayParent);
+        assert_eq!(relationships["orphan"], ParentKind::GameplayParent);
+    }
+}
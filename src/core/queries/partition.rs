@@ -0,0 +1,60 @@
+use crate::models::Machine;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Partitions the machines into several maps according to a key.
+///
+/// Each machine is cloned into the map of the key returned by `key_fn`, so the machines can be split by any
+/// attribute (manufacturer, category, decade...) to be processed or exported per group.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+/// * `key_fn` - A function returning the key of the group a machine belongs to.
+///
+/// # Returns
+///
+/// * `HashMap<K, HashMap<String, Machine>>` - A `HashMap` where the key is the group key, and the value
+///   is a `HashMap` with the machines of that group, keyed by machine name.
+///
+pub fn partition_by<K: Eq + Hash>(
+    machines: &HashMap<String, Machine>,
+    key_fn: impl Fn(&Machine) -> K,
+) -> HashMap<K, HashMap<String, Machine>> {
+    let mut partitions: HashMap<K, HashMap<String, Machine>> = HashMap::new();
+
+    for (name, machine) in machines {
+        partitions
+            .entry(key_fn(machine))
+            .or_default()
+            .insert(name.clone(), machine.clone());
+    }
+
+    partitions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_by_manufacturer() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman").manufacturer("Namco").build(),
+            Machine::builder("galaga").manufacturer("Namco").build(),
+            Machine::builder("dkong").manufacturer("Nintendo").build(),
+            Machine::builder("z80").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let partitions = partition_by(&machines, |machine| machine.manufacturer.clone());
+
+        assert_eq!(partitions.len(), 3);
+        assert_eq!(partitions[&Some("Namco".to_string())].len(), 2);
+        assert!(partitions[&Some("Nintendo".to_string())].contains_key("dkong"));
+        assert!(partitions[&None].contains_key("z80"));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::models::Machine;
+use std::collections::{HashMap, HashSet};
+
+/// Groups the machines into ROM sets, keyed by the top-level parent of each set.
+///
+/// A ROM set contains a parent machine and every machine whose ROMs are stored together with it in a
+/// merged set layout: its clones, the clones of its clones, and the machines using its ROMs through `rom_of`.
+/// For each machine, the `clone_of` relation is followed first, then `rom_of`, until a machine without a parent
+/// is reached. `rom_of` references to BIOS machines are not followed, as BIOS sets are stored separately.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `HashMap<String, Vec<String>>` - A `HashMap` where the key is the name of the top-level parent,
+///   and the value is the sorted list of every machine name in its ROM set, including the parent.
+///   References to machines that are not present in `machines` end the chain at the last known machine.
+///
+pub fn build_rom_sets(machines: &HashMap<String, Machine>) -> HashMap<String, Vec<String>> {
+    let mut rom_sets: HashMap<String, Vec<String>> = HashMap::new();
+
+    for name in machines.keys() {
+        let parent = find_top_parent(machines, name);
+        rom_sets.entry(parent).or_default().push(name.clone());
+    }
+
+    for names in rom_sets.values_mut() {
+        names.sort_unstable();
+    }
+
+    rom_sets
+}
+
+/// Finds the top-level parent of the ROM set a machine belongs to.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` containing all the machines.
+/// * `name` - The name of the machine whose top-level parent is searched.
+///
+/// # Returns
+///
+/// * `String` - The name of the top-level parent, which is the machine itself if it has no parent.
+///   A circular reference ends the chain at the first repeated machine.
+///
+fn find_top_parent(machines: &HashMap<String, Machine>, name: &str) -> String {
+    let mut visited = HashSet::new();
+    let mut current = name;
+
+    while visited.insert(current) {
+        let machine = &machines[current];
+        let parent = machine
+            .clone_of
+            .as_deref()
+            .or(machine.rom_of.as_deref())
+            .and_then(|parent| machines.get_key_value(parent))
+            .filter(|(_, parent)| !parent.is_bios.unwrap_or(false));
+
+        match parent {
+            Some((parent_name, _)) => current = parent_name,
+            None => break,
+        }
+    }
+
+    current.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_rom_sets() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("neogeo").is_bios(true).build(),
+            Machine::builder("mslug").rom_of("neogeo").build(),
+            Machine::builder("pacman").build(),
+            Machine::builder("puckman")
+                .clone_of("pacman")
+                .rom_of("pacman")
+                .build(),
+            Machine::builder("pacmanf")
+                .clone_of("puckman")
+                .rom_of("puckman")
+                .build(),
+            Machine::builder("orphan").clone_of("missing").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let rom_sets = build_rom_sets(&machines);
+
+        assert_eq!(rom_sets.len(), 4);
+        assert_eq!(rom_sets["pacman"], vec!["pacman", "pacmanf", "puckman"]);
+        assert_eq!(rom_sets["mslug"], vec!["mslug"]);
+        assert_eq!(rom_sets["neogeo"], vec!["neogeo"]);
+        assert_eq!(rom_sets["orphan"], vec!["orphan"]);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::models::Machine;
+use std::collections::HashMap;
+
+/// Returns the machines sorted by name.
+///
+/// This gives a stable, deterministic ordering over the machines, which are stored in a `HashMap`
+/// and therefore have no meaningful iteration order.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `Vec<&Machine>` - The machines sorted by name.
+///
+pub fn machines_sorted(machines: &HashMap<String, Machine>) -> Vec<&Machine> {
+    let mut sorted: Vec<&Machine> = machines.values().collect();
+    sorted.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    sorted
+}
+
+/// Returns the machines sorted by year, then by name.
+///
+/// Machines without a year are placed at the end.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `Vec<&Machine>` - The machines sorted by year, then by name.
+///
+pub fn machines_sorted_by_year(machines: &HashMap<String, Machine>) -> Vec<&Machine> {
+    machines_sorted_by(machines, |machine| machine.year.as_deref())
+}
+
+/// Returns the machines sorted by manufacturer, then by name.
+///
+/// Machines without a manufacturer are placed at the end.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` where the key is a `String` representing
+///   the machine's name, and the value is a `Machine` struct containing the machine details.
+///
+/// # Returns
+///
+/// * `Vec<&Machine>` - The machines sorted by manufacturer, then by name.
+///
+pub fn machines_sorted_by_manufacturer(machines: &HashMap<String, Machine>) -> Vec<&Machine> {
+    machines_sorted_by(machines, |machine| machine.manufacturer.as_deref())
+}
+
+/// Sorts the machines by an optional field, then by name, placing the machines without a value at the end.
+///
+/// # Arguments
+///
+/// * `machines` - A reference to a `HashMap` containing the machines to sort.
+/// * `field` - A function returning the value of the field to sort by.
+///
+/// # Returns
+///
+/// * `Vec<&Machine>` - The sorted machines.
+///
+fn machines_sorted_by(
+    machines: &HashMap<String, Machine>,
+    field: fn(&Machine) -> Option<&str>,
+) -> Vec<&Machine> {
+    let mut sorted: Vec<&Machine> = machines.values().collect();
+    sorted.sort_unstable_by(|a, b| {
+        (field(a).is_none(), field(a), &a.name).cmp(&(field(b).is_none(), field(b), &b.name))
+    });
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_machines_sorted_by_year() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("galaga").year("1981").build(),
+            Machine::builder("pacman").year("1980").build(),
+            Machine::builder("unknown").build(),
+            Machine::builder("digdug").year("1982").build(),
+            Machine::builder("defender").year("1981").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let names: Vec<&str> = machines_sorted(&machines)
+            .iter()
+            .map(|machine| machine.name.as_str())
+            .collect();
+        assert_eq!(names, ["defender", "digdug", "galaga", "pacman", "unknown"]);
+
+        let names: Vec<&str> = machines_sorted_by_year(&machines)
+            .iter()
+            .map(|machine| machine.name.as_str())
+            .collect();
+        assert_eq!(names, ["pacman", "defender", "galaga", "digdug", "unknown"]);
+    }
+}
@@ -1,13 +1,18 @@
+use crate::core::models::cancellation::check_cancelled_at;
 use crate::{
     core::models::{
-        callback_progress::{CallbackType, ProgressCallback, ProgressInfo},
+        callback_progress::{ProgressCallback, ProgressInfo},
         core_models::Machine,
     },
-    helpers::callback_progress_helper::get_progress_info,
+    helpers::{
+        callback_progress_helper::get_progress_info,
+        file_system_helpers::{
+            open_text_file, open_text_file_with_capacity, read_text, strip_bom, DEFAULT_BUFFER_SIZE,
+        },
+    },
 };
 use anyhow::Context;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, Read};
 use std::{collections::HashMap, error::Error};
 
 /// Reads and processes a catver.ini file to extract machine categories and subcategories.
@@ -15,6 +20,8 @@ use std::{collections::HashMap, error::Error};
 /// This function reads a specified catver.ini file line by line, extracts machine information,
 /// and populates a `HashMap` with machine names as keys and their corresponding `Machine` structs as values.
 /// It identifies categories, subcategories, and flags machines marked as "Mature".
+/// When the file includes a `[VerAdded]` section, the MAME version in which each machine was added is also read.
+/// The file may be gzipped (e.g. `catver.ini.gz`), in which case it is decompressed while being read.
 /// Progress updates are provided via a callback function.
 ///
 /// # Parameters
@@ -25,7 +32,7 @@ use std::{collections::HashMap, error::Error};
 /// # Returns
 /// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
 /// - On success: Contains a `HashMap` where the keys are machine names and the values are `Machine` structs
-///   with their associated categories, subcategories, and maturity flags.
+///   with their associated categories, subcategories, maturity flags, and versions added.
 /// - On failure: Contains an error if the file cannot be opened, read, or if there are issues processing its content.
 ///
 /// # Errors
@@ -33,6 +40,7 @@ use std::{collections::HashMap, error::Error};
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
 /// - The total number of elements in the file cannot be determined.
+/// - The file is empty or contains no entries.
 ///
 /// # File structure
 /// The `catver.ini` file represents configurations and data related to game classification in the MAME system.
@@ -51,103 +59,225 @@ use std::{collections::HashMap, error::Error};
 ///   - `<Category>`: The category of the game.
 ///   - `<Subcategory>`: The subcategory of the game, which may be followed by `* Mature *` if the game is marked as mature.
 ///
-/// Note: The `category` and `subcategory` are separated by ` / `, and the subcategory may or may not end with the `* Mature *` marker.
+/// - `[Mature]`: An optional section listing the names of the machines marked as mature, one per line.
+///
+/// - `[VerAdded]`: An optional section mapping machines to the MAME version in which they were added.
+///   - `<ROM Name>=<Version>`: The name of the ROM and the MAME version, e.g. `0.37b5`.
+///
+/// Note: The `category` and `subcategory` are separated by ` / `, and the value may or may not end with the `* Mature *` marker
+/// (matched case-insensitively, with or without spaces). An entry with the marker may have a category without a subcategory.
 ///
 pub fn read_catver_file(
     file_path: &str,
     progress_callback: ProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
-    let mut machines: HashMap<String, Machine> = HashMap::new();
+    read_catver_file_with_buffer_size(file_path, DEFAULT_BUFFER_SIZE, progress_callback)
+}
 
+/// Reads a catver.ini file using read buffers of the given size.
+///
+/// This function behaves like `read_catver_file`, with `buffer_size` setting the capacity of the buffered file reader.
+/// Larger buffers can improve the throughput of large files on slow disks.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_catver_file`.
+///
+pub(crate) fn read_catver_file_with_buffer_size(
+    file_path: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
     let data_file_name = file_path.split('/').last().unwrap();
 
+    read_catver(
+        || {
+            Ok(open_text_file_with_capacity(file_path, buffer_size)
+                .with_context(|| format!("Failed to open file: {}", file_path))?)
+        },
+        data_file_name,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a catver.ini file held in memory.
+///
+/// This function behaves like `read_catver_file`, reading the entries from `contents` instead of a file.
+///
+/// # Parameters
+/// - `contents`: A `&str` with the content of the catver.ini file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_catver_file`.
+///
+pub fn read_catver_from_str(
+    contents: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = strip_bom(contents);
+    read_catver(
+        || Ok(contents.as_bytes()),
+        "catver.ini data",
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a catver.ini file from any reader.
+///
+/// As the entries are counted before being processed, the whole content of `reader` is read into memory first,
+/// and is then processed like `read_catver_from_str` does. The content may be gzipped and may start with a byte order mark.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content of the catver.ini file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_catver_file`.
+///
+pub fn read_catver_from_reader(
+    reader: impl Read,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = read_text(reader)?;
+    read_catver_from_str(&contents, progress_callback)
+}
+
+/// Reads and processes the entries of a catver.ini file from a source that can be opened more than once.
+///
+/// # Parameters
+/// - `open`: A function returning a new buffered reader positioned at the start of the content.
+///   It is called twice: once to count the entries and once to process them.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_catver_file`.
+///
+fn read_catver<R: BufRead>(
+    open: impl Fn() -> Result<R, Box<dyn Error + Send + Sync>>,
+    data_file_name: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut machines: HashMap<String, Machine> = HashMap::new();
+
     // Get total elements
     progress_callback(get_progress_info(
         format!("Getting total entries for {}", data_file_name).as_str(),
     ));
 
-    let total_elements = match count_total_elements(file_path) {
+    let total_elements = match open().and_then(count_entries) {
         Ok(total_elements) => total_elements,
         Err(err) => {
-            progress_callback(ProgressInfo {
-                progress: 0,
-                total: 0,
-                message: format!("Couldn't get total entries for {}", data_file_name),
-                callback_type: CallbackType::Error,
-            });
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't get total entries for {}",
+                data_file_name
+            )));
 
             return Err(err.into());
         }
     };
 
+    if total_elements == 0 {
+        let message = format!(
+            "Data file {} is empty or contains no entries",
+            data_file_name
+        );
+        progress_callback(ProgressInfo::error(message.clone()));
+
+        return Err(message.into());
+    }
+
     progress_callback(get_progress_info(
         format!("Reading {}", data_file_name).as_str(),
     ));
 
     let to_ignore = ["[", ";", "", " "];
 
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
+    let reader = open()?;
 
     let mut processed_count = 0;
-    let batch = total_elements / 10;
+    let batch = (total_elements / 10).max(1);
+    let mut in_version_added = false;
+    let mut in_mature = false;
 
     for line in reader.lines() {
-        let line = line.with_context(|| format!("Failed to read line in file: {}", file_path))?;
+        let line =
+            line.with_context(|| format!("Failed to read line in file: {}", data_file_name))?;
         let trimmed = line.trim();
         let first_char = trimmed.chars().next().unwrap_or(' ');
 
+        if first_char == '[' {
+            // Track whether the current section holds the versions added
+            in_version_added = trimmed.eq_ignore_ascii_case("[VerAdded]");
+            in_mature = trimmed.eq_ignore_ascii_case("[Mature]");
+            continue;
+        }
+
         if to_ignore.contains(&first_char.to_string().as_str()) {
             continue;
         }
 
+        if in_mature {
+            // The mature section lists the machine names, optionally followed by a value
+            let machine_name = trimmed.split('=').next().unwrap_or_default().trim();
+            if !machine_name.is_empty() {
+                machines
+                    .entry(machine_name.to_owned())
+                    .or_insert_with(|| Machine::new(machine_name.to_owned()))
+                    .is_mature = Some(true);
+            }
+            continue;
+        }
+
         if let Some(equal_pos) = trimmed.find('=') {
             let (machine_name, value) = trimmed.split_at(equal_pos);
             let machine_name = machine_name.trim();
             let value = &value[1..].trim(); // Skip the '=' and trim the value
 
+            let (value, is_mature) = strip_mature_marker(value);
             let parts: Vec<&str> = value.split(" / ").collect();
-            if parts.len() >= 2 {
-                let category = parts[0].to_string();
-                let mut subcategory = parts[1].to_string();
-                let is_mature = subcategory.ends_with(" * Mature *");
-
-                if is_mature {
-                    subcategory = subcategory
-                        .trim_end_matches(" * Mature *")
-                        .trim()
-                        .to_string();
+            if in_version_added {
+                if !value.is_empty() {
+                    let machine = machines
+                        .entry(machine_name.to_owned())
+                        .or_insert_with(|| Machine::new(machine_name.to_owned()));
+
+                    machine.version_added = Some(value.to_string());
                 }
+            } else if parts.len() >= 2 || (is_mature && !value.is_empty()) {
                 // Get or insert machine
                 let machine = machines
                     .entry(machine_name.to_owned())
                     .or_insert_with(|| Machine::new(machine_name.to_owned()));
 
-                machine.category = Some(category);
-                machine.subcategory = Some(subcategory);
-                machine.is_mature = Some(is_mature);
+                machine.category = Some(parts[0].to_string());
+                machine.subcategory = parts.get(1).map(|subcategory| subcategory.to_string());
+                // Keeps the flag of a machine listed in the mature section
+                machine.is_mature = Some(is_mature || machine.is_mature == Some(true));
             }
             // Increase processed count
             processed_count += 1;
+            // Stop if the read was cancelled
+            check_cancelled_at(processed_count)?;
             // Progress callback
             if processed_count % batch == 0 {
-                progress_callback(ProgressInfo {
-                    progress: processed_count as u64,
-                    total: total_elements as u64,
-                    message: String::from(""),
-                    callback_type: CallbackType::Progress,
-                });
+                progress_callback(ProgressInfo::progress(
+                    processed_count as u64,
+                    total_elements as u64,
+                ));
             }
         }
     }
 
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: total_elements as u64,
-        message: format!("{} loaded successfully", data_file_name),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} loaded successfully", data_file_name))
+            .with_progress(processed_count as u64, total_elements as u64),
+    );
 
     Ok(machines)
 }
@@ -171,14 +301,27 @@ pub fn read_catver_file(
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
 ///
-fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
+pub(crate) fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let reader =
+        open_text_file(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+
+    count_entries(reader)
+}
+
+/// Counts the entries of the content of a catver.ini file, like `count_total_elements` does for a file.
+///
+/// # Parameters
+/// - `reader`: A buffered reader positioned at the start of the content.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>` with the total number of entries,
+/// or an error if the content cannot be read.
+///
+fn count_entries(reader: impl BufRead) -> Result<usize, Box<dyn Error + Send + Sync>> {
     let mut count = 0;
 
     for line in reader.lines() {
-        let line = line.with_context(|| format!("Failed to read a line in file: {}", file_path))?;
+        let line = line.context("Failed to read a line")?;
         if line.trim().contains('=') {
             count += 1;
         }
@@ -186,3 +329,220 @@ fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send +
 
     Ok(count)
 }
+
+/// Removes the `* Mature *` marker ending a catver value.
+///
+/// The marker is matched case-insensitively, with or without the spaces around `Mature`.
+///
+/// # Parameters
+/// - `value`: The value of a catver entry, e.g. `Shooter / Flying Vertical * Mature *`.
+///
+/// # Returns
+/// Returns the value without the marker, trimmed, and `true` if the marker was present.
+fn strip_mature_marker(value: &str) -> (&str, bool) {
+    let lowercase_value = value.to_ascii_lowercase();
+
+    for marker in ["* mature *", "*mature*"] {
+        if lowercase_value.ends_with(marker) {
+            return (value[..value.len() - marker.len()].trim(), true);
+        }
+    }
+
+    (value, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_catver_file_with_version_added() {
+        let folder = std::env::temp_dir().join("mame_parser_catver_ver_added");
+        std::fs::create_dir_all(&folder).unwrap();
+        let file_path = folder.join("catver.ini");
+        std::fs::write(
+            &file_path,
+            "[FOLDER_SETTINGS]\nRootFolderIcon=mame\n\n[ROOT_FOLDER]\n\n[Category]\n\
+             pacman=Maze / Collect\ngalaga=Shooter / Flying Vertical\n\n\
+             [VerAdded]\npacman=0.30\ngalaga=0.37b5\nnewgame=0.268\n",
+        )
+        .unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let machines = read_catver_file(file_path.to_str().unwrap(), progress_callback).unwrap();
+
+        let pacman = machines.get("pacman").unwrap();
+        assert_eq!(pacman.category.as_deref(), Some("Maze"));
+        assert_eq!(pacman.subcategory.as_deref(), Some("Collect"));
+        assert_eq!(pacman.version_added.as_deref(), Some("0.30"));
+        assert_eq!(
+            machines.get("galaga").unwrap().version_added.as_deref(),
+            Some("0.37b5")
+        );
+        let new_game = machines.get("newgame").unwrap();
+        assert_eq!(new_game.version_added.as_deref(), Some("0.268"));
+        assert_eq!(new_game.category, None);
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn test_read_catver_file_with_mature_flags() {
+        let folder = std::env::temp_dir().join("mame_parser_catver_mature");
+        std::fs::create_dir_all(&folder).unwrap();
+        let file_path = folder.join("catver.ini");
+        std::fs::write(
+            &file_path,
+            "[Category]
+             pacman=Maze / Collect
+             strip=Puzzle / Cards * Mature *
+             casino=Casino *Mature*
+             poker=Casino / Cards
+
+             [Mature]
+             poker
+",
+        )
+        .unwrap();
+
+        let machines = read_catver_file(file_path.to_str().unwrap(), Box::new(|_| {})).unwrap();
+
+        assert_eq!(machines["pacman"].is_mature, Some(false));
+        let strip = &machines["strip"];
+        assert_eq!(strip.is_mature, Some(true));
+        assert_eq!(strip.subcategory.as_deref(), Some("Cards"));
+        let casino = &machines["casino"];
+        assert_eq!(casino.is_mature, Some(true));
+        assert_eq!(casino.category.as_deref(), Some("Casino"));
+        assert_eq!(casino.subcategory, None);
+        let poker = &machines["poker"];
+        assert_eq!(poker.is_mature, Some(true));
+        assert_eq!(poker.category.as_deref(), Some("Casino"));
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn test_read_catver_file_with_small_buffer() {
+        let folder = std::env::temp_dir().join("mame_parser_catver_small_buffer");
+        std::fs::create_dir_all(&folder).unwrap();
+        let file_path = folder.join("catver.ini");
+        std::fs::write(
+            &file_path,
+            "[Category]\npacman=Maze / Collect\ngalaga=Shooter / Flying Vertical\n",
+        )
+        .unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let machines =
+            read_catver_file_with_buffer_size(file_path.to_str().unwrap(), 4, progress_callback)
+                .unwrap();
+
+        assert_eq!(machines.len(), 2);
+        assert_eq!(
+            machines.get("galaga").unwrap().subcategory.as_deref(),
+            Some("Flying Vertical")
+        );
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn test_read_catver_from_reader() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let contents =
+            "\u{feff}[Category]\npacman=Maze / Collect\ngalaga=Shooter / Flying Vertical\n";
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let machines = read_catver_from_str(contents, progress_callback).unwrap();
+        assert_eq!(machines.len(), 2);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(contents.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let machines = read_catver_from_reader(compressed.as_slice(), progress_callback).unwrap();
+        assert_eq!(machines.len(), 2);
+        assert_eq!(
+            machines.get("pacman").unwrap().category.as_deref(),
+            Some("Maze")
+        );
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        assert!(read_catver_from_str("", progress_callback).is_err());
+    }
+
+    #[test]
+    fn test_read_catver_file_gzipped() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let folder = std::env::temp_dir().join("mame_parser_catver_gzipped");
+        std::fs::create_dir_all(&folder).unwrap();
+        let file_path = folder.join("catver.ini.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(b"[Category]\npacman=Maze / Collect\n")
+            .unwrap();
+        std::fs::write(&file_path, encoder.finish().unwrap()).unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let machines = read_catver_file(file_path.to_str().unwrap(), progress_callback).unwrap();
+
+        let pacman = machines.get("pacman").unwrap();
+        assert_eq!(pacman.category.as_deref(), Some("Maze"));
+        assert_eq!(pacman.subcategory.as_deref(), Some("Collect"));
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn test_read_catver_file_with_bom() {
+        let folder = std::env::temp_dir().join("mame_parser_catver_bom");
+        std::fs::create_dir_all(&folder).unwrap();
+        let content = "[Category]\r\npacman=Maze / Collect\r\n";
+
+        let utf8_path = folder.join("catver_utf8.ini");
+        std::fs::write(&utf8_path, format!("\u{FEFF}{}", content)).unwrap();
+
+        let utf16_path = folder.join("catver_utf16.ini");
+        let utf16: Vec<u8> = "\u{FEFF}"
+            .encode_utf16()
+            .chain(content.encode_utf16())
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        std::fs::write(&utf16_path, utf16).unwrap();
+
+        for file_path in [utf8_path, utf16_path] {
+            let progress_callback: ProgressCallback = Box::new(|_| {});
+            let machines =
+                read_catver_file(file_path.to_str().unwrap(), progress_callback).unwrap();
+
+            let pacman = machines.get("pacman").unwrap();
+            assert_eq!(pacman.category.as_deref(), Some("Maze"));
+            assert_eq!(pacman.subcategory.as_deref(), Some("Collect"));
+        }
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn test_read_catver_file_empty() {
+        let folder = std::env::temp_dir().join("mame_parser_catver_empty");
+        std::fs::create_dir_all(&folder).unwrap();
+        let file_path = folder.join("catver.ini");
+        std::fs::write(&file_path, "").unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let result = read_catver_file(file_path.to_str().unwrap(), progress_callback);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("is empty or contains no entries"));
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+}
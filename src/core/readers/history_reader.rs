@@ -1,9 +1,13 @@
+use crate::core::models::cancellation::check_cancelled_at;
 use crate::{
     core::models::{
-        callback_progress::{CallbackType, ProgressCallback, ProgressInfo},
+        callback_progress::{ProgressCallback, ProgressInfo},
         core_models::{HistorySection, Machine},
     },
-    helpers::callback_progress_helper::get_progress_info,
+    helpers::{
+        callback_progress_helper::get_progress_info,
+        file_system_helpers::{read_text, strip_bom, DEFAULT_BUFFER_SIZE},
+    },
 };
 use anyhow::{Context, Result};
 use quick_xml::events::Event;
@@ -11,7 +15,7 @@ use quick_xml::Reader;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs::{self, File};
-use std::io::BufReader;
+use std::io::{BufRead, BufReader, Read};
 
 /// Reads and processes a history XML file to extract machine data and history sections.
 ///
@@ -36,6 +40,7 @@ use std::io::BufReader;
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
 /// - The XML content is malformed or cannot be parsed correctly.
+/// - The file is empty or contains no entries.
 ///
 /// # File structure
 /// The XML file follows this general structure:
@@ -76,37 +81,136 @@ pub fn read_history_file(
     file_path: &str,
     progress_callback: ProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
-    let mut machines: HashMap<String, Machine> = HashMap::new();
+    read_history_file_with_buffer_size(file_path, DEFAULT_BUFFER_SIZE, progress_callback)
+}
 
+/// Reads a history file using read buffers of the given size.
+///
+/// This function behaves like `read_history_file`, with `buffer_size` setting the capacity of the buffered file reader and of the XML event buffer.
+/// Larger buffers can improve the throughput of large files on slow disks.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_history_file`.
+///
+pub(crate) fn read_history_file_with_buffer_size(
+    file_path: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
     let data_file_name = file_path.split('/').last().unwrap();
 
+    let file_content = fs::read_to_string(file_path)
+        .with_context(|| format!("Failed to read file content: {}", file_path))?;
+    let file =
+        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+
+    read_history(
+        &file_content,
+        BufReader::with_capacity(buffer_size, file),
+        data_file_name,
+        buffer_size,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a history XML file held in memory.
+///
+/// This function behaves like `read_history_file`, reading the entries from `contents` instead of a file.
+///
+/// # Parameters
+/// - `contents`: A `&str` with the content of the history XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_history_file`.
+///
+pub fn read_history_from_str(
+    contents: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = strip_bom(contents);
+    read_history(
+        contents,
+        contents.as_bytes(),
+        "history data",
+        DEFAULT_BUFFER_SIZE,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a history XML file from any reader.
+///
+/// As the entries are counted before being processed, the whole content of `reader` is read into memory first,
+/// and is then processed like `read_history_from_str` does. The content may be gzipped and may start with a byte order mark.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content of the history XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_history_file`.
+///
+pub fn read_history_from_reader(
+    reader: impl Read,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = read_text(reader)?;
+    read_history_from_str(&contents, progress_callback)
+}
+
+/// Reads and processes the entries of a history XML file.
+///
+/// # Parameters
+/// - `file_content`: The whole content of the file, used to count the entries before processing them.
+/// - `reader`: A buffered reader positioned at the start of the same content, from which the entries are processed.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `buffer_size`: The capacity of the XML event buffer, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_history_file`.
+///
+fn read_history<R: BufRead>(
+    file_content: &str,
+    reader: R,
+    data_file_name: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut machines: HashMap<String, Machine> = HashMap::new();
+
     // Get total elements
     progress_callback(get_progress_info(
         format!("Getting total entries for {}", data_file_name).as_str(),
     ));
 
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
-
-    // Read the file content
-    let file_content = fs::read_to_string(file_path)
-        .with_context(|| format!("Failed to read file content: {}", file_path))?;
-
-    let total_elements = match count_total_elements(&file_content) {
+    let total_elements = match count_total_elements(file_content) {
         Ok(total_elements) => total_elements,
         Err(err) => {
-            progress_callback(ProgressInfo {
-                progress: 0,
-                total: 0,
-                message: format!("Couldn't get total entries for {}", data_file_name),
-                callback_type: CallbackType::Error,
-            });
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't get total entries for {}",
+                data_file_name
+            )));
 
             return Err(err.into());
         }
     };
 
+    if total_elements == 0 {
+        let message = format!(
+            "Data file {} is empty or contains no entries",
+            data_file_name
+        );
+        progress_callback(ProgressInfo::error(message.clone()));
+
+        return Err(message.into());
+    }
+
     progress_callback(get_progress_info(
         format!("Reading {}", data_file_name).as_str(),
     ));
@@ -114,12 +218,12 @@ pub fn read_history_file(
     let mut xml_reader = Reader::from_reader(reader);
     xml_reader.trim_text(true);
 
-    let mut buf = Vec::with_capacity(8 * 1024);
+    let mut buf = Vec::with_capacity(buffer_size);
 
     let mut current_entry: Option<HistoryEntry> = None;
 
     let mut processed_count = 0;
-    let batch = total_elements / 10;
+    let batch = (total_elements / 10).max(1);
 
     loop {
         match xml_reader.read_event(&mut buf) {
@@ -157,14 +261,14 @@ pub fn read_history_file(
 
                         // Increase processed count
                         processed_count += 1;
+                        // Stop if the read was cancelled
+                        check_cancelled_at(processed_count)?;
                         // Progress callback
                         if processed_count % batch == 0 {
-                            progress_callback(ProgressInfo {
-                                progress: processed_count as u64,
-                                total: total_elements as u64,
-                                message: String::from(""),
-                                callback_type: CallbackType::Progress,
-                            });
+                            progress_callback(ProgressInfo::progress(
+                                processed_count as u64,
+                                total_elements as u64,
+                            ));
                         }
                         // Reset current entry
                         current_entry = None;
@@ -179,12 +283,10 @@ pub fn read_history_file(
         buf.clear();
     }
 
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: total_elements as u64,
-        message: format!("{} loaded successfully", data_file_name),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} loaded successfully", data_file_name))
+            .with_progress(processed_count as u64, total_elements as u64),
+    );
 
     Ok(machines)
 }
@@ -210,9 +312,9 @@ pub fn read_history_file(
 /// This function can return an error if:
 /// - An attribute of a node cannot be decoded correctly.
 /// - Reading the text content of a `text` node fails.
-fn process_node(
+fn process_node<R: BufRead>(
     e: &quick_xml::events::BytesStart,
-    reader: &mut Reader<BufReader<File>>,
+    reader: &mut Reader<R>,
 ) -> Result<Option<HistoryEntry>, Box<dyn std::error::Error + Send + Sync>> {
     let mut current_entry: Option<HistoryEntry> = None;
 
@@ -361,6 +463,24 @@ fn get_section_order(section: &str) -> usize {
     }
 }
 
+/// Counts the total number of `<entry>` elements in the provided file.
+///
+/// This function reads the whole file and counts its entries with `count_total_elements`,
+/// without processing them.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and analyzed.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the total number of `<entry>` elements found in the file.
+/// - On failure: Contains an error if the file cannot be read or its content cannot be parsed.
+///
+pub(crate) fn count_file_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let file_content = fs::read_to_string(file_path)?;
+    count_total_elements(&file_content)
+}
+
 /// Counts the total number of elements in a string based on the presence of specific XML tags (`<entry>`).
 ///
 /// This function reads the content of a string representing an XML document line by line
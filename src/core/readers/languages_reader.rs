@@ -1,13 +1,18 @@
+use crate::core::models::cancellation::check_cancelled_at;
 use crate::{
     core::models::{
-        callback_progress::{CallbackType, ProgressCallback, ProgressInfo},
+        callback_progress::{ProgressCallback, ProgressInfo},
         core_models::Machine,
     },
-    helpers::callback_progress_helper::get_progress_info,
+    helpers::{
+        callback_progress_helper::get_progress_info,
+        file_system_helpers::{
+            open_text_file, open_text_file_with_capacity, read_text, strip_bom, DEFAULT_BUFFER_SIZE,
+        },
+    },
 };
 use anyhow::Context;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, Read};
 use std::{collections::HashMap, error::Error};
 
 /// Reads and processes a "languages" file to extract machine language information.
@@ -15,6 +20,7 @@ use std::{collections::HashMap, error::Error};
 /// This function reads a specified "languages" file line by line, extracts machine names
 /// and their associated languages, and populates a `HashMap` with machine names as keys
 /// and their corresponding `Machine` structs as values. It tracks progress through a callback function.
+/// The file may be gzipped (e.g. `languages.ini.gz`), in which case it is decompressed while being read.
 ///
 /// # Parameters
 /// - `file_path`: A `&str` representing the path to the "languages" file to be read and processed.
@@ -32,6 +38,7 @@ use std::{collections::HashMap, error::Error};
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
 /// - The total number of elements in the file cannot be determined.
+/// - The file is empty or contains no entries.
 ///
 /// # File structure
 /// The `languages.ini` file format represents configurations and data related to different languages in the system.
@@ -52,43 +59,140 @@ pub fn read_languages_file(
     file_path: &str,
     progress_callback: ProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
-    let mut machines: HashMap<String, Machine> = HashMap::new();
+    read_languages_file_with_buffer_size(file_path, DEFAULT_BUFFER_SIZE, progress_callback)
+}
+
+/// Reads a languages file using read buffers of the given size.
+///
+/// This function behaves like `read_languages_file`, with `buffer_size` setting the capacity of the buffered file reader.
+/// Larger buffers can improve the throughput of large files on slow disks.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_languages_file`.
+///
+pub(crate) fn read_languages_file_with_buffer_size(
+    file_path: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
     let data_file_name = file_path.split('/').last().unwrap();
 
+    read_languages(
+        || {
+            Ok(open_text_file_with_capacity(file_path, buffer_size)
+                .with_context(|| format!("Failed to open file: {}", file_path))?)
+        },
+        data_file_name,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a languages.ini file held in memory.
+///
+/// This function behaves like `read_languages_file`, reading the entries from `contents` instead of a file.
+///
+/// # Parameters
+/// - `contents`: A `&str` with the content of the languages.ini file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_languages_file`.
+///
+pub fn read_languages_from_str(
+    contents: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = strip_bom(contents);
+    read_languages(
+        || Ok(contents.as_bytes()),
+        "languages.ini data",
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a languages.ini file from any reader.
+///
+/// As the entries are counted before being processed, the whole content of `reader` is read into memory first,
+/// and is then processed like `read_languages_from_str` does. The content may be gzipped and may start with a byte order mark.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content of the languages.ini file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_languages_file`.
+///
+pub fn read_languages_from_reader(
+    reader: impl Read,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = read_text(reader)?;
+    read_languages_from_str(&contents, progress_callback)
+}
+
+/// Reads and processes the entries of a languages.ini file from a source that can be opened more than once.
+///
+/// # Parameters
+/// - `open`: A function returning a new buffered reader positioned at the start of the content.
+///   It is called twice: once to count the entries and once to process them.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_languages_file`.
+///
+fn read_languages<R: BufRead>(
+    open: impl Fn() -> Result<R, Box<dyn Error + Send + Sync>>,
+    data_file_name: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut machines: HashMap<String, Machine> = HashMap::new();
+
     // Get total elements
     progress_callback(get_progress_info(
         format!("Getting total entries for {}", data_file_name).as_str(),
     ));
 
-    let total_elements = match count_total_elements(file_path) {
+    let total_elements = match open().and_then(count_entries) {
         Ok(total_elements) => total_elements,
         Err(err) => {
-            progress_callback(ProgressInfo {
-                progress: 0,
-                total: 0,
-                message: format!("Couldn't get total entries for {}", data_file_name),
-                callback_type: CallbackType::Error,
-            });
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't get total entries for {}",
+                data_file_name
+            )));
 
             return Err(err.into());
         }
     };
 
+    if total_elements == 0 {
+        let message = format!(
+            "Data file {} is empty or contains no entries",
+            data_file_name
+        );
+        progress_callback(ProgressInfo::error(message.clone()));
+
+        return Err(message.into());
+    }
+
     progress_callback(get_progress_info(
         format!("Reading {}", data_file_name).as_str(),
     ));
 
     // Open the file and create a buffered reader
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
+    let reader = open()?;
     let mut current_language: Option<String> = None;
 
     // Define lines to ignore
     let to_ignore = vec![";", "", " ", "", "[FOLDER_SETTINGS]", "[ROOT_FOLDER]"];
 
     let mut processed_count = 0;
-    let batch = total_elements / 10;
+    let batch = (total_elements / 10).max(1);
 
     // Process each line of the file
     for line in reader.lines() {
@@ -114,26 +218,24 @@ pub fn read_languages_file(
 
                     // Increase processed count
                     processed_count += 1;
+                    // Stop if the read was cancelled
+                    check_cancelled_at(processed_count)?;
                     // Progress callback
                     if processed_count % batch == 0 {
-                        progress_callback(ProgressInfo {
-                            progress: processed_count as u64,
-                            total: total_elements as u64,
-                            message: String::from(""),
-                            callback_type: CallbackType::Progress,
-                        });
+                        progress_callback(ProgressInfo::progress(
+                            processed_count as u64,
+                            total_elements as u64,
+                        ));
                     }
                 }
             }
         }
     }
 
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: total_elements as u64,
-        message: format!("{} loaded successfully", data_file_name),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} loaded successfully", data_file_name))
+            .with_progress(processed_count as u64, total_elements as u64),
+    );
 
     Ok(machines)
 }
@@ -158,7 +260,23 @@ pub fn read_languages_file(
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
 ///
-fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+pub(crate) fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let reader =
+        open_text_file(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+
+    count_entries(reader)
+}
+
+/// Counts the entries of the content of a languages.ini file, like `count_total_elements` does for a file.
+///
+/// # Parameters
+/// - `reader`: A buffered reader positioned at the start of the content.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>` with the total number of entries,
+/// or an error if the content cannot be read.
+///
+fn count_entries(reader: impl BufRead) -> Result<usize, Box<dyn Error + Send + Sync>> {
     let to_ignore = vec![
         ";",
         "",
@@ -171,10 +289,6 @@ fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send +
         "SubFolderIcon folder",
     ];
 
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
-
     let count = reader
         .lines()
         .filter_map(|line| line.ok())
@@ -1,18 +1,28 @@
+use crate::core::models::cancellation::check_cancelled_at;
 use crate::{
     core::{
-        data_cleanup::name_normalization,
+        data_cleanup::{
+            machine_filtering::{filter_applies, MachineFilter},
+            name_normalization,
+        },
         models::{
-            callback_progress::{CallbackType, ProgressCallback, ProgressInfo},
-            core_models::{BiosSet, DeviceRef, Disk, ExtendedData, Machine, Rom, Sample, Software},
+            callback_progress::{ProgressCallback, ProgressInfo},
+            core_models::{
+                BiosSet, DeviceRef, Disk, ExtendedData, Machine, Rom, Sample, Slot, SlotOption,
+                Software,
+            },
         },
     },
-    helpers::callback_progress_helper::get_progress_info,
+    helpers::{
+        callback_progress_helper::get_progress_info,
+        file_system_helpers::{read_text, strip_bom, DEFAULT_BUFFER_SIZE},
+    },
 };
 use anyhow::Context;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use std::fs::{self, File};
-use std::io::BufReader;
+use std::io::{BufRead, BufReader, Read};
 use std::{collections::HashMap, error::Error};
 
 /// Reads a MAME file and processes the machine entries contained within.
@@ -32,6 +42,7 @@ use std::{collections::HashMap, error::Error};
 /// # Errors
 /// - Returns an error if the file cannot be opened or read.
 /// - Returns an error if there is an issue processing the XML content.
+/// - Returns an error if the file is empty or contains no entries.
 ///
 /// # File structure
 /// The `mame.dat` file format represents data about arcade machines and their components.
@@ -70,6 +81,9 @@ use std::{collections::HashMap, error::Error};
 ///     - `status`: Status attribute (optional, attribute).
 ///     - `crc`: CRC value (optional, attribute).
 ///     - `sha1`: SHA1 value (optional, attribute).
+///     - `region`: Memory region where the ROM is loaded (optional, attribute).
+///     - `offset`: Offset where the ROM is loaded in its region (optional, attribute).
+///     - `bios`: Name of the BIOS set the ROM belongs to (optional, attribute).
 ///
 /// # Device References
 /// - `device_refs`: List of device references related to the machine (optional, child nodes).
@@ -97,41 +111,335 @@ use std::{collections::HashMap, error::Error};
 ///     - `merge`: Merge attribute (optional, attribute).
 ///     - `status`: Status attribute (optional, attribute).
 ///     - `region`: Region attribute (optional, attribute).
+///
+/// # Slots
+/// - `slots`: List of the expansion slots of the machine (optional, child nodes).
+///   - Each `<slot>` element includes:
+///     - `name`: Name of the slot (attribute).
+///     - `options`: The `<slotoption>` child elements, each with its `name`, `devname`, and `default` attributes.
 pub fn read_mame_file(
     file_path: &str,
     progress_callback: ProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
-    let mut machines: HashMap<String, Machine> = HashMap::new();
+    read_mame_file_with_buffer_size(file_path, DEFAULT_BUFFER_SIZE, progress_callback)
+}
 
+/// Reads a MAME file using read buffers of the given size.
+///
+/// This function behaves like `read_mame_file`, with `buffer_size` setting the capacity of the buffered file reader and of the XML event buffer.
+/// Larger buffers can improve the throughput of large files on slow disks.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_mame_file`.
+///
+pub(crate) fn read_mame_file_with_buffer_size(
+    file_path: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
     let data_file_name = file_path.split('/').last().unwrap();
 
+    let file_content = fs::read_to_string(file_path)
+        .with_context(|| format!("Failed to read file content: {}", file_path))?;
+    let file =
+        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+
+    read_mame(
+        &file_content,
+        BufReader::with_capacity(buffer_size, file),
+        data_file_name,
+        buffer_size,
+        false,
+        progress_callback,
+    )
+}
+
+/// Reads a MAME file, keeping the machines read before the XML content turns out to be corrupted.
+///
+/// This function behaves like `read_mame_file_with_buffer_size`, but an XML parse error (e.g. a file truncated by
+/// a partial download) doesn't abort the reading: the machines completely read before the error are returned,
+/// and a `CallbackType::Error` update reports the partial results and the approximate position of the error.
+/// The machine being read when the error occurs is dropped.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_mame_file`.
+/// An error is still returned if the file cannot be read or if no machine could be read before the corruption.
+///
+pub(crate) fn read_mame_file_tolerant(
+    file_path: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let data_file_name = file_path.split('/').next_back().unwrap();
+
+    let mut file_bytes = fs::read(file_path)
+        .with_context(|| format!("Failed to read file content: {}", file_path))?;
+    // A truncation can split a multibyte character: the invalid UTF-8 is dropped with the rest of the
+    // corrupted content, which the XML parser then reports
+    if let Err(err) = std::str::from_utf8(&file_bytes) {
+        file_bytes.truncate(err.valid_up_to());
+    }
+    let file_content = String::from_utf8(file_bytes)?;
+
+    read_mame(
+        &file_content,
+        file_content.as_bytes(),
+        data_file_name,
+        buffer_size,
+        true,
+        progress_callback,
+    )
+}
+
+/// Reads a MAME file, keeping only the machines to which none of the given filters applies.
+///
+/// This function behaves like `read_mame_file` followed by `remove_machines_by_filter`, but the filters are
+/// applied to each machine as soon as it has been parsed, so the removed machines are never stored. Like
+/// `read_mame_file_streaming`, the file is streamed twice instead of being loaded into memory, so when most machines
+/// are removed (e.g. clones and devices), the peak memory used is bounded by the kept machines.
+///
+/// As only the MAME file is read, the filters are applied to its data only: the data merged from the other
+/// data files, such as the number of players used by `MachineFilter::Modified`, is not available yet.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `filters`: A slice of `MachineFilter`s; a machine is skipped if any of them applies to it.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_mame_file`, with only the kept machines.
+///
+pub fn read_mame_file_filtered(
+    file_path: &str,
+    filters: &[MachineFilter],
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let data_file_name = file_path.split('/').next_back().unwrap();
+
+    let open = || -> Result<BufReader<File>, Box<dyn Error + Send + Sync>> {
+        let file =
+            File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+        Ok(BufReader::with_capacity(DEFAULT_BUFFER_SIZE, file))
+    };
+
+    let mut machines: HashMap<String, Machine> = HashMap::new();
+    read_mame_entries(
+        open()?,
+        open()?,
+        data_file_name,
+        DEFAULT_BUFFER_SIZE,
+        false,
+        &mut |machine| {
+            if !filters
+                .iter()
+                .any(|filter| filter_applies(&machine, filter))
+            {
+                machines
+                    .entry(machine.name.clone())
+                    .or_insert_with(|| machine);
+            }
+            Ok(())
+        },
+        progress_callback,
+    )?;
+
+    Ok(machines)
+}
+
+/// Reads a MAME file, passing each machine to a callback as soon as it has been parsed.
+///
+/// This function behaves like `read_mame_file`, but the machines are never collected into a `HashMap`, and the file
+/// is streamed twice (once to count the entries, then to process them) instead of being loaded into memory.
+/// Combined with a sink such as the one returned by `write_sqlite_streaming`, the whole MAME file can be exported
+/// while only one machine is held in memory at a time.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `on_machine`: A callback called with each machine, in the order of the file. An error returned by the callback
+///   stops the reading and is returned.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the number of machines passed to `on_machine`.
+/// - On failure: Contains an error if the file cannot be read or processed, or if `on_machine` fails.
+///
+pub fn read_mame_file_streaming(
+    file_path: &str,
+    mut on_machine: impl FnMut(Machine) -> Result<(), Box<dyn Error + Send + Sync>>,
+    progress_callback: ProgressCallback,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let data_file_name = file_path.split('/').next_back().unwrap();
+
+    let open = || -> Result<BufReader<File>, Box<dyn Error + Send + Sync>> {
+        let file =
+            File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+        Ok(BufReader::with_capacity(DEFAULT_BUFFER_SIZE, file))
+    };
+
+    read_mame_entries(
+        open()?,
+        open()?,
+        data_file_name,
+        DEFAULT_BUFFER_SIZE,
+        false,
+        &mut on_machine,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a MAME XML file held in memory.
+///
+/// This function behaves like `read_mame_file`, reading the entries from `contents` instead of a file.
+///
+/// # Parameters
+/// - `contents`: A `&str` with the content of the MAME XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_mame_file`.
+///
+pub fn read_mame_from_str(
+    contents: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = strip_bom(contents);
+    read_mame(
+        contents,
+        contents.as_bytes(),
+        "MAME data",
+        DEFAULT_BUFFER_SIZE,
+        false,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a MAME XML file from any reader.
+///
+/// As the entries are counted before being processed, the whole content of `reader` is read into memory first,
+/// and is then processed like `read_mame_from_str` does. The content may be gzipped and may start with a byte order mark.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content of the MAME XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_mame_file`.
+///
+pub fn read_mame_from_reader(
+    reader: impl Read,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = read_text(reader)?;
+    read_mame_from_str(&contents, progress_callback)
+}
+
+/// Reads and processes the entries of a MAME XML file.
+///
+/// # Parameters
+/// - `file_content`: The whole content of the file, used to count the entries before processing them.
+/// - `reader`: A buffered reader positioned at the start of the same content, from which the entries are processed.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `buffer_size`: The capacity of the XML event buffer, in bytes.
+/// - `tolerate_truncation`: When `true`, an XML parse error ends the reading with the machines read so far
+///   instead of returning the error.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_mame_file`.
+///
+fn read_mame<R: BufRead>(
+    file_content: &str,
+    reader: R,
+    data_file_name: &str,
+    buffer_size: usize,
+    tolerate_truncation: bool,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut machines: HashMap<String, Machine> = HashMap::new();
+
+    read_mame_entries(
+        file_content.as_bytes(),
+        reader,
+        data_file_name,
+        buffer_size,
+        tolerate_truncation,
+        &mut |machine| {
+            machines
+                .entry(machine.name.clone())
+                .or_insert_with(|| machine);
+            Ok(())
+        },
+        progress_callback,
+    )?;
+
+    Ok(machines)
+}
+
+/// Reads the entries of a MAME XML file, passing each machine to a sink as soon as it has been parsed.
+///
+/// # Parameters
+/// - `counting_reader`: A buffered reader positioned at the start of the content, used to count the entries before processing them.
+/// - `reader`: A buffered reader positioned at the start of the same content, from which the entries are processed.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `buffer_size`: The capacity of the XML event buffer, in bytes.
+/// - `tolerate_truncation`: When `true`, an XML parse error ends the reading with the machines read so far
+///   instead of returning the error.
+/// - `sink`: A function called with each machine once it has been parsed. An error returned by the sink stops the reading.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the number of machines passed to the sink.
+/// - On failure: Contains an error if the content cannot be parsed or if the sink fails.
+///
+fn read_mame_entries<C: BufRead, R: BufRead>(
+    counting_reader: C,
+    reader: R,
+    data_file_name: &str,
+    buffer_size: usize,
+    tolerate_truncation: bool,
+    sink: &mut dyn FnMut(Machine) -> Result<(), Box<dyn Error + Send + Sync>>,
+    progress_callback: ProgressCallback,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
     // Get total elements
     progress_callback(get_progress_info(
         format!("Getting total entries for {}", data_file_name).as_str(),
     ));
 
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
-
-    // Read the file content
-    let file_content = fs::read_to_string(file_path)?;
-
     // Count the number of machines in the file
-    let total_elements = match count_total_elements(&file_content) {
+    let total_elements = match count_elements(counting_reader, tolerate_truncation) {
         Ok(total_elements) => total_elements,
         Err(err) => {
-            progress_callback(ProgressInfo {
-                progress: 0,
-                total: 0,
-                message: format!("Couldn't get total entries for {}", data_file_name),
-                callback_type: CallbackType::Error,
-            });
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't get total entries for {}",
+                data_file_name
+            )));
 
             return Err(err.into());
         }
     };
 
+    if total_elements == 0 {
+        let message = format!(
+            "Data file {} is empty or contains no entries",
+            data_file_name
+        );
+        progress_callback(ProgressInfo::error(message.clone()));
+
+        return Err(message.into());
+    }
+
     progress_callback(get_progress_info(
         format!("Reading {}", data_file_name).as_str(),
     ));
@@ -139,43 +447,108 @@ pub fn read_mame_file(
     let mut xml_reader = Reader::from_reader(reader);
     xml_reader.trim_text(true);
 
-    let mut buf = Vec::with_capacity(8 * 1024);
+    let mut buf = Vec::with_capacity(buffer_size);
 
     let mut current_machine: Option<Machine> = None;
 
     let mut processed_count = 0;
-    let batch = total_elements / 10;
+    let batch = (total_elements / 10).max(1);
 
     loop {
-        match xml_reader.read_event(&mut buf) {
-            Ok(Event::Start(ref e)) => {
-                process_node(e, &mut xml_reader, &mut current_machine)?;
-            }
-            Ok(Event::Empty(ref e)) => {
-                process_node(e, &mut xml_reader, &mut current_machine)?;
-            }
-            Ok(Event::End(ref e)) => match e.name() {
-                b"machine" => {
+        let processed = match xml_reader.read_event(&mut buf) {
+            Ok(Event::Start(ref e)) => process_node(e, &mut xml_reader, &mut current_machine),
+            Ok(Event::Empty(ref e)) => process_node(e, &mut xml_reader, &mut current_machine),
+            Ok(Event::End(ref e)) => {
+                if e.name() == b"machine" {
                     if let Some(machine) = current_machine.take() {
-                        machines
-                            .entry(machine.name.clone())
-                            .or_insert_with(|| machine);
+                        // Errors of the sink are never tolerated
+                        sink(machine)?;
                     }
 
                     // Increase processed count
                     processed_count += 1;
+                    // Stop if the read was cancelled
+                    check_cancelled_at(processed_count)?;
                     // Progress callback
                     if processed_count % batch == 0 {
-                        progress_callback(ProgressInfo {
-                            progress: processed_count as u64,
-                            total: total_elements as u64,
-                            message: String::from(""),
-                            callback_type: CallbackType::Progress,
-                        });
+                        progress_callback(ProgressInfo::progress(
+                            processed_count as u64,
+                            total_elements as u64,
+                        ));
                     }
                 }
-                _ => (),
-            },
+                Ok(())
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => Err(Box::new(e).into()),
+            _ => Ok(()),
+        };
+
+        if let Err(err) = processed {
+            if !tolerate_truncation || processed_count == 0 {
+                return Err(err);
+            }
+
+            progress_callback(ProgressInfo::error(format!(
+                "{} is corrupted near byte {} ({}), keeping the {} entries read before",
+                data_file_name,
+                xml_reader.buffer_position(),
+                err,
+                processed_count
+            )));
+
+            return Ok(processed_count);
+        }
+        buf.clear();
+    }
+
+    progress_callback(
+        ProgressInfo::finish(format!("{} loaded successfully", data_file_name))
+            .with_progress(processed_count as u64, total_elements as u64),
+    );
+
+    Ok(processed_count)
+}
+
+/// Reads only the names of the machines contained in a MAME file.
+///
+/// This function streams the MAME file and captures the `name` attribute of each `<machine>` element,
+/// skipping the rest of its content. It is much faster and lighter than `read_mame_file`, and is useful
+/// to check whether a machine is present or to build an index of the machine names.
+///
+/// # Parameters
+/// - `file_path`: The path to the MAME file to be read.
+///
+/// # Returns
+/// - `Result<Vec<String>, Box<dyn Error + Send + Sync>>`:
+///   - On success: The names of the machines, in the order they appear in the file.
+///   - On failure: An error if the file could not be read or processed.
+///
+/// # Errors
+/// - Returns an error if the file cannot be opened or read.
+/// - Returns an error if there is an issue processing the XML content.
+pub fn read_machine_names(file_path: &str) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+    let mut xml_reader = create_xml_reader(file_path, DEFAULT_BUFFER_SIZE)?;
+
+    let mut buf = Vec::with_capacity(DEFAULT_BUFFER_SIZE);
+    let mut skip_buf = Vec::with_capacity(DEFAULT_BUFFER_SIZE);
+    let mut machine_names = Vec::new();
+
+    loop {
+        match xml_reader.read_event(&mut buf) {
+            Ok(Event::Start(ref e)) if e.name() == b"machine" => {
+                if let Some(name) = e.attributes().flatten().find(|attr| attr.key == b"name") {
+                    machine_names.push(name.unescape_and_decode_value(&xml_reader)?);
+                }
+                // Skip the content of the machine
+                xml_reader.read_to_end(b"machine", &mut skip_buf)?;
+                skip_buf.clear();
+            }
+            Ok(Event::Empty(ref e)) if e.name() == b"machine" => {
+                if let Some(name) = e.attributes().flatten().find(|attr| attr.key == b"name") {
+                    machine_names.push(name.unescape_and_decode_value(&xml_reader)?);
+                }
+            }
             Ok(Event::Eof) => break,
             Err(e) => return Err(Box::new(e)),
             _ => (),
@@ -183,14 +556,29 @@ pub fn read_mame_file(
         buf.clear();
     }
 
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: total_elements as u64,
-        message: format!("{} loaded successfully", data_file_name),
-        callback_type: CallbackType::Finish,
-    });
+    Ok(machine_names)
+}
 
-    Ok(machines)
+/// Creates the XML reader used to stream a MAME file.
+///
+/// # Parameters
+/// - `file_path`: The path to the MAME file to be read.
+/// - `buffer_size`: The capacity of the buffered file reader, in bytes.
+///
+/// # Returns
+/// Returns a `Result<Reader<BufReader<File>>, Box<dyn Error + Send + Sync>>`:
+/// - On success: A `Reader` over the file, trimming the text of the elements.
+/// - On failure: An error if the file cannot be opened.
+fn create_xml_reader(
+    file_path: &str,
+    buffer_size: usize,
+) -> Result<Reader<BufReader<File>>, Box<dyn Error + Send + Sync>> {
+    let file =
+        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+    let mut xml_reader = Reader::from_reader(BufReader::with_capacity(buffer_size, file));
+    xml_reader.trim_text(true);
+
+    Ok(xml_reader)
 }
 
 /// Processes an XML node and updates the current machine with the parsed data.
@@ -209,9 +597,9 @@ pub fn read_mame_file(
 /// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
 /// - On success: Indicates the node was processed without errors.
 /// - On failure: Contains an error if there were issues reading the XML or updating the machine data.
-fn process_node(
+fn process_node<R: BufRead>(
     e: &quick_xml::events::BytesStart,
-    reader: &mut Reader<BufReader<File>>,
+    reader: &mut Reader<R>,
     current_machine: &mut Option<Machine>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     match e.name() {
@@ -241,10 +629,12 @@ fn process_node(
                 category: None,
                 subcategory: None,
                 is_mature: None,
+                version_added: None,
                 history_sections: vec![],
                 disks: vec![],
                 extended_data: None,
                 resources: vec![],
+                slots: vec![],
             };
             let attrs = e.attributes().map(|a| a.unwrap());
             for attr in attrs {
@@ -275,13 +665,11 @@ fn process_node(
                 }
             }
             // Set is_parent flag in Extended Data
-            if machine.extended_data.is_none() {
-                machine.extended_data = Some(ExtendedData::default());
-            }
-            machine.extended_data.as_mut().unwrap().is_parent = Some(true);
-            if machine.clone_of.is_some() || machine.rom_of.is_some() {
-                machine.extended_data.as_mut().unwrap().is_parent = Some(false);
-            }
+            let is_parent = machine.references_no_parent();
+            machine
+                .extended_data
+                .get_or_insert_with(ExtendedData::default)
+                .is_parent = Some(is_parent);
 
             *current_machine = Some(machine);
         }
@@ -297,14 +685,9 @@ fn process_node(
         b"year" => {
             if let Some(ref mut machine) = current_machine {
                 machine.year = Some(reader.read_text(b"year", &mut Vec::new())?);
-                // If year contains ? or is empty then set year in Extended Data as Unknown
-                if machine.year.as_ref().unwrap().contains('?')
-                    || machine.year.as_ref().unwrap().is_empty()
-                {
-                    machine.extended_data.as_mut().unwrap().year = Some("Unknown".to_string());
-                } else {
-                    machine.extended_data.as_mut().unwrap().year = machine.year.clone();
-                }
+                // Set normalized year in Extended Data, Unknown if it contains ? or is empty
+                machine.extended_data.as_mut().unwrap().year =
+                    Some(name_normalization::normalize_year(&machine.year));
             }
         }
         b"manufacturer" => {
@@ -345,6 +728,9 @@ fn process_node(
                 crc: None,
                 sha1: None,
                 status: None,
+                region: None,
+                offset: None,
+                bios: None,
             };
             let attrs = e.attributes().map(|a| a.unwrap());
             for attr in attrs {
@@ -357,6 +743,9 @@ fn process_node(
                     b"crc" => rom.crc = Some(attr.unescape_and_decode_value(reader)?),
                     b"sha1" => rom.sha1 = Some(attr.unescape_and_decode_value(reader)?),
                     b"status" => rom.status = Some(attr.unescape_and_decode_value(reader)?),
+                    b"region" => rom.region = Some(attr.unescape_and_decode_value(reader)?),
+                    b"offset" => rom.offset = Some(attr.unescape_and_decode_value(reader)?),
+                    b"bios" => rom.bios = Some(attr.unescape_and_decode_value(reader)?),
                     _ => {}
                 }
             }
@@ -380,9 +769,52 @@ fn process_node(
                 machine.device_refs.push(device_ref);
             }
         }
+        b"slot" => {
+            let mut slot = Slot {
+                name: String::new(),
+                options: vec![],
+            };
+
+            let attrs = e.attributes().map(|a| a.unwrap());
+            for attr in attrs {
+                if attr.key == b"name" {
+                    slot.name = attr.unescape_and_decode_value(reader)?;
+                }
+            }
+            if let Some(ref mut machine) = current_machine {
+                machine.slots.push(slot);
+            }
+        }
+        b"slotoption" => {
+            let mut slot_option = SlotOption {
+                name: String::new(),
+                devname: String::new(),
+                is_default: false,
+            };
+
+            let attrs = e.attributes().map(|a| a.unwrap());
+            for attr in attrs {
+                match attr.key {
+                    b"name" => slot_option.name = attr.unescape_and_decode_value(reader)?,
+                    b"devname" => slot_option.devname = attr.unescape_and_decode_value(reader)?,
+                    b"default" => {
+                        slot_option.is_default = attr.unescape_and_decode_value(reader)? == "yes"
+                    }
+                    _ => {}
+                }
+            }
+            // Slot options are nested in the last slot read
+            if let Some(slot) = current_machine
+                .as_mut()
+                .and_then(|machine| machine.slots.last_mut())
+            {
+                slot.options.push(slot_option);
+            }
+        }
         b"softwarelist" => {
             let mut software = Software {
                 name: String::new(),
+                titles: vec![],
             };
 
             let attrs = e.attributes().map(|a| a.unwrap());
@@ -454,6 +886,24 @@ fn process_node(
     Ok(())
 }
 
+/// Counts the total number of `<machine>` elements in the provided file.
+///
+/// This function reads the whole file and counts its entries with `count_total_elements`,
+/// without processing them.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and analyzed.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the total number of `<machine>` elements found in the file.
+/// - On failure: Contains an error if the file cannot be read or its content cannot be parsed.
+///
+pub(crate) fn count_file_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let file_content = fs::read_to_string(file_path)?;
+    count_total_elements(&file_content)
+}
+
 /// Counts the total number of `<machine>` elements in the provided XML content.
 ///
 /// This function parses the given XML content line by line and counts how many `<machine>` elements
@@ -471,7 +921,22 @@ fn process_node(
 /// This function will return an error if:
 /// - There are I/O errors or issues while reading and parsing the XML content.
 fn count_total_elements(file_content: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
-    let mut reader = Reader::from_str(file_content);
+    count_elements(file_content.as_bytes(), false)
+}
+
+/// Counts the `<machine>` elements like `count_total_elements`, optionally stopping at the first XML parse error.
+///
+/// # Parameters
+/// - `content`: A buffered reader positioned at the start of the XML content.
+/// - `stop_at_error`: When `true`, an XML parse error ends the count instead of being returned.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>` like `count_total_elements`.
+fn count_elements<R: BufRead>(
+    content: R,
+    stop_at_error: bool,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let mut reader = Reader::from_reader(content);
     reader.trim_text(true);
     let mut buf = Vec::with_capacity(8 * 1024);
     let mut count = 0;
@@ -482,6 +947,7 @@ fn count_total_elements(file_content: &str) -> Result<usize, Box<dyn Error + Sen
                 count += 1;
             }
             Ok(Event::Eof) => break,
+            Err(_) if stop_at_error => break,
             Err(e) => {
                 // Return the error instead of printing it
                 return Err(Box::new(e));
@@ -493,3 +959,286 @@ fn count_total_elements(file_content: &str) -> Result<usize, Box<dyn Error + Sen
 
     Ok(count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::callback_progress::CallbackType;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_read_mame_file_rom_region_and_offset() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_rom_region");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("MAME 0.1.dat");
+        fs::write(
+            &file_path,
+            r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="pacman" sourcefile="pacman/pacman.cpp">
+        <description>Pac-Man (Midway)</description>
+        <year>1980</year>
+        <manufacturer>Namco (Midway license)</manufacturer>
+        <rom name="pacman.6e" size="4096" crc="c1e6ab10" sha1="e87e059c5be45753f7e9f33dff851f16d6751181" region="maincpu" offset="0"/>
+        <rom name="pacman.5e" size="4096" crc="0c944964" sha1="06ef227747a440831c9a3a613b76693d52a2f0a9"/>
+    </machine>
+</datafile>"#,
+        )
+        .unwrap();
+
+        let machines = read_mame_file(file_path.to_str().unwrap(), Box::new(|_| {})).unwrap();
+
+        let roms = &machines["pacman"].roms;
+        assert_eq!(roms[0].region.as_deref(), Some("maincpu"));
+        assert_eq!(roms[0].offset.as_deref(), Some("0"));
+        assert_eq!(roms[1].region, None);
+        assert_eq!(roms[1].offset, None);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_mame_from_str_with_bom() {
+        let contents = "\u{feff}<?xml version=\"1.0\"?>\n<mame>\n<machine name=\"pacman\"><description>Pac-Man</description></machine>\n</mame>\n";
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let machines = read_mame_from_str(contents, progress_callback).unwrap();
+
+        assert_eq!(
+            machines.get("pacman").unwrap().description.as_deref(),
+            Some("Pac-Man")
+        );
+    }
+
+    #[test]
+    fn test_read_mame_file_tolerant() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_tolerant");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("MAME 0.1.dat");
+        fs::write(
+            &file_path,
+            r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="pacman">
+        <description>Pac-Man (Midway)</description>
+    </machine>
+    <machine name="galaga">
+        <description>Galaga</descr"#,
+        )
+        .unwrap();
+        let file_path = file_path.to_str().unwrap();
+
+        assert!(read_mame_file(file_path, Box::new(|_| {})).is_err());
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let callback_errors = Arc::clone(&errors);
+        let machines = read_mame_file_tolerant(
+            file_path,
+            DEFAULT_BUFFER_SIZE,
+            Box::new(move |progress_info| {
+                if let CallbackType::Error = progress_info.callback_type {
+                    callback_errors.lock().unwrap().push(progress_info.message);
+                }
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(machines.len(), 1);
+        assert!(machines.contains_key("pacman"));
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("corrupted near byte"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_mame_file_tolerant_truncated_character() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_tolerant_character");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("MAME 0.1.dat");
+        // Truncated after the first byte of the two-byte "é"
+        let mut content = br#"<?xml version="1.0"?>
+<datafile>
+    <machine name="pacman">
+        <description>Pac-Man (Midway)</description>
+    </machine>
+    <machine name="pokemonp">
+        <description>Pok"#
+            .to_vec();
+        content.push(0xc3);
+        fs::write(&file_path, content).unwrap();
+
+        let machines = read_mame_file_tolerant(
+            file_path.to_str().unwrap(),
+            DEFAULT_BUFFER_SIZE,
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        assert_eq!(machines.len(), 1);
+        assert!(machines.contains_key("pacman"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_mame_file_rom_bios() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_rom_bios");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("MAME 0.1.dat");
+        fs::write(
+            &file_path,
+            r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="neogeo" sourcefile="neogeo/neogeo.cpp" isbios="yes">
+        <description>Neo-Geo MV-6F</description>
+        <year>1990</year>
+        <manufacturer>SNK</manufacturer>
+        <biosset name="euro" description="Europe MVS (Ver. 2)" default="yes"/>
+        <biosset name="japan" description="Japan MVS (Ver. 3)"/>
+        <rom name="sp-s2.sp1" bios="euro" size="131072" crc="9036d879" sha1="4f5ed7105b7128794654ce82b51723e16e389543" region="mainbios" offset="0"/>
+        <rom name="vs-bios.rom" bios="japan" size="131072" crc="f0e8f27d" sha1="ecf01eda815909f1facec62abf3594eaa8d11075" region="mainbios" offset="0"/>
+        <rom name="sfix.sfix" size="131072" crc="c2ea0cfd" sha1="fd4a618cdcdbf849374f0a50dd8efe9dbab706c3" region="fixedbios" offset="0"/>
+    </machine>
+</datafile>"#,
+        )
+        .unwrap();
+
+        let machines = read_mame_file(file_path.to_str().unwrap(), Box::new(|_| {})).unwrap();
+
+        let roms = &machines["neogeo"].roms;
+        assert_eq!(roms[0].bios.as_deref(), Some("euro"));
+        assert_eq!(roms[1].bios.as_deref(), Some("japan"));
+        assert_eq!(roms[2].bios, None);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_mame_from_str() {
+        let contents = r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="pacman" sourcefile="pacman.cpp">
+        <description>Pac-Man (Midway)</description>
+        <year>1980</year>
+        <manufacturer>Namco (Midway license)</manufacturer>
+    </machine>
+    <machine name="puckman" sourcefile="pacman.cpp" cloneof="pacman">
+        <description>Puck Man (Japan set 1)</description>
+        <year>1980</year>
+        <manufacturer>Namco</manufacturer>
+    </machine>
+</datafile>"#;
+
+        let machines = read_mame_from_str(contents, Box::new(|_| {})).unwrap();
+        assert_eq!(machines.len(), 2);
+        assert_eq!(machines["puckman"].clone_of.as_deref(), Some("pacman"));
+
+        let from_reader = read_mame_from_reader(contents.as_bytes(), Box::new(|_| {})).unwrap();
+        assert_eq!(from_reader.len(), 2);
+        assert_eq!(
+            from_reader["pacman"].description.as_deref(),
+            Some("Pac-Man (Midway)")
+        );
+    }
+
+    #[test]
+    fn test_read_machine_names() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_machine_names");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("MAME 0.1.dat");
+        fs::write(
+            &file_path,
+            r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="pacman" sourcefile="pacman/pacman.cpp">
+        <description>Pac-Man (Midway)</description>
+        <rom name="pacman.6e" size="4096" crc="c1e6ab10"/>
+    </machine>
+    <machine name="z80" isdevice="yes"/>
+    <machine name="galaga">
+        <description>Galaga</description>
+    </machine>
+</datafile>"#,
+        )
+        .unwrap();
+
+        let names = read_machine_names(file_path.to_str().unwrap()).unwrap();
+        assert_eq!(names, ["pacman", "z80", "galaga"]);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_mame_file_empty() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_mame_empty");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("MAME 0.1.dat");
+        fs::write(&file_path, "").unwrap();
+
+        let result = read_mame_file(file_path.to_str().unwrap(), Box::new(|_| {}));
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("is empty or contains no entries"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_mame_file_filtered() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_mame_filtered");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("MAME 0.1.dat");
+        fs::write(
+            &file_path,
+            r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="pacman"><description>Pac-Man (Midway)</description></machine>
+    <machine name="puckman" cloneof="pacman"><description>Puck Man</description></machine>
+    <machine name="z80" isdevice="yes"/>
+</datafile>"#,
+        )
+        .unwrap();
+
+        let machines = read_mame_file_filtered(
+            file_path.to_str().unwrap(),
+            &[MachineFilter::Clones, MachineFilter::Device],
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        assert_eq!(machines.len(), 1);
+        assert!(machines.contains_key("pacman"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_mame_slots() {
+        let contents = r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="nes">
+        <description>Nintendo Entertainment System / Famicom</description>
+        <slot name="ctrl1">
+            <slotoption name="joypad" devname="nes_joypad" default="yes"/>
+            <slotoption name="zapper" devname="nes_zapper"/>
+        </slot>
+        <slot name="nes_slot"/>
+    </machine>
+</datafile>"#;
+
+        let machines = read_mame_from_str(contents, Box::new(|_| {})).unwrap();
+        let slots = &machines["nes"].slots;
+
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].name, "ctrl1");
+        assert_eq!(slots[0].options.len(), 2);
+        assert_eq!(slots[0].options[0].devname, "nes_joypad");
+        assert!(slots[0].options[0].is_default);
+        assert!(!slots[0].options[1].is_default);
+        assert_eq!(slots[1].name, "nes_slot");
+        assert!(slots[1].options.is_empty());
+    }
+}
@@ -5,3 +5,4 @@ pub mod mame_reader;
 pub mod nplayers_reader;
 pub mod resources_reader;
 pub mod series_reader;
+pub mod software_list_reader;
@@ -1,25 +1,32 @@
+use crate::core::models::cancellation::check_cancelled_at;
 use crate::{
     core::{
         data_cleanup::name_normalization,
         models::{
-            callback_progress::{CallbackType, ProgressCallback, ProgressInfo},
+            callback_progress::{ProgressCallback, ProgressInfo},
             core_models::Machine,
         },
     },
-    helpers::callback_progress_helper::get_progress_info,
+    helpers::{
+        callback_progress_helper::get_progress_info,
+        file_system_helpers::{
+            open_text_file, open_text_file_with_capacity, read_text, strip_bom, DEFAULT_BUFFER_SIZE,
+        },
+    },
 };
 use anyhow::Context;
 use std::collections::HashMap;
 use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, Read};
 
 /// Reads and processes the "nplayers.ini" file to extract the number of players for each machine.
 ///
 /// This function reads a specified "nplayers.ini" file line by line, extracts machine information,
 /// and populates a `HashMap` with machine names as keys and their corresponding `Machine` structs as values.
-/// It identifies the number of players for each machine, normalizes the player count, and stores it in the `extended_data`.
+/// It identifies the number of players for each machine, normalizes the player count, and stores it in the `extended_data`,
+/// along with the maximum number of players (`max_players`) and whether they can play simultaneously (`players_simultaneous`).
 /// Progress updates are provided via a callback function.
+/// The file may be gzipped (e.g. `nplayers.ini.gz`), in which case it is decompressed while being read.
 ///
 /// # Parameters
 /// - `file_path`: A `&str` representing the path to the "nplayers.ini" file to be read and processed.
@@ -37,6 +44,7 @@ use std::io::{BufRead, BufReader};
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
 /// - The total number of elements in the file cannot be determined.
+/// - The file is empty or contains no entries.
 ///
 /// # File structure
 /// The `nplayers.ini` file format represents configurations related to the number of players and game types for various ROMs.
@@ -77,41 +85,137 @@ pub fn read_nplayers_file(
     file_path: &str,
     progress_callback: ProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
-    let mut machines: HashMap<String, Machine> = HashMap::new();
+    read_nplayers_file_with_buffer_size(file_path, DEFAULT_BUFFER_SIZE, progress_callback)
+}
 
+/// Reads an nplayers.ini file using read buffers of the given size.
+///
+/// This function behaves like `read_nplayers_file`, with `buffer_size` setting the capacity of the buffered file reader.
+/// Larger buffers can improve the throughput of large files on slow disks.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_nplayers_file`.
+///
+pub(crate) fn read_nplayers_file_with_buffer_size(
+    file_path: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
     let data_file_name = file_path.split('/').last().unwrap();
 
+    read_nplayers(
+        || {
+            Ok(open_text_file_with_capacity(file_path, buffer_size)
+                .with_context(|| format!("Failed to open file: {}", file_path))?)
+        },
+        data_file_name,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of an nplayers.ini file held in memory.
+///
+/// This function behaves like `read_nplayers_file`, reading the entries from `contents` instead of a file.
+///
+/// # Parameters
+/// - `contents`: A `&str` with the content of the nplayers.ini file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_nplayers_file`.
+///
+pub fn read_nplayers_from_str(
+    contents: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = strip_bom(contents);
+    read_nplayers(
+        || Ok(contents.as_bytes()),
+        "nplayers.ini data",
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of an nplayers.ini file from any reader.
+///
+/// As the entries are counted before being processed, the whole content of `reader` is read into memory first,
+/// and is then processed like `read_nplayers_from_str` does. The content may be gzipped and may start with a byte order mark.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content of the nplayers.ini file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_nplayers_file`.
+///
+pub fn read_nplayers_from_reader(
+    reader: impl Read,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = read_text(reader)?;
+    read_nplayers_from_str(&contents, progress_callback)
+}
+
+/// Reads and processes the entries of an nplayers.ini file from a source that can be opened more than once.
+///
+/// # Parameters
+/// - `open`: A function returning a new buffered reader positioned at the start of the content.
+///   It is called twice: once to count the entries and once to process them.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_nplayers_file`.
+///
+fn read_nplayers<R: BufRead>(
+    open: impl Fn() -> Result<R, Box<dyn Error + Send + Sync>>,
+    data_file_name: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut machines: HashMap<String, Machine> = HashMap::new();
+
     // Get total elements
     progress_callback(get_progress_info(
         format!("Getting total entries for {}", data_file_name).as_str(),
     ));
 
-    let total_elements = match count_total_elements(file_path) {
+    let total_elements = match open().and_then(count_entries) {
         Ok(total_elements) => total_elements,
         Err(err) => {
-            progress_callback(ProgressInfo {
-                progress: 0,
-                total: 0,
-                message: format!("Couldn't get total entries for {}", data_file_name),
-                callback_type: CallbackType::Error,
-            });
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't get total entries for {}",
+                data_file_name
+            )));
 
             return Err(err.into());
         }
     };
 
+    if total_elements == 0 {
+        let message = format!(
+            "Data file {} is empty or contains no entries",
+            data_file_name
+        );
+        progress_callback(ProgressInfo::error(message.clone()));
+
+        return Err(message.into());
+    }
+
     progress_callback(get_progress_info(
         format!("Reading {}", data_file_name).as_str(),
     ));
 
     let to_ignore = ["[", ";", "", " "];
 
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
+    let reader = open()?;
 
     let mut processed_count = 0;
-    let batch = total_elements / 10;
+    let batch = (total_elements / 10).max(1);
 
     for line in reader.lines() {
         let line = line?;
@@ -136,28 +240,30 @@ pub fn read_nplayers_file(
             machine.players = Some(value.to_string());
             // Add normalized player count to the extended data
             let normalized_name = name_normalization::normalize_nplayer_name(&machine.players);
-            machine.extended_data.as_mut().unwrap().players = Some(normalized_name.clone());
+            let (max_players, players_simultaneous) = name_normalization::parse_nplayers(value);
+            let extended_data = machine.extended_data.as_mut().unwrap();
+            extended_data.players = Some(normalized_name.clone());
+            extended_data.max_players = max_players;
+            extended_data.players_simultaneous = players_simultaneous;
 
             // Increase processed count
             processed_count += 1;
+            // Stop if the read was cancelled
+            check_cancelled_at(processed_count)?;
             // Progress callback
             if processed_count % batch == 0 {
-                progress_callback(ProgressInfo {
-                    progress: processed_count as u64,
-                    total: total_elements as u64,
-                    message: String::from(""),
-                    callback_type: CallbackType::Progress,
-                });
+                progress_callback(ProgressInfo::progress(
+                    processed_count as u64,
+                    total_elements as u64,
+                ));
             }
         }
     }
 
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: total_elements as u64,
-        message: format!("{} loaded successfully", data_file_name),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} loaded successfully", data_file_name))
+            .with_progress(processed_count as u64, total_elements as u64),
+    );
 
     Ok(machines)
 }
@@ -180,10 +286,23 @@ pub fn read_nplayers_file(
 /// This function will return an error if:
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
-fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
+pub(crate) fn count_total_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let reader =
+        open_text_file(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+
+    count_entries(reader)
+}
+
+/// Counts the entries of the content of an nplayers.ini file, like `count_total_elements` does for a file.
+///
+/// # Parameters
+/// - `reader`: A buffered reader positioned at the start of the content.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>` with the total number of entries,
+/// or an error if the content cannot be read.
+///
+fn count_entries(reader: impl BufRead) -> Result<usize, Box<dyn Error + Send + Sync>> {
     let mut count = 0;
 
     for line in reader.lines() {
@@ -1,9 +1,13 @@
+use crate::core::models::cancellation::check_cancelled_at;
 use crate::{
     core::models::{
-        callback_progress::{CallbackType, ProgressCallback, ProgressInfo},
+        callback_progress::{ProgressCallback, ProgressInfo},
         core_models::{Machine, Resource},
     },
-    helpers::callback_progress_helper::get_progress_info,
+    helpers::{
+        callback_progress_helper::get_progress_info,
+        file_system_helpers::{read_text, strip_bom, DEFAULT_BUFFER_SIZE},
+    },
 };
 use anyhow::Context;
 use quick_xml::events::Event;
@@ -11,7 +15,7 @@ use quick_xml::Reader;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs::{self, File};
-use std::io::BufReader;
+use std::io::{BufRead, BufReader, Read};
 
 /// Reads a resource file and processes its content to extract machine-related resources.
 ///
@@ -35,6 +39,7 @@ use std::io::BufReader;
 /// - The file cannot be opened due to permission issues or if it does not exist.
 /// - There are I/O errors while reading the file.
 /// - There is an error while parsing the XML content.
+/// - The file is empty or contains no entries.
 ///
 /// # File structure
 /// The `resources.dat` file format represents a structured dataset of various resources associated with arcade machines.
@@ -64,36 +69,180 @@ pub fn read_resources_file(
     file_path: &str,
     progress_callback: ProgressCallback,
 ) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
-    let mut machines: HashMap<String, Machine> = HashMap::new();
+    read_resources_file_filtered(file_path, None, progress_callback)
+}
+
+/// Reads a resource file, keeping only the resources of the given types.
+///
+/// This function behaves like `read_resources_file`, but the resource groups whose type is not listed in
+/// `only_types` are skipped while parsing, so they are never loaded in memory. This is useful when only a few
+/// resource types are needed (e.g., `snap` and `titles`), as the resources file is very large.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the resource file to be read and processed.
+/// - `only_types`: An optional slice of resource types to keep (e.g., `&["snap", "titles"]`).
+///   When `None`, every resource type is kept.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///   The callback receives a `ProgressInfo` struct containing `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains a `HashMap` where the keys are machine names and the values are `Machine` structs
+///   with their associated resources of the requested types.
+/// - On failure: Contains an error if the file cannot be opened, read, or if there are issues processing its content.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The file cannot be opened due to permission issues or if it does not exist.
+/// - There are I/O errors while reading the file.
+/// - There is an error while parsing the XML content.
+///
+pub fn read_resources_file_filtered(
+    file_path: &str,
+    only_types: Option<&[&str]>,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    read_resources_file_with_buffer_size(
+        file_path,
+        only_types,
+        DEFAULT_BUFFER_SIZE,
+        progress_callback,
+    )
+}
 
+/// Reads a resource file, keeping only the resources of the given types, using read buffers of the given size.
+///
+/// This function behaves like `read_resources_file_filtered`, with `buffer_size` setting the capacity of the
+/// buffered file reader and of the XML event buffer. Larger buffers can improve the throughput of large files on slow disks.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the resource file to be read and processed.
+/// - `only_types`: An optional slice of resource types to keep. When `None`, every resource type is kept.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_resources_file_filtered`.
+///
+pub(crate) fn read_resources_file_with_buffer_size(
+    file_path: &str,
+    only_types: Option<&[&str]>,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
     let data_file_name = file_path.split('/').last().unwrap();
 
+    let file_content = fs::read_to_string(file_path)
+        .with_context(|| format!("Failed to read file content: {}", file_path))?;
+    let file =
+        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+
+    read_resources(
+        &file_content,
+        BufReader::with_capacity(buffer_size, file),
+        data_file_name,
+        only_types,
+        buffer_size,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a resources XML file held in memory.
+///
+/// This function behaves like `read_resources_file`, reading the entries from `contents` instead of a file.
+///
+/// # Parameters
+/// - `contents`: A `&str` with the content of the resources XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_resources_file`.
+///
+pub fn read_resources_from_str(
+    contents: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = strip_bom(contents);
+    read_resources(
+        contents,
+        contents.as_bytes(),
+        "resources data",
+        None,
+        DEFAULT_BUFFER_SIZE,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a resources XML file from any reader.
+///
+/// As the entries are counted before being processed, the whole content of `reader` is read into memory first,
+/// and is then processed like `read_resources_from_str` does. The content may be gzipped and may start with a byte order mark.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content of the resources XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_resources_file`.
+///
+pub fn read_resources_from_reader(
+    reader: impl Read,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = read_text(reader)?;
+    read_resources_from_str(&contents, progress_callback)
+}
+
+/// Reads and processes the entries of a resources XML file.
+///
+/// # Parameters
+/// - `file_content`: The whole content of the file, used to count the entries before processing them.
+/// - `reader`: A buffered reader positioned at the start of the same content, from which the entries are processed.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `only_types`: An optional slice of resource types to keep. When `None`, every resource type is kept.
+/// - `buffer_size`: The capacity of the XML event buffer, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_resources_file`.
+///
+fn read_resources<R: BufRead>(
+    file_content: &str,
+    reader: R,
+    data_file_name: &str,
+    only_types: Option<&[&str]>,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut machines: HashMap<String, Machine> = HashMap::new();
+
     // Get total elements
     progress_callback(get_progress_info(
         format!("Getting total entries for {}", data_file_name).as_str(),
     ));
 
-    let file =
-        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-    let reader = BufReader::new(file);
-
-    // Read the file content
-    let file_content = fs::read_to_string(file_path)?;
-
-    let total_elements = match count_total_elements(&file_content) {
+    let total_elements = match count_total_elements(file_content) {
         Ok(total_elements) => total_elements,
         Err(err) => {
-            progress_callback(ProgressInfo {
-                progress: 0,
-                total: 0,
-                message: format!("Couldn't get total entries for {}", data_file_name),
-                callback_type: CallbackType::Error,
-            });
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't get total entries for {}",
+                data_file_name
+            )));
 
             return Err(err.into());
         }
     };
 
+    if total_elements == 0 {
+        let message = format!(
+            "Data file {} is empty or contains no entries",
+            data_file_name
+        );
+        progress_callback(ProgressInfo::error(message.clone()));
+
+        return Err(message.into());
+    }
+
     progress_callback(get_progress_info(
         format!("Reading {}", data_file_name).as_str(),
     ));
@@ -101,30 +250,42 @@ pub fn read_resources_file(
     let mut xml_reader = Reader::from_reader(reader);
     xml_reader.trim_text(true);
 
-    let mut buf = Vec::with_capacity(8 * 1024);
+    let mut buf = Vec::with_capacity(buffer_size);
 
     let mut current_section: Option<String> = None;
 
     let mut processed_count = 0;
-    let batch = total_elements / 10;
+    let batch = (total_elements / 10).max(1);
 
     loop {
         match xml_reader.read_event(&mut buf) {
             Ok(Event::Start(ref e)) => {
-                process_node(e, &mut xml_reader, &mut current_section, &mut machines)?;
+                process_node(
+                    e,
+                    &mut xml_reader,
+                    only_types,
+                    &mut current_section,
+                    &mut machines,
+                )?;
             }
             Ok(Event::Empty(ref e)) => {
-                process_node(e, &mut xml_reader, &mut current_section, &mut machines)?;
+                process_node(
+                    e,
+                    &mut xml_reader,
+                    only_types,
+                    &mut current_section,
+                    &mut machines,
+                )?;
                 // Increase processed count
                 processed_count += 1;
+                // Stop if the read was cancelled
+                check_cancelled_at(processed_count)?;
                 // Progress callback
                 if processed_count % batch == 0 {
-                    progress_callback(ProgressInfo {
-                        progress: processed_count as u64,
-                        total: total_elements as u64,
-                        message: String::from(""),
-                        callback_type: CallbackType::Progress,
-                    });
+                    progress_callback(ProgressInfo::progress(
+                        processed_count as u64,
+                        total_elements as u64,
+                    ));
                 }
             }
             Ok(Event::Eof) => break,
@@ -134,12 +295,10 @@ pub fn read_resources_file(
         buf.clear();
     }
 
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: total_elements as u64,
-        message: format!("{} loaded successfully", data_file_name),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} loaded successfully", data_file_name))
+            .with_progress(processed_count as u64, total_elements as u64),
+    );
 
     Ok(machines)
 }
@@ -153,6 +312,7 @@ pub fn read_resources_file(
 /// # Parameters
 /// - `e`: A reference to the current XML event (`BytesStart`) representing the node being processed.
 /// - `reader`: A mutable reference to the `Reader` instance that reads the XML content.
+/// - `only_types`: An optional slice of resource types to keep. The sections of other types are skipped.
 /// - `current_section`: A mutable reference to an `Option<String>` representing the current section being processed.
 ///   It is updated with the machine or section name if a `machine` node is encountered.
 /// - `machines`: A mutable reference to a `HashMap<String, Machine>` that stores the `Machine` objects
@@ -167,9 +327,10 @@ pub fn read_resources_file(
 /// This function will return an error if:
 /// - There is a failure to decode or extract any XML attributes or content.
 /// - There is a parsing issue or I/O error while processing the XML node.
-fn process_node(
+fn process_node<R: BufRead>(
     e: &quick_xml::events::BytesStart,
-    reader: &mut Reader<BufReader<File>>,
+    reader: &mut Reader<R>,
+    only_types: Option<&[&str]>,
     current_section: &mut Option<String>,
     machines: &mut HashMap<String, Machine>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -184,6 +345,11 @@ fn process_node(
                 }
             }
 
+            // Skip the sections of the resource types that are not requested
+            if let Some(only_types) = only_types {
+                section_name = section_name.filter(|name| only_types.contains(&name.as_str()));
+            }
+
             *current_section = section_name;
         }
         b"rom" => {
@@ -237,6 +403,24 @@ fn process_node(
     Ok(())
 }
 
+/// Counts the total number of `<rom>` elements in the provided file.
+///
+/// This function reads the whole file and counts its entries with `count_total_elements`,
+/// without processing them.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and analyzed.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the total number of `<rom>` elements found in the file.
+/// - On failure: Contains an error if the file cannot be read or its content cannot be parsed.
+///
+pub(crate) fn count_file_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let file_content = fs::read_to_string(file_path)?;
+    count_total_elements(&file_content)
+}
+
 /// Counts the total number of `<rom>` elements in an XML file content.
 ///
 /// This function reads the content of an XML string and counts the number of `<rom>` elements
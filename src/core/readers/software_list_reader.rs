@@ -0,0 +1,598 @@
+use crate::core::models::cancellation::check_cancelled_at;
+use crate::{
+    core::{
+        data_cleanup::name_normalization,
+        models::{
+            callback_progress::{CallbackType, ProgressCallback, ProgressInfo},
+            core_models::{Disk, ExtendedData, Machine, Rom, Software},
+        },
+    },
+    helpers::{
+        callback_progress_helper::get_progress_info,
+        file_system_helpers::{read_text, strip_bom, DEFAULT_BUFFER_SIZE},
+    },
+};
+use anyhow::Context;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::sync::{Arc, Mutex};
+use std::{collections::HashMap, error::Error};
+
+/// Reads a MAME software list file and processes the software entries contained within.
+///
+/// This function opens and reads the specified software list file, counting the total number of
+/// software entries, then iteratively processes each entry to construct a `HashMap` of software titles.
+/// Each software title is stored as a `Machine`, so the result can be handled like the output of the
+/// other readers.
+///
+/// # Parameters
+/// - `file_path`: The path to the software list file to be read.
+/// - `progress_callback`: A callback function to report progress during the file processing.
+///
+/// # Returns
+/// - `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>`:
+///   - On success: A `HashMap` where each key is `<list name>:<software name>` and the value is the
+///     corresponding software title stored as a `Machine` struct.
+///   - On failure: An error if the file could not be read or processed.
+///
+/// # Errors
+/// - Returns an error if the file cannot be opened or read.
+/// - Returns an error if there is an issue processing the XML content.
+/// - Returns an error if the file is empty or contains no entries.
+///
+/// # File structure
+/// Software list files (`hash/*.xml` in the MAME distribution) describe the cartridges, disks and tapes
+/// that can be loaded by the machines referencing the list. A file contains a single `<softwarelist>`
+/// element, or several of them wrapped in a `<softwarelists>` element.
+///
+/// # Software List
+/// - `name`: The name of the software list (e.g., `nes`), stored in the `software_list` of every title (attribute).
+///
+/// # Software
+/// Represents a single software title with various attributes:
+/// - `name`: The unique identifier for the software within its list (attribute).
+/// - `clone_of`: Indicates the software is a variant of another software title (optional, attribute).
+/// - `description`: Textual description of the software (optional, child node).
+/// - `year`: Year of release (optional, child node).
+/// - `publisher`: Publisher name, stored as the manufacturer (optional, child node).
+///
+/// # Parts
+/// - Each `<part>` element groups the media of the software, and contains:
+///   - `<dataarea>` elements with `<rom>` elements, stored in `roms`:
+///     - `name`: Name of the ROM (attribute).
+///     - `size`: Size of the ROM (attribute).
+///     - `status`: Status attribute (optional, attribute).
+///     - `crc`: CRC value (optional, attribute).
+///     - `sha1`: SHA1 value (optional, attribute).
+///     - `offset`: Offset where the ROM is loaded in its data area (optional, attribute).
+///   - `<diskarea>` elements with `<disk>` elements, stored in `disks`:
+///     - `name`: Name of the disk (attribute).
+///     - `sha1`: SHA1 value (optional, attribute).
+///     - `status`: Status attribute (optional, attribute).
+pub fn read_software_list_file(
+    file_path: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    read_software_list_file_with_buffer_size(file_path, DEFAULT_BUFFER_SIZE, progress_callback)
+}
+
+/// Reads a software list file using read buffers of the given size.
+///
+/// This function behaves like `read_software_list_file`, with `buffer_size` setting the capacity of the buffered file reader and of the XML event buffer.
+/// Larger buffers can improve the throughput of large files on slow disks.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and processed.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_software_list_file`.
+///
+pub(crate) fn read_software_list_file_with_buffer_size(
+    file_path: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let data_file_name = file_path.split('/').next_back().unwrap();
+
+    let file_content = fs::read_to_string(file_path)
+        .with_context(|| format!("Failed to read file content: {}", file_path))?;
+    let file =
+        File::open(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
+
+    read_software_list(
+        &file_content,
+        BufReader::with_capacity(buffer_size, file),
+        data_file_name,
+        buffer_size,
+        progress_callback,
+    )
+}
+
+/// Reads several software list files, such as the `hash/*.xml` files of the MAME software list pack.
+///
+/// Each file is read with `read_software_list_file_with_buffer_size`, and the software titles of every list are
+/// merged. As the titles are keyed by `<list name>:<software name>`, the titles of different lists don't collide.
+/// The end of each file is reported as a `CallbackType::Info` update, and a single `CallbackType::Finish` update
+/// is sent once every file has been read.
+///
+/// # Parameters
+/// - `file_paths`: The paths to the software list files to be read.
+/// - `buffer_size`: The size of the read buffers, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_software_list_file`,
+/// with the software titles of every file, or the error of the first file that cannot be read.
+///
+pub(crate) fn read_software_list_files(
+    file_paths: &[String],
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let progress_callback = Arc::new(Mutex::new(progress_callback));
+    let mut software_titles: HashMap<String, Machine> = HashMap::new();
+
+    for file_path in file_paths {
+        let file_callback = Arc::clone(&progress_callback);
+        let list_titles = read_software_list_file_with_buffer_size(
+            file_path,
+            buffer_size,
+            Box::new(move |mut progress_info| {
+                if let CallbackType::Finish = progress_info.callback_type {
+                    progress_info.callback_type = CallbackType::Info;
+                }
+                if let Ok(callback) = file_callback.lock() {
+                    callback(progress_info);
+                }
+            }),
+        )?;
+
+        for (key, software) in list_titles {
+            software_titles.entry(key).or_insert(software);
+        }
+    }
+
+    if let Ok(callback) = progress_callback.lock() {
+        callback(
+            ProgressInfo::finish(format!(
+                "{} software lists loaded successfully",
+                file_paths.len()
+            ))
+            .with_progress(software_titles.len() as u64, software_titles.len() as u64),
+        );
+    }
+
+    Ok(software_titles)
+}
+
+/// Reads and processes the content of a software list XML file held in memory.
+///
+/// This function behaves like `read_software_list_file`, reading the entries from `contents` instead of a file.
+///
+/// # Parameters
+/// - `contents`: A `&str` with the content of the software list XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_software_list_file`.
+///
+pub fn read_software_list_from_str(
+    contents: &str,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = strip_bom(contents);
+    read_software_list(
+        contents,
+        contents.as_bytes(),
+        "software list data",
+        DEFAULT_BUFFER_SIZE,
+        progress_callback,
+    )
+}
+
+/// Reads and processes the content of a software list XML file from any reader.
+///
+/// As the entries are counted before being processed, the whole content of `reader` is read into memory first,
+/// and is then processed like `read_software_list_from_str` does. The content may be gzipped and may start with a byte order mark.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content of the software list XML file.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_software_list_file`.
+///
+pub fn read_software_list_from_reader(
+    reader: impl Read,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let contents = read_text(reader)?;
+    read_software_list_from_str(&contents, progress_callback)
+}
+
+/// Reads and processes the entries of a software list XML file.
+///
+/// # Parameters
+/// - `file_content`: The whole content of the file, used to count the entries before processing them.
+/// - `reader`: A buffered reader positioned at the start of the same content, from which the entries are processed.
+/// - `data_file_name`: The name of the data, used in the progress messages.
+/// - `buffer_size`: The capacity of the XML event buffer, in bytes.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that tracks progress and provides status updates.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>>` like `read_software_list_file`.
+///
+fn read_software_list<R: BufRead>(
+    file_content: &str,
+    reader: R,
+    data_file_name: &str,
+    buffer_size: usize,
+    progress_callback: ProgressCallback,
+) -> Result<HashMap<String, Machine>, Box<dyn Error + Send + Sync>> {
+    let mut software_titles: HashMap<String, Machine> = HashMap::new();
+
+    // Get total elements
+    progress_callback(get_progress_info(
+        format!("Getting total entries for {}", data_file_name).as_str(),
+    ));
+
+    // Count the number of software entries in the file
+    let total_elements = match count_total_elements(file_content) {
+        Ok(total_elements) => total_elements,
+        Err(err) => {
+            progress_callback(ProgressInfo::error(format!(
+                "Couldn't get total entries for {}",
+                data_file_name
+            )));
+
+            return Err(err);
+        }
+    };
+
+    if total_elements == 0 {
+        let message = format!(
+            "Data file {} is empty or contains no entries",
+            data_file_name
+        );
+        progress_callback(ProgressInfo::error(message.clone()));
+
+        return Err(message.into());
+    }
+
+    progress_callback(get_progress_info(
+        format!("Reading {}", data_file_name).as_str(),
+    ));
+
+    let mut xml_reader = Reader::from_reader(reader);
+    xml_reader.trim_text(true);
+
+    let mut buf = Vec::with_capacity(buffer_size);
+
+    let mut current_list: Option<String> = None;
+    let mut current_software: Option<Machine> = None;
+
+    let mut processed_count = 0;
+    let batch = (total_elements / 10).max(1);
+
+    loop {
+        match xml_reader.read_event(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                process_node(e, &mut xml_reader, &mut current_list, &mut current_software)?;
+            }
+            Ok(Event::Empty(ref e)) => {
+                process_node(e, &mut xml_reader, &mut current_list, &mut current_software)?;
+            }
+            Ok(Event::End(ref e)) => match e.name() {
+                b"software" => {
+                    if let Some(software) = current_software.take() {
+                        let list_name = current_list.clone().unwrap_or_default();
+                        software_titles
+                            .entry(format!("{}:{}", list_name, software.name))
+                            .or_insert_with(|| software);
+                    }
+
+                    // Increase processed count
+                    processed_count += 1;
+                    // Stop if the read was cancelled
+                    check_cancelled_at(processed_count)?;
+                    // Progress callback
+                    if processed_count % batch == 0 {
+                        progress_callback(ProgressInfo::progress(
+                            processed_count as u64,
+                            total_elements as u64,
+                        ));
+                    }
+                }
+                b"softwarelist" => {
+                    current_list = None;
+                }
+                _ => (),
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(Box::new(e)),
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    progress_callback(
+        ProgressInfo::finish(format!("{} loaded successfully", data_file_name))
+            .with_progress(processed_count as u64, total_elements as u64),
+    );
+
+    Ok(software_titles)
+}
+
+/// Processes an XML node and updates the current software title with the parsed data.
+///
+/// This function handles the XML elements relevant to the structure of a software list. It keeps
+/// track of the software list being read, initializes new software titles, reads their attributes,
+/// and adds the ROMs and disks found in their parts.
+///
+/// # Parameters
+/// - `e`: A reference to the `BytesStart` event representing the start of an XML element.
+/// - `reader`: A mutable reference to the `Reader` used to read the XML data.
+/// - `current_list`: A mutable reference to an `Option<String>` holding the name of the software list being read.
+/// - `current_software`: A mutable reference to an `Option<Machine>`, which will be updated
+///   with the parsed software data.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Indicates the node was processed without errors.
+/// - On failure: Contains an error if there were issues reading the XML or updating the software data.
+fn process_node<R: BufRead>(
+    e: &quick_xml::events::BytesStart,
+    reader: &mut Reader<R>,
+    current_list: &mut Option<String>,
+    current_software: &mut Option<Machine>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match e.name() {
+        b"softwarelist" => {
+            let attrs = e.attributes().map(|a| a.unwrap());
+            for attr in attrs {
+                if attr.key == b"name" {
+                    *current_list = Some(attr.unescape_and_decode_value(reader)?);
+                }
+            }
+        }
+        b"software" => {
+            let mut software = Machine::new(String::new());
+            let attrs = e.attributes().map(|a| a.unwrap());
+            for attr in attrs {
+                match attr.key {
+                    b"name" => software.name = attr.unescape_and_decode_value(reader)?,
+                    b"cloneof" => software.clone_of = Some(attr.unescape_and_decode_value(reader)?),
+                    _ => {}
+                }
+            }
+            if let Some(list_name) = current_list {
+                software.software_list.push(Software {
+                    name: list_name.clone(),
+                    titles: vec![],
+                });
+            }
+            // Set is_parent flag in Extended Data
+            software.extended_data = Some(ExtendedData {
+                is_parent: Some(software.references_no_parent()),
+                ..Default::default()
+            });
+
+            *current_software = Some(software);
+        }
+        b"description" => {
+            if let Some(ref mut software) = current_software {
+                software.description = Some(reader.read_text(b"description", &mut Vec::new())?);
+                // Set normalized name in Extended Data
+                let refactored_name =
+                    name_normalization::normalize_machine_name(&software.description);
+                software.extended_data.as_mut().unwrap().name = Some(refactored_name);
+            }
+        }
+        b"year" => {
+            if let Some(ref mut software) = current_software {
+                software.year = Some(reader.read_text(b"year", &mut Vec::new())?);
+                // Set normalized year in Extended Data, Unknown if it contains ? or is empty
+                software.extended_data.as_mut().unwrap().year =
+                    Some(name_normalization::normalize_year(&software.year));
+            }
+        }
+        b"publisher" => {
+            if let Some(ref mut software) = current_software {
+                software.manufacturer = Some(reader.read_text(b"publisher", &mut Vec::new())?);
+                // Set normalized manufacturer in Extended Data
+                let normalized_manufacturer =
+                    name_normalization::normalize_manufacturer_name(&software.manufacturer);
+                software.extended_data.as_mut().unwrap().manufacturer =
+                    Some(normalized_manufacturer);
+            }
+        }
+        b"rom" => {
+            let mut rom = Rom {
+                name: String::new(),
+                merge: None,
+                size: 0,
+                crc: None,
+                sha1: None,
+                status: None,
+                region: None,
+                offset: None,
+                bios: None,
+            };
+            let attrs = e.attributes().map(|a| a.unwrap());
+            for attr in attrs {
+                match attr.key {
+                    b"name" => rom.name = attr.unescape_and_decode_value(reader)?,
+                    b"size" => {
+                        rom.size = attr.unescape_and_decode_value(reader)?.parse().unwrap_or(0)
+                    }
+                    b"crc" => rom.crc = Some(attr.unescape_and_decode_value(reader)?),
+                    b"sha1" => rom.sha1 = Some(attr.unescape_and_decode_value(reader)?),
+                    b"status" => rom.status = Some(attr.unescape_and_decode_value(reader)?),
+                    b"region" => rom.region = Some(attr.unescape_and_decode_value(reader)?),
+                    b"offset" => rom.offset = Some(attr.unescape_and_decode_value(reader)?),
+                    _ => {}
+                }
+            }
+            // Roms without a name only extend the previous rom (e.g. `loadflag="continue"`)
+            if rom.name.is_empty() {
+                return Ok(());
+            }
+            if let Some(ref mut software) = current_software {
+                software.roms.push(rom);
+            }
+        }
+        b"disk" => {
+            let mut disk = Disk {
+                name: String::new(),
+                sha1: None,
+                merge: None,
+                status: None,
+                region: None,
+            };
+            let attrs = e.attributes().map(|a| a.unwrap());
+            for attr in attrs {
+                match attr.key {
+                    b"name" => disk.name = attr.unescape_and_decode_value(reader)?,
+                    b"sha1" => disk.sha1 = Some(attr.unescape_and_decode_value(reader)?),
+                    b"status" => disk.status = Some(attr.unescape_and_decode_value(reader)?),
+                    _ => {}
+                }
+            }
+            if let Some(ref mut software) = current_software {
+                software.disks.push(disk);
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Counts the total number of `<software>` elements in the provided file.
+///
+/// This function reads the whole file and counts its entries with `count_total_elements`,
+/// without processing them.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to be read and analyzed.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the total number of `<software>` elements found in the file.
+/// - On failure: Contains an error if the file cannot be read or its content cannot be parsed.
+///
+pub(crate) fn count_file_elements(file_path: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let file_content = fs::read_to_string(file_path)?;
+    count_total_elements(&file_content)
+}
+
+/// Counts the total number of `<software>` elements in the provided XML content.
+///
+/// This function parses the given XML content and counts how many `<software>` elements
+/// are present. The count is used to determine the total number of software titles in the XML.
+///
+/// # Parameters
+/// - `file_content`: A `&str` containing the entire content of the XML file as a string.
+///
+/// # Returns
+/// Returns a `Result<usize, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the total number of `<software>` elements found in the XML content.
+/// - On failure: Contains an error if there are issues while reading or parsing the XML content.
+///
+/// # Errors
+/// This function will return an error if:
+/// - There are I/O errors or issues while reading and parsing the XML content.
+fn count_total_elements(file_content: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let mut reader = Reader::from_str(file_content);
+    reader.trim_text(true);
+    let mut buf = Vec::with_capacity(8 * 1024);
+    let mut count = 0;
+
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(ref e)) if e.name() == b"software" => {
+                count += 1;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(Box::new(e));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_software_list_file() {
+        let temp_dir = std::env::temp_dir().join("mame_parser_software_list");
+        fs::create_dir_all(&temp_dir).unwrap();
+        let file_path = temp_dir.join("software lists.xml");
+        fs::write(
+            &file_path,
+            r#"<?xml version="1.0"?>
+<softwarelists>
+    <softwarelist name="nes" description="Nintendo Entertainment System cartridges">
+        <software name="smb">
+            <description>Super Mario Bros. (World)</description>
+            <year>1985</year>
+            <publisher>Nintendo</publisher>
+            <part name="cart" interface="nes_cart">
+                <dataarea name="prg" size="32768">
+                    <rom name="smb.prg" size="32768" crc="5cf548d3" sha1="fefe07c2ac5b8ea3a2f8f5e0d0a8d50d5e5c0b86" offset="00000" />
+                </dataarea>
+                <dataarea name="chr" size="8192">
+                    <rom name="smb.chr" size="8192" crc="867b51ad" sha1="394badaf0b0bdd0ac279005a5d1fd1a0c2a0d93c" offset="00000" />
+                </dataarea>
+            </part>
+        </software>
+        <software name="smbj" cloneof="smb">
+            <description>Super Mario Bros. (Japan)</description>
+            <year>1985</year>
+            <publisher>Nintendo</publisher>
+        </software>
+    </softwarelist>
+    <softwarelist name="snes" description="Nintendo SNES cartridges">
+        <software name="smb">
+            <description>Homonym in another list</description>
+        </software>
+    </softwarelist>
+</softwarelists>"#,
+        )
+        .unwrap();
+
+        let software_titles =
+            read_software_list_file(file_path.to_str().unwrap(), Box::new(|_| {})).unwrap();
+
+        assert_eq!(software_titles.len(), 3);
+
+        let smb = &software_titles["nes:smb"];
+        assert_eq!(smb.name, "smb");
+        assert_eq!(smb.year.as_deref(), Some("1985"));
+        assert_eq!(smb.manufacturer.as_deref(), Some("Nintendo"));
+        assert_eq!(smb.roms.len(), 2);
+        assert_eq!(smb.roms[0].name, "smb.prg");
+        assert_eq!(smb.software_list[0].name, "nes");
+
+        let smbj = &software_titles["nes:smbj"];
+        assert_eq!(smbj.clone_of.as_deref(), Some("smb"));
+        assert_eq!(smbj.extended_data.as_ref().unwrap().is_parent, Some(false));
+
+        assert_eq!(
+            software_titles["snes:smb"].description.as_deref(),
+            Some("Homonym in another list")
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
@@ -4,11 +4,26 @@ use crate::{
         get_series_list, get_subcategories_list,
     },
     helpers::callback_progress_helper::get_progress_info,
-    models::Machine,
-    progress::{CallbackType, ProgressCallback, ProgressInfo},
+    models::{ExtendedData, Machine, WriteOptions},
+    progress::{ProgressCallback, ProgressInfo},
+    queries::machines_sorted,
 };
 use csv::Writer;
-use std::{collections::HashMap, error::Error, fs::File, io::Write};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    error::Error,
+    fs::File,
+    io::Write,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// UTF-8 byte order mark written at the start of the CSV files when requested.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
 
 /// Writes machine data to multiple CSV files for export.
 ///
@@ -20,8 +35,10 @@ use std::{collections::HashMap, error::Error, fs::File, io::Write};
 /// - `export_path`: A `&str` representing the path where the CSV files will be exported.
 /// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
 ///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `options`: A reference to the `WriteOptions` used for the export (e.g., `bom` to start each file with a UTF-8 BOM).
 /// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the CSV writing process.
 ///   The callback receives a `ProgressInfo` struct containing fields like `progress`, `total`, `message`, and `callback_type`.
+///   The `progress` and `total` count the CSV files written, as the files are written concurrently.
 ///
 /// # Returns
 /// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
@@ -45,6 +62,8 @@ use std::{collections::HashMap, error::Error, fs::File, io::Write};
 /// - `samples.csv`: Contains sample data for each machine.
 /// - `history_sections.csv`: Contains historical information and sections for each machine.
 /// - `resources.csv`: Contains resource information such as size, type, and checksums for each machine.
+/// - `slots.csv`: Contains the devices that can be plugged into each expansion slot of each machine, one row per option.
+///   Slots without options are written with empty option fields.
 /// - `manufacturers.csv`: Contains a list of manufacturers and the machines associated with them.
 /// - `series.csv`: Contains a list of game series and the machines associated with each series.
 /// - `languages.csv`: Contains a list of languages and the machines available in each language.
@@ -55,6 +74,7 @@ use std::{collections::HashMap, error::Error, fs::File, io::Write};
 pub fn write_csv(
     export_path: &str,
     machines: &HashMap<String, Machine>,
+    options: &WriteOptions,
     progress_callback: ProgressCallback,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     // If the machines were not loaded, return an error
@@ -66,318 +86,431 @@ pub fn write_csv(
         format!("Writing CSV files to {}", export_path).as_str(),
     ));
 
-    let total_elements = machines.len();
-    let mut processed_count = 0;
-    let batch = total_elements / 10;
-
-    let mut machines_vec: Vec<(&String, &Machine)> = machines.iter().collect();
-    machines_vec.sort_by_key(|&(name, _)| name);
-
-    // Create the CSV writers
-    let mut machines_wtr = create_writer(export_path, "machines")?;
-    let mut roms_wtr = create_writer(export_path, "roms")?;
-    let mut bios_sets_wtr = create_writer(export_path, "bios_sets")?;
-    let mut device_refs_wtr = create_writer(export_path, "device_refs")?;
-    let mut disks_wtr = create_writer(export_path, "disks")?;
-    let mut softwares_wtr = create_writer(export_path, "softwares")?;
-    let mut samples_wtr = create_writer(export_path, "samples")?;
-    let mut history_sections_wtr = create_writer(export_path, "history_sections")?;
-    let mut resources_wtr = create_writer(export_path, "resources")?;
-
-    // Write the CSV headers
-    write_csv_header(
-        &mut machines_wtr,
-        &[
-            "name",
-            "source_file",
-            "rom_of",
-            "clone_of",
-            "is_bios",
-            "is_device",
-            "runnable",
-            "is_mechanical",
-            "sample_of",
-            "description",
-            "year",
-            "manufacturer",
-            "driver_status",
+    // The collections of machines, written once the entity files are
+    let collections: [(&str, CollectionList, &[&str], bool); 6] = [
+        (
+            "manufacturers",
+            get_manufacturers_list,
+            &["name", "machines"],
+            false,
+        ),
+        ("series", get_series_list, &["name", "machines"], false),
+        (
             "languages",
-            "players",
-            "series",
-            "category",
-            "subcategory",
-            "is_mature",
-            "extended_name",
-            "extended_manufacturer",
-            "extended_players",
-            "extended_is_parent",
-            "extended_year",
-        ],
-    )?;
-    write_csv_header(
-        &mut roms_wtr,
-        &[
-            "machine_name",
-            "name",
-            "size",
-            "merge",
-            "status",
-            "crc",
-            "sha1",
-        ],
-    )?;
-    write_csv_header(&mut bios_sets_wtr, &["machine_name", "name", "description"])?;
-    write_csv_header(&mut device_refs_wtr, &["machine_name", "name"])?;
-    write_csv_header(
-        &mut disks_wtr,
-        &["machine_name", "name", "sha1", "merge", "status", "region"],
-    )?;
-    write_csv_header(&mut softwares_wtr, &["machine_name", "name"])?;
-    write_csv_header(&mut samples_wtr, &["machine_name", "name"])?;
-    write_csv_header(
-        &mut history_sections_wtr,
-        &["machine_name", "name", "text", "order"],
-    )?;
-    write_csv_header(
-        &mut resources_wtr,
-        &["machine_name", "type", "name", "size", "crc", "sha1"],
-    )?;
-
-    for (name, machine) in machines_vec {
-        // Write machine
+            get_languages_list,
+            &["name", "machines"],
+            false,
+        ),
+        ("players", get_players_list, &["name", "machines"], false),
+        (
+            "categories",
+            get_categories_list,
+            &["name", "machines"],
+            false,
+        ),
+        (
+            "subcategories",
+            get_subcategories_list,
+            &["category", "subcategory", "machines"],
+            true,
+        ),
+    ];
+
+    // Write each entity file on its own thread, up to the number of threads of the options
+    let entity_files = entity_files(options);
+    let sorted_machines = machines_sorted(machines);
+    let total_files = (entity_files.len() + collections.len()) as u64;
+    let next_file = AtomicUsize::new(0);
+    let written_files = AtomicUsize::new(0);
+    let shared_callback = Mutex::new(progress_callback);
+
+    thread::scope(|scope| -> Result<(), Box<dyn Error + Send + Sync>> {
+        let workers: Vec<_> = (0..options.csv_threads.clamp(1, entity_files.len()))
+            .map(|_| {
+                scope.spawn(|| -> Result<(), Box<dyn Error + Send + Sync>> {
+                    loop {
+                        let Some(entity_file) =
+                            entity_files.get(next_file.fetch_add(1, Ordering::SeqCst))
+                        else {
+                            return Ok(());
+                        };
+
+                        write_entity_file(export_path, entity_file, &sorted_machines, options)?;
+
+                        let written = written_files.fetch_add(1, Ordering::SeqCst) + 1;
+                        if let Ok(callback) = shared_callback.lock() {
+                            callback(ProgressInfo::progress(written as u64, total_files));
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            worker.join().map_err(|_| "CSV writer thread panicked")??;
+        }
+
+        Ok(())
+    })?;
+
+    let progress_callback = shared_callback
+        .into_inner()
+        .map_err(|_| "CSV progress callback poisoned")?;
+    let mut written_files = written_files.into_inner() as u64;
+
+    for (file_name, get_list, headers, is_subcategory) in collections {
+        progress_callback(get_progress_info(format!("Adding {}", file_name).as_str()));
+        export_collection(
+            get_list(machines),
+            export_path,
+            file_name,
+            headers,
+            is_subcategory,
+            options.bom,
+        )?;
+
+        written_files += 1;
+        progress_callback(ProgressInfo::progress(written_files, total_files));
+    }
+
+    progress_callback(
+        ProgressInfo::finish(format!("CSVs exported successfully to {}", export_path))
+            .with_progress(total_files, total_files),
+    );
+
+    Ok(())
+}
+
+/// Lists the values of a collection of machines, with the number of machines of each value.
+type CollectionList = fn(&HashMap<String, Machine>) -> HashMap<String, usize>;
+
+/// Writes the rows of a machine to the CSV file of an entity.
+type WriteRows =
+    fn(&mut Writer<File>, &Machine, &WriteOptions) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+/// Represents a CSV file with the rows of an entity of the machines, such as their ROMs.
+struct EntityFile {
+    /// The name of the file, without extension.
+    file_name: &'static str,
+    /// The header row of the file.
+    headers: Vec<&'static str>,
+    /// Writes the rows of a machine to the file.
+    write_rows: WriteRows,
+}
+
+/// Returns the CSV files written for the entities of the machines, in the order they are listed by `write_csv`.
+///
+/// # Parameters
+/// - `options`: A reference to the `WriteOptions`, of which `debug_fields` adds columns to `machines.csv`.
+fn entity_files(options: &WriteOptions) -> Vec<EntityFile> {
+    let mut machine_headers = vec![
+        "name",
+        "display_name",
+        "source_file",
+        "rom_of",
+        "clone_of",
+        "is_bios",
+        "is_device",
+        "runnable",
+        "is_mechanical",
+        "sample_of",
+        "description",
+        "year",
+        "manufacturer",
+        "driver_status",
+        "languages",
+        "players",
+        "series",
+        "category",
+        "subcategory",
+        "is_mature",
+        "version_added",
+        "extended_name",
+        "extended_manufacturer",
+        "extended_players",
+        "extended_is_parent",
+        "extended_year",
+    ];
+    if options.debug_fields {
+        machine_headers.extend([
+            "extended_name_raw",
+            "extended_manufacturer_raw",
+            "extended_players_raw",
+            "extended_year_raw",
+        ]);
+    }
+
+    vec![
+        EntityFile {
+            file_name: "machines",
+            headers: machine_headers,
+            write_rows: write_machine_rows,
+        },
+        EntityFile {
+            file_name: "roms",
+            headers: vec![
+                "machine_name",
+                "name",
+                "size",
+                "merge",
+                "status",
+                "crc",
+                "sha1",
+                "region",
+                "offset",
+                "bios",
+            ],
+            write_rows: write_rom_rows,
+        },
+        EntityFile {
+            file_name: "bios_sets",
+            headers: vec!["machine_name", "name", "description"],
+            write_rows: |wtr, machine, _| {
+                for bios_set in &machine.bios_sets {
+                    write_csv_record(wtr, &[&machine.name, &bios_set.name, &bios_set.description])?;
+                }
+                Ok(())
+            },
+        },
+        EntityFile {
+            file_name: "device_refs",
+            headers: vec!["machine_name", "name"],
+            write_rows: |wtr, machine, _| {
+                for device_ref in &machine.device_refs {
+                    write_csv_record(wtr, &[&machine.name, &device_ref.name])?;
+                }
+                Ok(())
+            },
+        },
+        EntityFile {
+            file_name: "disks",
+            headers: vec!["machine_name", "name", "sha1", "merge", "status", "region"],
+            write_rows: |wtr, machine, _| {
+                for disk in &machine.disks {
+                    write_csv_record(
+                        wtr,
+                        &[
+                            &machine.name,
+                            &disk.name,
+                            disk.sha1.as_deref().unwrap_or(""),
+                            disk.merge.as_deref().unwrap_or(""),
+                            disk.status.as_deref().unwrap_or(""),
+                            disk.region.as_deref().unwrap_or(""),
+                        ],
+                    )?;
+                }
+                Ok(())
+            },
+        },
+        EntityFile {
+            file_name: "softwares",
+            headers: vec!["machine_name", "name"],
+            write_rows: |wtr, machine, _| {
+                for software in &machine.software_list {
+                    write_csv_record(wtr, &[&machine.name, &software.name])?;
+                }
+                Ok(())
+            },
+        },
+        EntityFile {
+            file_name: "samples",
+            headers: vec!["machine_name", "name"],
+            write_rows: |wtr, machine, _| {
+                for sample in &machine.samples {
+                    write_csv_record(wtr, &[&machine.name, &sample.name])?;
+                }
+                Ok(())
+            },
+        },
+        EntityFile {
+            file_name: "history_sections",
+            headers: vec!["machine_name", "name", "text", "order"],
+            write_rows: |wtr, machine, options| {
+                for history_section in &machine.history_sections {
+                    let text = escape_newlines(
+                        &history_section.text,
+                        options.csv_newline_placeholder.as_deref(),
+                    );
+                    write_csv_record(
+                        wtr,
+                        &[
+                            &machine.name,
+                            &history_section.name,
+                            &text,
+                            &history_section.order.to_string(),
+                        ],
+                    )?;
+                }
+                Ok(())
+            },
+        },
+        EntityFile {
+            file_name: "resources",
+            headers: vec!["machine_name", "type", "name", "size", "crc", "sha1"],
+            write_rows: |wtr, machine, options| {
+                for resource in &machine.resources {
+                    write_csv_record(
+                        wtr,
+                        &[
+                            &machine.name,
+                            &resource.type_,
+                            &options.resource_paths.name_of(resource),
+                            &resource.size.to_string(),
+                            &resource.crc,
+                            &resource.sha1,
+                        ],
+                    )?;
+                }
+                Ok(())
+            },
+        },
+        EntityFile {
+            file_name: "slots",
+            headers: vec!["machine_name", "slot_name", "name", "devname", "is_default"],
+            write_rows: write_slot_rows,
+        },
+    ]
+}
+
+/// Writes the CSV file of an entity, with a row per item of each machine.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the CSV file is created.
+/// - `entity_file`: The `EntityFile` to write.
+/// - `machines`: The machines, in the order their rows are written.
+/// - `options`: A reference to the `WriteOptions` used for the export.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` once the file is written and flushed.
+/// - On failure: Returns an error if the file cannot be created or written.
+fn write_entity_file(
+    export_path: &str,
+    entity_file: &EntityFile,
+    machines: &[&Machine],
+    options: &WriteOptions,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut wtr = create_writer(export_path, entity_file.file_name, options.bom)?;
+    write_csv_header(&mut wtr, &entity_file.headers)?;
+
+    for machine in machines {
+        (entity_file.write_rows)(&mut wtr, machine, options)?;
+    }
+
+    wtr.flush()?;
+
+    Ok(())
+}
+
+/// Writes the row of a machine to `machines.csv`.
+fn write_machine_rows(
+    wtr: &mut Writer<File>,
+    machine: &Machine,
+    options: &WriteOptions,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // Machines built by hand or deserialized may have no extended data
+    let default_extended_data = ExtendedData::default();
+    let extended_data = machine
+        .extended_data
+        .as_ref()
+        .unwrap_or(&default_extended_data);
+    let languages = machine.languages.join(", ");
+    let mut machine_record = vec![
+        &machine.name,
+        machine.display_name(),
+        machine.source_file.as_deref().unwrap_or(""),
+        machine.rom_of.as_deref().unwrap_or(""),
+        machine.clone_of.as_deref().unwrap_or(""),
+        options.bool_encoding.encode_str(machine.is_bios),
+        options.bool_encoding.encode_str(machine.is_device),
+        options.bool_encoding.encode_str(machine.runnable),
+        options.bool_encoding.encode_str(machine.is_mechanical),
+        machine.sample_of.as_deref().unwrap_or(""),
+        machine.description.as_deref().unwrap_or(""),
+        machine.year.as_deref().unwrap_or(""),
+        machine.manufacturer.as_deref().unwrap_or(""),
+        machine.driver_status.as_deref().unwrap_or(""),
+        &languages,
+        machine.players.as_deref().unwrap_or(""),
+        machine.series.as_deref().unwrap_or(""),
+        machine.category.as_deref().unwrap_or(""),
+        machine.subcategory.as_deref().unwrap_or(""),
+        options.bool_encoding.encode_str(machine.is_mature),
+        machine.version_added.as_deref().unwrap_or(""),
+        extended_data.name.as_deref().unwrap_or(""),
+        extended_data.manufacturer.as_deref().unwrap_or(""),
+        extended_data.players.as_deref().unwrap_or(""),
+        options.bool_encoding.encode_str(extended_data.is_parent),
+        extended_data.year.as_deref().unwrap_or(""),
+    ];
+    if options.debug_fields {
+        machine_record.extend([
+            machine.description.as_deref().unwrap_or(""),
+            machine.manufacturer.as_deref().unwrap_or(""),
+            machine.players.as_deref().unwrap_or(""),
+            machine.year.as_deref().unwrap_or(""),
+        ]);
+    }
+    write_csv_record(wtr, &machine_record)?;
+
+    Ok(())
+}
+
+/// Writes the rows of the ROMs of a machine to `roms.csv`.
+fn write_rom_rows(
+    wtr: &mut Writer<File>,
+    machine: &Machine,
+    _options: &WriteOptions,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    for rom in &machine.roms {
         write_csv_record(
-            &mut machines_wtr,
+            wtr,
             &[
-                name,
-                machine.source_file.as_deref().unwrap_or(""),
-                machine.rom_of.as_deref().unwrap_or(""),
-                machine.clone_of.as_deref().unwrap_or(""),
-                machine
-                    .is_bios
-                    .map(|is_bios| if is_bios { "true" } else { "false" })
-                    .unwrap_or(""),
-                machine
-                    .is_device
-                    .map(|is_device| if is_device { "true" } else { "false" })
-                    .unwrap_or(""),
-                machine
-                    .runnable
-                    .map(|runnable| if runnable { "true" } else { "false" })
-                    .unwrap_or(""),
-                machine
-                    .is_mechanical
-                    .map(|is_mechanical| if is_mechanical { "true" } else { "false" })
-                    .unwrap_or(""),
-                machine.sample_of.as_deref().unwrap_or(""),
-                machine.description.as_deref().unwrap_or(""),
-                machine.year.as_deref().unwrap_or(""),
-                machine.manufacturer.as_deref().unwrap_or(""),
-                machine.driver_status.as_deref().unwrap_or(""),
-                &machine.languages.join(", "),
-                machine.players.as_deref().unwrap_or(""),
-                machine.series.as_deref().unwrap_or(""),
-                machine.category.as_deref().unwrap_or(""),
-                machine.subcategory.as_deref().unwrap_or(""),
-                machine
-                    .is_mature
-                    .map(|is_mature| if is_mature { "true" } else { "false" })
-                    .unwrap_or(""),
-                machine
-                    .extended_data
-                    .as_ref()
-                    .unwrap()
-                    .name
-                    .as_deref()
-                    .unwrap_or(""),
-                machine
-                    .extended_data
-                    .as_ref()
-                    .unwrap()
-                    .manufacturer
-                    .as_deref()
-                    .unwrap_or(""),
-                machine
-                    .extended_data
-                    .as_ref()
-                    .unwrap()
-                    .players
-                    .as_deref()
-                    .unwrap_or(""),
-                machine
-                    .extended_data
-                    .as_ref()
-                    .unwrap()
-                    .is_parent
-                    .map(|is_parent| if is_parent { "true" } else { "false" })
-                    .unwrap_or(""),
-                machine
-                    .extended_data
-                    .as_ref()
-                    .unwrap()
-                    .year
-                    .as_deref()
-                    .unwrap_or(""),
+                &machine.name,
+                &rom.name,
+                &rom.size.to_string(),
+                rom.merge.as_deref().unwrap_or(""),
+                rom.status.as_deref().unwrap_or(""),
+                rom.crc.as_deref().unwrap_or(""),
+                rom.sha1.as_deref().unwrap_or(""),
+                rom.region.as_deref().unwrap_or(""),
+                rom.offset.as_deref().unwrap_or(""),
+                rom.bios.as_deref().unwrap_or(""),
             ],
         )?;
-        // Write roms
-        for rom in &machine.roms {
-            write_csv_record(
-                &mut roms_wtr,
-                &[
-                    name,
-                    &rom.name,
-                    &rom.size.to_string(),
-                    rom.merge.as_deref().unwrap_or(""),
-                    rom.status.as_deref().unwrap_or(""),
-                    rom.crc.as_deref().unwrap_or(""),
-                    rom.sha1.as_deref().unwrap_or(""),
-                ],
-            )?;
-        }
-        // Write bios sets
-        for bios_set in &machine.bios_sets {
-            write_csv_record(
-                &mut bios_sets_wtr,
-                &[name, &bios_set.name, &bios_set.description],
-            )?;
-        }
-        // Write device refs
-        for device_ref in &machine.device_refs {
-            write_csv_record(&mut device_refs_wtr, &[name, &device_ref.name])?;
-        }
-        // Write disks
-        for disk in &machine.disks {
-            write_csv_record(
-                &mut disks_wtr,
-                &[
-                    name,
-                    &disk.name,
-                    disk.sha1.as_deref().unwrap_or(""),
-                    disk.merge.as_deref().unwrap_or(""),
-                    disk.status.as_deref().unwrap_or(""),
-                    disk.region.as_deref().unwrap_or(""),
-                ],
-            )?;
-        }
-        // Write softwares
-        for software in &machine.software_list {
-            write_csv_record(&mut softwares_wtr, &[name, &software.name])?;
-        }
-        // Write samples
-        for sample in &machine.samples {
-            write_csv_record(&mut samples_wtr, &[name, &sample.name])?;
-        }
-        // Write history sections
-        for history_section in &machine.history_sections {
-            write_csv_record(
-                &mut history_sections_wtr,
-                &[
-                    name,
-                    &history_section.name,
-                    &history_section.text,
-                    &history_section.order.to_string(),
-                ],
-            )?;
+    }
+
+    Ok(())
+}
+
+/// Writes the rows of the slots of a machine to `slots.csv`, one per slot option.
+///
+/// Slots without options are written with empty option fields.
+fn write_slot_rows(
+    wtr: &mut Writer<File>,
+    machine: &Machine,
+    options: &WriteOptions,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    for slot in &machine.slots {
+        if slot.options.is_empty() {
+            write_csv_record(wtr, &[&machine.name, &slot.name, "", "", ""])?;
         }
-        // Write resources
-        for resource in &machine.resources {
+        for slot_option in &slot.options {
             write_csv_record(
-                &mut resources_wtr,
+                wtr,
                 &[
-                    name,
-                    &resource.type_,
-                    &resource.name,
-                    &resource.size.to_string(),
-                    &resource.crc,
-                    &resource.sha1,
+                    &machine.name,
+                    &slot.name,
+                    &slot_option.name,
+                    &slot_option.devname,
+                    options
+                        .bool_encoding
+                        .encode_str(Some(slot_option.is_default)),
                 ],
             )?;
         }
-
-        // Increase processed count
-        processed_count += 1;
-        // Progress callback
-        if processed_count % batch == 0 {
-            progress_callback(ProgressInfo {
-                progress: processed_count as u64,
-                total: total_elements as u64,
-                message: String::from(""),
-                callback_type: CallbackType::Progress,
-            });
-        }
     }
 
-    machines_wtr.flush()?;
-    roms_wtr.flush()?;
-    bios_sets_wtr.flush()?;
-    device_refs_wtr.flush()?;
-    disks_wtr.flush()?;
-    softwares_wtr.flush()?;
-    samples_wtr.flush()?;
-    history_sections_wtr.flush()?;
-    resources_wtr.flush()?;
-
-    progress_callback(get_progress_info("Adding manufacturers"));
-    export_collection(
-        get_manufacturers_list(&machines),
-        export_path,
-        "manufacturers",
-        &["name", "machines"],
-        false,
-    )?;
-
-    progress_callback(get_progress_info("Adding series"));
-    export_collection(
-        get_series_list(&machines),
-        export_path,
-        "series",
-        &["name", "machines"],
-        false,
-    )?;
-
-    progress_callback(get_progress_info("Adding languages"));
-    export_collection(
-        get_languages_list(&machines),
-        export_path,
-        "languages",
-        &["name", "machines"],
-        false,
-    )?;
-
-    progress_callback(get_progress_info("Adding players"));
-    export_collection(
-        get_players_list(&machines),
-        export_path,
-        "players",
-        &["name", "machines"],
-        false,
-    )?;
-
-    progress_callback(get_progress_info("Adding categories"));
-    export_collection(
-        get_categories_list(&machines),
-        export_path,
-        "categories",
-        &["name", "machines"],
-        false,
-    )?;
-
-    progress_callback(get_progress_info("Adding subcategories"));
-    export_collection(
-        get_subcategories_list(&machines),
-        export_path,
-        "subcategories",
-        &["category", "subcategory", "machines"],
-        true,
-    )?;
-
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: processed_count as u64,
-        message: format!("CSVs exported successfully to {}", export_path),
-        callback_type: CallbackType::Finish,
-    });
-
     Ok(())
 }
 
@@ -390,6 +523,8 @@ pub fn write_csv(
 /// # Parameters
 /// - `export_path`: A `&str` representing the directory path where the CSV file should be created.
 /// - `file_name`: A `&str` representing the base name of the CSV file (without extension) to be created.
+/// - `bom`: A `bool` indicating whether the file starts with a UTF-8 byte order mark (`EF BB BF`), which
+///   allows spreadsheet applications like Excel to detect the encoding.
 ///
 /// # Returns
 /// Returns a `Result<Writer<File>, Box<dyn Error + Send + Sync>>`:
@@ -403,13 +538,30 @@ pub fn write_csv(
 fn create_writer(
     export_path: &str,
     file_name: &str,
+    bom: bool,
 ) -> Result<Writer<File>, Box<dyn Error + Send + Sync>> {
     let file_path = format!("{}/{}.csv", export_path, file_name);
-    let file = File::create(file_path)?;
+    let mut file = File::create(file_path)?;
+    if bom {
+        file.write_all(UTF8_BOM)?;
+    }
     let writer = Writer::from_writer(file);
     Ok(writer)
 }
 
+/// Replaces the newlines of a text field with a placeholder.
+///
+/// `\r\n` line endings are replaced as a single newline. Without a placeholder the text is returned unchanged.
+fn escape_newlines<'a>(text: &'a str, placeholder: Option<&str>) -> Cow<'a, str> {
+    match placeholder {
+        Some(placeholder) if text.contains(['\n', '\r']) => Cow::Owned(
+            text.replace("\r\n", "\n")
+                .replace(['\n', '\r'], placeholder),
+        ),
+        _ => Cow::Borrowed(text),
+    }
+}
+
 /// Writes a header row to a CSV file.
 ///
 /// This function writes the provided header fields to the beginning of a CSV file using the given CSV writer.
@@ -466,6 +618,7 @@ fn write_csv_record<W: Write>(wtr: &mut Writer<W>, fields: &[&str]) -> Result<()
 /// - `file_name`: A `&str` representing the base name of the CSV file (without extension).
 /// - `headers`: A slice of `&str` containing the header fields to be written to the CSV file.
 /// - `is_subcategory`: A `bool` indicating whether the data represents subcategories (`true`) or categories (`false`).
+/// - `bom`: A `bool` indicating whether the file starts with a UTF-8 byte order mark.
 ///
 /// # Returns
 /// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
@@ -483,14 +636,12 @@ fn export_collection(
     file_name: &str,
     headers: &[&str],
     is_subcategory: bool,
+    bom: bool,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     let mut data_vec: Vec<(&String, &usize)> = data.iter().collect();
     data_vec.sort_by_key(|&(name, _)| name);
 
-    // Create the file path
-    let file_path = format!("{}/{}.csv", export_path, file_name);
-    let file = File::create(file_path)?;
-    let mut wtr = Writer::from_writer(file);
+    let mut wtr = create_writer(export_path, file_name, bom)?;
 
     // Write the header
     wtr.write_record(headers)?;
@@ -515,3 +666,173 @@ fn export_collection(
 
     Ok(())
 }
+
+/// Writes a compact index of the machines to `index.csv`.
+///
+/// The index holds only the `name`, `description`, `year` and `manufacturer` of each machine, sorted by name.
+/// Missing values are written as empty fields.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the CSV file will be created.
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after successfully writing the index.
+/// - On failure: Returns an error if there are issues creating or writing to the CSV file.
+pub(crate) fn write_csv_index(
+    export_path: &str,
+    machines: &HashMap<String, Machine>,
+    progress_callback: ProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    progress_callback(get_progress_info("Writing machines index to CSV"));
+
+    let mut wtr = create_writer(export_path, "index", false)?;
+    write_csv_header(&mut wtr, &["name", "description", "year", "manufacturer"])?;
+
+    for machine in machines_sorted(machines) {
+        write_csv_record(
+            &mut wtr,
+            &[
+                &machine.name,
+                machine.description.as_deref().unwrap_or_default(),
+                machine.year.as_deref().unwrap_or_default(),
+                machine.manufacturer.as_deref().unwrap_or_default(),
+            ],
+        )?;
+    }
+
+    wtr.flush()?;
+
+    progress_callback(
+        ProgressInfo::finish(format!("Index exported successfully to {}", export_path))
+            .with_progress(machines.len() as u64, machines.len() as u64),
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::core_models::DeviceRef;
+
+    #[test]
+    fn test_create_writer_with_bom() {
+        let export_path = std::env::temp_dir().join("mame_parser_csv_bom");
+        std::fs::create_dir_all(&export_path).unwrap();
+        let export_path_str = export_path.to_str().unwrap();
+
+        for (file_name, bom) in [("with_bom", true), ("without_bom", false)] {
+            let mut wtr = create_writer(export_path_str, file_name, bom).unwrap();
+            write_csv_header(&mut wtr, &["name", "manufacturer"]).unwrap();
+            wtr.flush().unwrap();
+        }
+
+        let with_bom = std::fs::read(export_path.join("with_bom.csv")).unwrap();
+        let without_bom = std::fs::read(export_path.join("without_bom.csv")).unwrap();
+        assert!(with_bom.starts_with(UTF8_BOM));
+        assert_eq!(&with_bom[UTF8_BOM.len()..], without_bom.as_slice());
+        assert!(without_bom.starts_with(b"name,"));
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_escape_newlines() {
+        let text = "First line\r\nSecond line\nThird line";
+
+        assert_eq!(escape_newlines(text, None), text);
+        assert_eq!(
+            escape_newlines(text, Some("\\n")),
+            "First line\\nSecond line\\nThird line"
+        );
+        assert_eq!(
+            escape_newlines(text, Some(" ")),
+            "First line Second line Third line"
+        );
+    }
+
+    #[test]
+    fn test_write_csv_without_extended_data() {
+        let export_path = std::env::temp_dir().join("mame_parser_csv_without_extended_data");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        let mut machine = Machine::builder("pacman").description("Pac-Man").build();
+        machine.extended_data = None;
+        let machines = HashMap::from([(machine.name.clone(), machine)]);
+
+        write_csv(
+            export_path.to_str().unwrap(),
+            &machines,
+            &WriteOptions::default().with_csv_threads(2),
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        let machines_csv = std::fs::read_to_string(export_path.join("machines.csv")).unwrap();
+        assert_eq!(machines_csv.lines().count(), 2);
+        assert!(machines_csv.lines().nth(1).unwrap().starts_with("pacman,"));
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_csv_threads() {
+        let export_path = std::env::temp_dir().join("mame_parser_csv_threads");
+        let machines: HashMap<String, Machine> = (0..100)
+            .map(|i| {
+                Machine::builder(format!("machine{:03}", i))
+                    .description(format!("Machine {}", i))
+                    .manufacturer("Namco")
+                    .add_device_ref(DeviceRef {
+                        name: format!("device{}", i % 7),
+                    })
+                    .build()
+            })
+            .map(|machine| (machine.name.clone(), machine))
+            .collect();
+
+        let mut exports = Vec::new();
+        for csv_threads in [1, 4] {
+            let thread_path = export_path.join(csv_threads.to_string());
+            std::fs::create_dir_all(&thread_path).unwrap();
+            let options = WriteOptions::default().with_csv_threads(csv_threads);
+            let (progress_callback, receiver) = crate::progress::channel_callback();
+            write_csv(
+                thread_path.to_str().unwrap(),
+                &machines,
+                &options,
+                progress_callback,
+            )
+            .unwrap();
+
+            // Every update, including the finish, counts the written files
+            let total_files = entity_files(&options).len() as u64 + 6;
+            let updates: Vec<ProgressInfo> = receiver
+                .try_iter()
+                .filter(|update| update.total > 0)
+                .collect();
+            assert_eq!(updates.len() as u64, total_files + 1);
+            assert!(updates.iter().all(|update| update.total == total_files));
+            assert_eq!(updates.last().unwrap().progress, total_files);
+
+            let files: Vec<Vec<u8>> = entity_files(&options)
+                .iter()
+                .map(|entity_file| {
+                    std::fs::read(thread_path.join(format!("{}.csv", entity_file.file_name)))
+                        .unwrap()
+                })
+                .collect();
+            exports.push(files);
+        }
+
+        assert_eq!(exports[0], exports[1]);
+        let device_refs = String::from_utf8(exports[0][3].clone()).unwrap();
+        assert_eq!(device_refs.lines().count(), 101);
+        assert!(device_refs.starts_with("machine_name,name\nmachine000,device0\n"));
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+}
@@ -1,15 +1,21 @@
 use crate::{
+    core::data_cleanup::name_normalization::{parse_year, YearValue},
     core::models::collections_helper::{
         get_categories_list, get_languages_list, get_manufacturers_list, get_players_list,
         get_series_list, get_subcategories_list,
     },
     helpers::callback_progress_helper::get_progress_info,
-    models::Machine,
-    progress::{CallbackType, ProgressCallback, ProgressInfo},
+    models::{
+        BiosSet, Disk, ExtendedData, HistorySection, Machine, Resource, ResourcePaths, Rom, Slot,
+        SlotOption, WriteOptions,
+    },
+    progress::{ProgressCallback, ProgressInfo},
+    queries::machines_sorted,
 };
-use serde_json::{json, to_writer_pretty};
+use serde::Serialize;
+use serde_json::json;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     error::Error,
     fs::File,
     io::{BufWriter, Write},
@@ -25,6 +31,7 @@ use std::{
 /// - `export_path`: A `&str` representing the directory path where the JSON files will be exported.
 /// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
 ///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `options`: A reference to the `WriteOptions` used for the export (e.g., `pretty` to pretty-print the JSON files).
 /// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the JSON writing process.
 ///   The callback receives a `ProgressInfo` struct containing fields like `progress`, `total`, `message`, and `callback_type`.
 ///
@@ -42,6 +49,8 @@ use std::{
 /// # JSON Files Created
 /// This function creates the following JSON files:
 /// - `machines.json`: Contains the main machine data, including metadata like name, source file, manufacturer, etc.
+///   When `options.shards` is greater than 1, the machines are split into `machines_0.json`, `machines_1.json`, etc.,
+///   and `machines_manifest.json` maps each machine name to the index of its shard.
 /// - `manufacturers.json`: Contains a list of manufacturers and the machines associated with them.
 /// - `series.json`: Contains a list of game series and the machines associated with each series.
 /// - `languages.json`: Contains a list of languages and the machines available in each language.
@@ -51,6 +60,7 @@ use std::{
 pub fn write_json(
     export_path: &str,
     machines: &HashMap<String, Machine>,
+    options: &WriteOptions,
     progress_callback: ProgressCallback,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     // If the machines were not loaded, return an error
@@ -60,7 +70,7 @@ pub fn write_json(
 
     let total_elements = machines.len();
 
-    export_machines_to_json(export_path, &machines, &progress_callback)?;
+    export_machines_to_json(export_path, &machines, options, &progress_callback)?;
 
     // Export additional collections to separate JSON files
     progress_callback(get_progress_info("Adding manufacturers"));
@@ -69,10 +79,17 @@ pub fn write_json(
         export_path,
         "manufacturers",
         false,
+        options.pretty,
     )?;
 
     progress_callback(get_progress_info("Adding series"));
-    export_collection_to_json(get_series_list(&machines), export_path, "series", false)?;
+    export_collection_to_json(
+        get_series_list(&machines),
+        export_path,
+        "series",
+        false,
+        options.pretty,
+    )?;
 
     progress_callback(get_progress_info("Adding languages"));
     export_collection_to_json(
@@ -80,10 +97,17 @@ pub fn write_json(
         export_path,
         "languages",
         false,
+        options.pretty,
     )?;
 
     progress_callback(get_progress_info("Adding players"));
-    export_collection_to_json(get_players_list(&machines), export_path, "players", false)?;
+    export_collection_to_json(
+        get_players_list(&machines),
+        export_path,
+        "players",
+        false,
+        options.pretty,
+    )?;
 
     progress_callback(get_progress_info("Adding categories"));
     export_collection_to_json(
@@ -91,6 +115,7 @@ pub fn write_json(
         export_path,
         "categories",
         false,
+        options.pretty,
     )?;
 
     progress_callback(get_progress_info("Adding subcategories"));
@@ -99,14 +124,13 @@ pub fn write_json(
         export_path,
         "subcategories",
         true,
+        options.pretty,
     )?;
 
-    progress_callback(ProgressInfo {
-        progress: total_elements as u64,
-        total: total_elements as u64,
-        message: format!("Json exported successfully to {}", export_path),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("Json exported successfully to {}", export_path))
+            .with_progress(total_elements as u64, total_elements as u64),
+    );
 
     Ok(())
 }
@@ -115,12 +139,18 @@ pub fn write_json(
 ///
 /// This function exports the contents of a `HashMap` of `Machine` data to a JSON file named `machines.json`.
 /// The machines are sorted by name, and each machine's metadata is formatted into a JSON object.
+/// When more than one shard is requested, the machines are split across `machines_<shard>.json` files
+/// by hashing their names, and a `machines_manifest.json` file maps each machine name to its shard.
+/// With `nested_clones`, only the parents and the orphaned clones are written at the top level, and the clones
+/// of each parent, including the clones of its clones, are written to its `clones` array, in the shard of their parent.
 /// The function uses a buffered writer to optimize file writing and provides progress updates via a callback function.
 ///
 /// # Parameters
 /// - `export_path`: A `&str` representing the directory path where the `machines.json` file will be created.
 /// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
 ///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `options`: A reference to the `WriteOptions`; `pretty`, `shards`, `resource_paths`, `debug_fields` and
+///   `nested_clones` apply to the machines. `0` and `1` shards write a single `machines.json` file.
 /// - `progress_callback`: A reference to a callback function of type `ProgressCallback` that provides progress updates during the JSON writing process.
 ///   The callback receives a `ProgressInfo` struct containing fields like `progress`, `total`, `message`, and `callback_type`.
 ///
@@ -138,118 +168,392 @@ pub fn write_json(
 /// # JSON Structure
 /// The `machines.json` file contains an array of JSON objects, where each object represents a machine and includes:
 /// - Basic metadata: name, source file, manufacturer, etc.
-/// - Associated collections: BIOS sets, ROMs, device references, software, samples, history sections, and resources.
+/// - Associated collections: BIOS sets, ROMs, device references, software, samples, history sections, resources, and slots.
 /// - Extended data: additional normalized fields such as name, manufacturer, players, parent status, and year.
 fn export_machines_to_json(
     export_path: &str,
     machines: &HashMap<String, Machine>,
+    options: &WriteOptions,
     progress_callback: &ProgressCallback,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     progress_callback(get_progress_info("Writing machines to JSON"));
 
-    let mut machine_names: Vec<&String> = machines.keys().collect();
-    machine_names.sort_unstable();
+    let pretty = options.pretty;
+    let shards = options.shards.max(1);
+    let to_json =
+        |machine: &Machine| machine_to_json(machine, options.resource_paths, options.debug_fields);
 
-    let file = File::create(format!("{}/machines.json", export_path))?;
-    let mut writer = BufWriter::new(file);
-    writer.write_all(b"[\n")?;
+    // Clones of a machine present in the dataset, written nested under it
+    let mut clones: HashMap<&str, Vec<&Machine>> = HashMap::new();
+    if options.nested_clones {
+        for machine in machines_sorted(machines) {
+            if let Some(parent) = nested_parent(machines, machine) {
+                clones.entry(parent).or_default().push(machine);
+            }
+        }
+    }
+
+    let mut writers = Vec::with_capacity(shards);
+    for shard in 0..shards {
+        let file_name = if shards == 1 {
+            "machines".to_string()
+        } else {
+            format!("machines_{}", shard)
+        };
+        writers.push(BufWriter::new(create_json_writer(export_path, &file_name)?));
+    }
+    let mut is_empty = vec![true; shards];
+    let mut manifest: BTreeMap<&str, usize> = BTreeMap::new();
+
+    let separator: &[u8] = if pretty { b"\n" } else { b"" };
+    for writer in writers.iter_mut() {
+        writer.write_all(b"[")?;
+        writer.write_all(separator)?;
+    }
 
     let total_elements = machines.len();
     let batch = std::cmp::max(total_elements / 30, 1);
 
-    for (i, &name) in machine_names.iter().enumerate() {
-        let machine = machines.get(name).unwrap(); // Get the machine by name
+    for (i, machine) in machines_sorted(machines).into_iter().enumerate() {
+        if options.nested_clones && nested_parent(machines, machine).is_some() {
+            continue;
+        }
 
-        if i > 0 {
-            writer.write_all(b",\n")?;
+        let shard = shard_of(&machine.name, shards);
+        let writer = &mut writers[shard];
+        if !is_empty[shard] {
+            writer.write_all(b",")?;
+            writer.write_all(separator)?;
         }
+        is_empty[shard] = false;
+        manifest.insert(&machine.name, shard);
 
-        to_writer_pretty(
-            &mut writer,
-            &json!({
-                "name": machine.name,
-                "source_file": machine.source_file,
-                "rom_of": machine.rom_of,
-                "clone_of": machine.clone_of,
-                "is_bios": machine.is_bios,
-                "is_device": machine.is_device,
-                "runnable": machine.runnable,
-                "is_mechanical": machine.is_mechanical,
-                "sample_of": machine.sample_of,
-                "description": machine.description,
-                "year": machine.year,
-                "manufacturer": machine.manufacturer,
-                "bios_sets": machine.bios_sets.iter().map(|bs| json!({
-                    "name": bs.name,
-                    "description": bs.description,
-                })).collect::<Vec<_>>(),
-                "roms": machine.roms.iter().map(|rom| json!({
-                    "name": rom.name,
-                    "size": rom.size,
-                    "merge": rom.merge,
-                    "status": rom.status,
-                    "crc": rom.crc,
-                    "sha1": rom.sha1,
-                })).collect::<Vec<_>>(),
-                "device_refs": machine.device_refs.iter().map(|dr| dr.name.clone()).collect::<Vec<_>>(),
-                "software_list": machine.software_list.iter().map(|sw| sw.name.clone()).collect::<Vec<_>>(),
-                "samples": machine.samples.iter().map(|sample| sample.name.clone()).collect::<Vec<_>>(),
-                "driver_status": machine.driver_status,
-                "languages": machine.languages,
-                "players": machine.players,
-                "series": machine.series,
-                "category": machine.category,
-                "subcategory": machine.subcategory,
-                "is_mature": machine.is_mature,
-                "history_sections": machine.history_sections.iter().map(|hs| json!({
-                    "order": hs.order,
-                    "name": hs.name,
-                    "text": hs.text,
-                })).collect::<Vec<_>>(),
-                "disks": machine.disks.iter().map(|disk| json!({
-                    "name": disk.name,
-                    "sha1": disk.sha1,
-                    "merge": disk.merge,
-                    "status": disk.status,
-                    "region": disk.region,
-                })).collect::<Vec<_>>(),
-                "extended_data": machine.extended_data.as_ref().map(|ext| json!({
-                    "name": ext.name,
-                    "manufacturer": ext.manufacturer,
-                    "players": ext.players.as_deref().unwrap_or("")
-                    .split(',')
-                    .map(|s| s.trim().to_string())
-                    .collect::<Vec<_>>(),
-                    "is_parent": ext.is_parent,
-                    "year": ext.year,
-                })),
-                "resources": machine.resources.iter().map(|res| json!({
-                    "type_": res.type_,
-                    "name": res.name,
-                    "size": res.size,
-                    "crc": res.crc,
-                    "sha1": res.sha1,
-                })).collect::<Vec<_>>(),
-            }),
-        )?;
+        let mut value = to_json(machine);
+        if options.nested_clones {
+            let machine_clones = clones.get(machine.name.as_str());
+            for clone in machine_clones.into_iter().flatten() {
+                manifest.insert(&clone.name, shard);
+            }
+            value["clones"] = machine_clones
+                .into_iter()
+                .flatten()
+                .map(|clone| to_json(clone))
+                .collect();
+        }
+
+        write_json_value(&mut *writer, &value, pretty)?;
 
         // Progress callback
         if (i + 1) % batch == 0 {
-            progress_callback(ProgressInfo {
-                progress: (i + 1) as u64,
-                total: total_elements as u64,
-                message: String::from(""),
-                callback_type: CallbackType::Progress,
-            });
+            progress_callback(ProgressInfo::progress(
+                (i + 1) as u64,
+                total_elements as u64,
+            ));
         }
     }
 
-    writer.write_all(b"\n]")?;
-    writer.flush()?;
+    for writer in writers.iter_mut() {
+        writer.write_all(separator)?;
+        writer.write_all(b"]")?;
+        writer.flush()?;
+    }
+
+    if shards > 1 {
+        let mut writer = BufWriter::new(create_json_writer(export_path, "machines_manifest")?);
+        write_json_value(&mut writer, &manifest, pretty)?;
+        writer.flush()?;
+    }
 
     Ok(())
 }
 
+/// Gets the top-level parent a clone is nested under with the `nested_clones` option.
+///
+/// The `clone_of` references are followed up to a machine that is not itself a clone of an exported machine,
+/// so the clones of a clone are nested under the same top-level parent instead of being dropped.
+///
+/// # Parameters
+/// - `machines`: A reference to the `HashMap` with all the exported machines.
+/// - `machine`: A reference to the `Machine` whose parent is searched.
+///
+/// # Returns
+/// Returns the name of the top-level parent if the `clone_of` machine is present in `machines`, or `None` for parents,
+/// orphaned clones and clones whose `clone_of` references form a cycle, which are written at the top level.
+fn nested_parent<'a>(machines: &'a HashMap<String, Machine>, machine: &Machine) -> Option<&'a str> {
+    let mut visited: HashSet<&str> = HashSet::from([machine.name.as_str()]);
+    let mut top_parent: Option<&'a str> = None;
+    let mut current = machine;
+
+    while let Some((parent_name, parent)) = current
+        .clone_of
+        .as_deref()
+        .and_then(|parent| machines.get_key_value(parent))
+    {
+        if !visited.insert(parent_name.as_str()) {
+            return None;
+        }
+        top_parent = Some(parent_name.as_str());
+        current = parent;
+    }
+
+    top_parent
+}
+
+/// Finds the shard a machine is written to.
+///
+/// The machine name is hashed with FNV-1a, which, unlike the standard library hasher, gives
+/// the same result across Rust versions and platforms, so a machine stays in the same shard between exports.
+///
+/// # Parameters
+/// - `name`: The name of the machine.
+/// - `shards`: The number of shards, greater than 0.
+///
+/// # Returns
+/// Returns the index of the shard, between `0` and `shards - 1`.
+fn shard_of(name: &str, shards: usize) -> usize {
+    let hash = name.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    (hash % shards as u64) as usize
+}
+
+/// Converts the machines to the JSON array written to the `machines.json` file, in memory.
+///
+/// The machines are sorted by name and converted with the same structure as `write_json`, with the resource
+/// names in their raw form. This allows to embed the machines in a larger JSON document, or to manipulate them
+/// before serializing them once.
+///
+/// # Parameters
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing the machines to be converted.
+///
+/// # Returns
+/// Returns a `serde_json::Value` with an array of JSON objects, one per machine.
+pub fn machines_to_value(machines: &HashMap<String, Machine>) -> serde_json::Value {
+    machines_sorted(machines)
+        .into_iter()
+        .map(|machine| machine_to_json(machine, ResourcePaths::Raw, false))
+        .collect()
+}
+
+/// Converts a machine to the JSON object written to the `machines.json` file.
+///
+/// # Parameters
+/// - `machine`: A reference to the `Machine` to be converted.
+/// - `resource_paths`: The `ResourcePaths` form in which the resource names are written.
+/// - `debug_fields`: A `bool` indicating whether the raw values the extended data was normalized from are
+///   added to it, as `name_raw`, `manufacturer_raw`, `players_raw` and `year_raw`.
+///
+/// # Returns
+/// Returns a `serde_json::Value` with the metadata of the machine, its associated collections, and its extended data.
+fn machine_to_json(
+    machine: &Machine,
+    resource_paths: ResourcePaths,
+    debug_fields: bool,
+) -> serde_json::Value {
+    let mut fields = machine_fields_to_json(machine);
+    fields.insert(
+        "bios_sets".to_string(),
+        machine.bios_sets.iter().map(bios_set_to_json).collect(),
+    );
+    fields.insert(
+        "roms".to_string(),
+        machine.roms.iter().map(rom_to_json).collect(),
+    );
+    fields.insert(
+        "device_refs".to_string(),
+        json!(machine
+            .device_refs
+            .iter()
+            .map(|dr| dr.name.clone())
+            .collect::<Vec<_>>()),
+    );
+    fields.insert(
+        "software_list".to_string(),
+        json!(machine
+            .software_list
+            .iter()
+            .map(|sw| sw.name.clone())
+            .collect::<Vec<_>>()),
+    );
+    fields.insert(
+        "samples".to_string(),
+        json!(machine
+            .samples
+            .iter()
+            .map(|sample| sample.name.clone())
+            .collect::<Vec<_>>()),
+    );
+    fields.insert(
+        "history_sections".to_string(),
+        machine
+            .history_sections
+            .iter()
+            .map(history_section_to_json)
+            .collect(),
+    );
+    fields.insert(
+        "disks".to_string(),
+        machine.disks.iter().map(disk_to_json).collect(),
+    );
+    fields.insert(
+        "extended_data".to_string(),
+        json!(machine.extended_data.as_ref().map(|extended_data| {
+            let mut extended_data = extended_data_to_json(extended_data);
+            if debug_fields {
+                extended_data["name_raw"] = json!(machine.description);
+                extended_data["manufacturer_raw"] = json!(machine.manufacturer);
+                extended_data["players_raw"] = json!(machine.players);
+                extended_data["year_raw"] = json!(machine.year);
+            }
+            extended_data
+        })),
+    );
+    fields.insert(
+        "resources".to_string(),
+        machine
+            .resources
+            .iter()
+            .map(|res| resource_to_json(res, resource_paths))
+            .collect(),
+    );
+    fields.insert(
+        "slots".to_string(),
+        machine.slots.iter().map(slot_to_json).collect(),
+    );
+
+    serde_json::Value::Object(fields)
+}
+
+/// Converts the scalar fields of a machine to a JSON object, without its associated collections.
+///
+/// # Parameters
+/// - `machine`: A reference to the `Machine` to be converted.
+///
+/// # Returns
+/// Returns a `serde_json::Map` with the metadata of the machine.
+fn machine_fields_to_json(machine: &Machine) -> serde_json::Map<String, serde_json::Value> {
+    let fields = json!({
+        "name": machine.name,
+        "display_name": machine.display_name(),
+        "source_file": machine.source_file,
+        "rom_of": machine.rom_of,
+        "clone_of": machine.clone_of,
+        "is_bios": machine.is_bios,
+        "is_device": machine.is_device,
+        "runnable": machine.runnable,
+        "is_mechanical": machine.is_mechanical,
+        "sample_of": machine.sample_of,
+        "description": machine.description,
+        "year": machine.year,
+        "manufacturer": machine.manufacturer,
+        "driver_status": machine.driver_status,
+        "languages": machine.languages,
+        "players": machine.players,
+        "series": machine.series,
+        "category": machine.category,
+        "subcategory": machine.subcategory,
+        "is_mature": machine.is_mature,
+        "version_added": machine.version_added,
+    });
+
+    into_fields(fields)
+}
+
+/// Returns the fields of a JSON object built with `json!`.
+fn into_fields(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    match value {
+        serde_json::Value::Object(fields) => fields,
+        _ => unreachable!("json! of an object literal is always an object"),
+    }
+}
+
+/// Converts a BIOS set to a JSON object.
+fn bios_set_to_json(bios_set: &BiosSet) -> serde_json::Value {
+    json!({
+        "name": bios_set.name,
+        "description": bios_set.description,
+    })
+}
+
+/// Converts a ROM to a JSON object.
+fn rom_to_json(rom: &Rom) -> serde_json::Value {
+    json!({
+        "name": rom.name,
+        "size": rom.size,
+        "merge": rom.merge,
+        "status": rom.status,
+        "crc": rom.crc,
+        "sha1": rom.sha1,
+        "region": rom.region,
+        "offset": rom.offset,
+        "bios": rom.bios,
+    })
+}
+
+/// Converts a history section to a JSON object.
+fn history_section_to_json(history_section: &HistorySection) -> serde_json::Value {
+    json!({
+        "order": history_section.order,
+        "name": history_section.name,
+        "text": history_section.text,
+    })
+}
+
+/// Converts a disk to a JSON object.
+fn disk_to_json(disk: &Disk) -> serde_json::Value {
+    json!({
+        "name": disk.name,
+        "sha1": disk.sha1,
+        "merge": disk.merge,
+        "status": disk.status,
+        "region": disk.region,
+    })
+}
+
+/// Converts a slot to a JSON object, with its options nested.
+fn slot_to_json(slot: &Slot) -> serde_json::Value {
+    json!({
+        "name": slot.name,
+        "options": slot.options.iter().map(slot_option_to_json).collect::<Vec<_>>(),
+    })
+}
+
+/// Converts a slot option to a JSON object.
+fn slot_option_to_json(slot_option: &SlotOption) -> serde_json::Value {
+    json!({
+        "name": slot_option.name,
+        "devname": slot_option.devname,
+        "is_default": slot_option.is_default,
+    })
+}
+
+/// Converts the extended data of a machine to a JSON object, with the players split into a list.
+fn extended_data_to_json(extended_data: &ExtendedData) -> serde_json::Value {
+    json!({
+        "name": extended_data.name,
+        "manufacturer": extended_data.manufacturer,
+        "players": extended_data.players.as_deref().unwrap_or("")
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .collect::<Vec<_>>(),
+        "is_parent": extended_data.is_parent,
+        "year": extended_data.year,
+        "max_players": extended_data.max_players,
+        "players_simultaneous": extended_data.players_simultaneous,
+    })
+}
+
+/// Converts a resource to a JSON object, writing its name in the given `ResourcePaths` form.
+fn resource_to_json(resource: &Resource, resource_paths: ResourcePaths) -> serde_json::Value {
+    json!({
+        "type_": resource.type_,
+        "name": resource_paths.name_of(resource),
+        "size": resource.size,
+        "crc": resource.crc,
+        "sha1": resource.sha1,
+    })
+}
+
 /// Creates a file for writing JSON data.
 ///
 /// This function creates a file with the specified name in the given export path, which will be used for writing JSON data.
@@ -277,6 +581,29 @@ fn create_json_writer(
     Ok(file)
 }
 
+/// Serializes a value as JSON to the given writer.
+///
+/// # Parameters
+/// - `writer`: The writer where the JSON data will be written.
+/// - `value`: The value to be serialized.
+/// - `pretty`: A `bool` indicating whether the value is pretty-printed (`true`) or written in compact form (`false`).
+///
+/// # Returns
+/// Returns a `Result<(), serde_json::Error>`:
+/// - On success: Returns `Ok(())` after successfully writing the value.
+/// - On failure: Returns an error if the value cannot be serialized or written.
+fn write_json_value<W: Write, T: Serialize + ?Sized>(
+    writer: W,
+    value: &T,
+    pretty: bool,
+) -> Result<(), serde_json::Error> {
+    if pretty {
+        serde_json::to_writer_pretty(writer, value)
+    } else {
+        serde_json::to_writer(writer, value)
+    }
+}
+
 /// Exports a collection of data to a JSON file.
 ///
 /// This function exports a `HashMap` containing data entries and their associated counts to a JSON file.
@@ -288,6 +615,7 @@ fn create_json_writer(
 /// - `export_path`: A `&str` representing the directory path where the JSON file will be created.
 /// - `file_name`: A `&str` representing the base name of the JSON file (without extension).
 /// - `is_subcategory`: A `bool` indicating whether the data represents subcategories (`true`) or categories (`false`).
+/// - `pretty`: A `bool` indicating whether the data is pretty-printed (`true`) or written in compact form (`false`).
 ///
 /// # Returns
 /// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
@@ -309,15 +637,38 @@ fn export_collection_to_json(
     export_path: &str,
     file_name: &str,
     is_subcategory: bool,
+    pretty: bool,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut wtr = create_json_writer(export_path, file_name)?;
+    let json_data = collection_to_json(data, is_subcategory);
+
+    // Write the data
+    write_json_value(&mut wtr, &json_data, pretty)?;
+    wtr.flush()?;
+
+    Ok(())
+}
+
+/// Converts a collection of data to a sorted vector of JSON objects.
+///
+/// # Parameters
+/// - `data`: A `HashMap<String, usize>` where the key represents the name (category or subcategory), and the value is the count associated with that name.
+/// - `is_subcategory`: A `bool` indicating whether the data represents subcategories (`true`) or categories (`false`).
+///
+/// # Returns
+/// Returns a `Vec<serde_json::Value>` sorted by name:
+/// - If `is_subcategory` is `true`, each object includes a "category", "subcategory", and the associated "machines" count.
+/// - If `is_subcategory` is `false`, each object includes a "name" and the associated "machines" count.
+fn collection_to_json(
+    data: HashMap<String, usize>,
+    is_subcategory: bool,
+) -> Vec<serde_json::Value> {
     let mut data_vec: Vec<(&String, &usize)> = data.iter().collect();
     data_vec.sort_by_key(|&(name, _)| name);
 
-    let mut wtr = create_json_writer(export_path, file_name)?;
-    let json_data: Vec<_>;
     // Convert the data to a vector of JSON objects
     if is_subcategory {
-        json_data = data_vec
+        data_vec
             .into_iter()
             .map(|(name, machines)| {
                 let splitted: Vec<&str> = name.split(" - ").collect();
@@ -329,9 +680,9 @@ fn export_collection_to_json(
                     "machines": machines,
                 })
             })
-            .collect();
+            .collect()
     } else {
-        json_data = data_vec
+        data_vec
             .into_iter()
             .map(|(name, machines)| {
                 json!({
@@ -339,12 +690,996 @@ fn export_collection_to_json(
                     "machines": machines,
                 })
             })
-            .collect();
+            .collect()
     }
+}
 
-    // Write the data
-    serde_json::to_writer_pretty(&mut wtr, &json_data)?;
+/// Writes all the collections of the machines to a single JSON file.
+///
+/// This function bundles the manufacturers, series, categories, subcategories, languages and players
+/// collections, which `write_json` exports to separate files, into a single `collections.json` file.
+/// This allows fetching all the aggregated lists at once.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the `collections.json` file will be created.
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
+///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after successfully writing the `collections.json` file.
+/// - On failure: Returns an error if there are issues creating or writing to the JSON file.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The `machines` HashMap is empty, indicating that there is no data to write.
+/// - There are any I/O errors when creating or writing to the JSON file.
+///
+/// # JSON Structure
+/// The `collections.json` file contains an object with the `manufacturers`, `series`, `categories`, `subcategories`,
+/// `languages` and `players` keys. Each key holds the same array of JSON objects as its separate JSON file.
+pub fn write_collections_json(
+    export_path: &str,
+    machines: &HashMap<String, Machine>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // If the machines were not loaded, return an error
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    let collections = json!({
+        "manufacturers": collection_to_json(get_manufacturers_list(machines), false),
+        "series": collection_to_json(get_series_list(machines), false),
+        "categories": collection_to_json(get_categories_list(machines), false),
+        "subcategories": collection_to_json(get_subcategories_list(machines), true),
+        "languages": collection_to_json(get_languages_list(machines), false),
+        "players": collection_to_json(get_players_list(machines), false),
+    });
+
+    let mut wtr = BufWriter::new(create_json_writer(export_path, "collections")?);
+    write_json_value(&mut wtr, &collections, true)?;
+    wtr.flush()?;
+
+    Ok(())
+}
+
+/// Writes chart-ready breakdowns of the machines to a `charts.json` file.
+///
+/// This function counts the machines by category, manufacturer and year, and writes each breakdown as an array of
+/// `{ "label", "value" }` objects that chart libraries can use directly, e.g. for a dashboard pie chart.
+/// The manufacturers are limited to the `top_n` with the most machines, the others being summed into an `Other` entry.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the `charts.json` file will be created.
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
+///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `top_n`: The number of manufacturers kept before the `Other` entry.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after successfully writing the `charts.json` file.
+/// - On failure: Returns an error if there are issues creating or writing to the JSON file.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The `machines` HashMap is empty, indicating that there is no data to write.
+/// - There are any I/O errors when creating or writing to the JSON file.
+///
+/// # JSON Structure
+/// The `charts.json` file contains an object with the `categories`, `manufacturers` and `years` keys.
+/// Each key holds an array of `{ "label", "value" }` objects sorted by descending value, then by label.
+/// The manufacturers and years are the normalized ones of the extended data; machines without a value are not counted.
+pub fn write_charts_json(
+    export_path: &str,
+    machines: &HashMap<String, Machine>,
+    top_n: usize,
+    progress_callback: ProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // If the machines were not loaded, return an error
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    progress_callback(get_progress_info("Writing charts to JSON"));
+
+    let mut years: HashMap<String, usize> = HashMap::new();
+    for year in machines
+        .values()
+        .filter_map(|machine| machine.extended_data.as_ref()?.year.as_ref())
+    {
+        *years.entry(year.clone()).or_default() += 1;
+    }
+
+    let charts = json!({
+        "categories": chart_to_json(get_categories_list(machines), None),
+        "manufacturers": chart_to_json(get_manufacturers_list(machines), Some(top_n)),
+        "years": chart_to_json(years, None),
+    });
+
+    let mut wtr = BufWriter::new(create_json_writer(export_path, "charts")?);
+    write_json_value(&mut wtr, &charts, true)?;
     wtr.flush()?;
 
+    progress_callback(ProgressInfo::finish(format!(
+        "Charts exported successfully to {}",
+        export_path
+    )));
+
     Ok(())
 }
+
+/// Converts counts to a chart-ready array of `{ "label", "value" }` objects, sorted by descending value, then by label.
+///
+/// When `top_n` is given and there are more entries, only the first `top_n` are kept, and the values
+/// of the others are summed into a final `Other` entry.
+fn chart_to_json(counts: HashMap<String, usize>, top_n: Option<usize>) -> Vec<serde_json::Value> {
+    let mut entries: Vec<(String, usize)> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let other = match top_n {
+        Some(top_n) if entries.len() > top_n => Some(
+            entries
+                .split_off(top_n)
+                .iter()
+                .map(|(_, value)| value)
+                .sum::<usize>(),
+        ),
+        _ => None,
+    };
+
+    entries
+        .into_iter()
+        .chain(other.map(|value| ("Other".to_string(), value)))
+        .map(|(label, value)| json!({ "label": label, "value": value }))
+        .collect()
+}
+
+/// Writes the machines to one JSON file per decade.
+///
+/// This function buckets the machines by the decade of their normalized year, and writes each bucket to a
+/// file named after the decade (e.g. `1980s.json`). Machines with an unknown year are written to `unknown.json`.
+/// Years are interpreted with `parse_year`, so a year known to the decade (e.g. `198?`) is written to its decade.
+/// A `decades.json` index lists every decade with its number of machines.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the JSON files will be created.
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
+///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the JSON writing process.
+///   The callback receives a `ProgressInfo` struct containing fields like `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after successfully writing the decade files and the index.
+/// - On failure: Returns an error if there are issues creating or writing to the JSON files.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The `machines` HashMap is empty, indicating that there is no data to write.
+/// - There are any I/O errors when creating or writing to the JSON files.
+///
+/// # JSON Structure
+/// - Each decade file contains an array of machines sorted by name, with the same structure as `machines.json`.
+/// - The `decades.json` index contains an array of objects with the `name` of the decade and its number of `machines`.
+pub fn write_json_by_decade(
+    export_path: &str,
+    machines: &HashMap<String, Machine>,
+    progress_callback: ProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // If the machines were not loaded, return an error
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    progress_callback(get_progress_info("Writing machines by decade to JSON"));
+
+    let mut decades: BTreeMap<String, Vec<serde_json::Value>> = BTreeMap::new();
+    for machine in machines_sorted(machines) {
+        decades
+            .entry(decade_of(machine))
+            .or_default()
+            .push(machine_to_json(machine, ResourcePaths::Raw, false));
+    }
+
+    let total_elements = decades.len();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for (i, (decade, decade_machines)) in decades.iter().enumerate() {
+        let mut wtr = BufWriter::new(create_json_writer(export_path, decade)?);
+        write_json_value(&mut wtr, decade_machines, true)?;
+        wtr.flush()?;
+
+        index.insert(decade.clone(), decade_machines.len());
+
+        progress_callback(ProgressInfo::progress(
+            (i + 1) as u64,
+            total_elements as u64,
+        ));
+    }
+
+    let mut wtr = BufWriter::new(create_json_writer(export_path, "decades")?);
+    write_json_value(&mut wtr, &collection_to_json(index, false), true)?;
+    wtr.flush()?;
+
+    progress_callback(
+        ProgressInfo::finish(format!(
+            "Json by decade exported successfully to {}",
+            export_path
+        ))
+        .with_progress(total_elements as u64, total_elements as u64),
+    );
+
+    Ok(())
+}
+
+/// Gets the decade of a machine from its year, falling back to its normalized year.
+///
+/// # Parameters
+/// - `machine`: A reference to the `Machine` to get the decade from.
+///
+/// # Returns
+/// Returns a `String` with the decade (e.g. `1980s`) of the year, of the first year of a range, or of a year
+/// known to the decade (e.g. `198?`), or `unknown` if the year is unknown.
+fn decade_of(machine: &Machine) -> String {
+    let year = match parse_year(machine.year.as_deref().unwrap_or("")) {
+        YearValue::Unknown => machine
+            .extended_data
+            .as_ref()
+            .and_then(|extended_data| extended_data.year.as_deref())
+            .map_or(YearValue::Unknown, parse_year),
+        year => year,
+    };
+
+    match year {
+        YearValue::Exact(year) | YearValue::Range(year, _) | YearValue::Decade(year) => {
+            format!("{}s", year / 10 * 10)
+        }
+        YearValue::Unknown => "unknown".to_string(),
+    }
+}
+
+/// Writes a manifest of the machine resources deduplicated by checksum.
+///
+/// Identical resource files are often shared by several machines (e.g., the same artwork for a parent and its clones).
+/// This function groups every resource by its SHA-1 hash and exports a `resources_dedup.json` file where each unique
+/// file appears once, together with the list of machines and resource names pointing at it.
+/// Resources without a SHA-1 hash are skipped. Progress updates are provided through a callback function.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the `resources_dedup.json` file will be created.
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
+///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the writing process.
+///   The callback receives a `ProgressInfo` struct containing fields like `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after successfully writing the manifest to the specified `export_path`.
+/// - On failure: Returns an error if there are issues creating or writing to the JSON file.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The `machines` HashMap is empty, indicating that there is no data to write.
+/// - There are any I/O errors when creating or writing to the JSON file.
+///
+/// # JSON Structure
+/// The `resources_dedup.json` file contains an object keyed by SHA-1 hash, sorted by hash, where each value includes:
+/// - `size`: The size of the resource file in bytes.
+/// - `crc`: The CRC32 hash of the resource file.
+/// - `usages`: An array of objects with the `machine`, `name` and `type` of every resource pointing at the file.
+pub fn write_resource_manifest(
+    export_path: &str,
+    machines: &HashMap<String, Machine>,
+    progress_callback: ProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // If the machines were not loaded, return an error
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    progress_callback(get_progress_info("Writing resources manifest to JSON"));
+
+    let total_elements = machines.len();
+    let batch = std::cmp::max(total_elements / 30, 1);
+
+    let mut manifest: BTreeMap<&str, serde_json::Value> = BTreeMap::new();
+
+    for (i, machine) in machines_sorted(machines).into_iter().enumerate() {
+        for resource in machine.resources.iter().filter(|res| !res.sha1.is_empty()) {
+            let entry = manifest.entry(&resource.sha1).or_insert_with(|| {
+                json!({
+                    "size": resource.size,
+                    "crc": resource.crc,
+                    "usages": [],
+                })
+            });
+            entry["usages"].as_array_mut().unwrap().push(json!({
+                "machine": machine.name,
+                "name": resource.name,
+                "type": resource.type_,
+            }));
+        }
+
+        // Progress callback
+        if (i + 1) % batch == 0 {
+            progress_callback(ProgressInfo::progress(
+                (i + 1) as u64,
+                total_elements as u64,
+            ));
+        }
+    }
+
+    let mut wtr = BufWriter::new(create_json_writer(export_path, "resources_dedup")?);
+    serde_json::to_writer_pretty(&mut wtr, &manifest)?;
+    wtr.flush()?;
+
+    progress_callback(
+        ProgressInfo::finish(format!(
+            "Resources manifest exported successfully to {}",
+            export_path
+        ))
+        .with_progress(total_elements as u64, total_elements as u64),
+    );
+
+    Ok(())
+}
+
+/// Writes the machines to one JSON file per table, with the same layout as the SQLite export.
+///
+/// Instead of nesting the associated collections in each machine, this function writes them to their own
+/// files, which suits GraphQL and relational consumers. The machines are written sorted by name to `machines.json`,
+/// each with an `id` starting at 1 in that order. Every row of the other files has its own `id`, and references its
+/// machine with the `machine_id` and `machine_name` keys. The fields are the same as in `machines.json` written by `write_json`.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the JSON files will be created.
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
+///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the JSON writing process.
+///   The callback receives a `ProgressInfo` struct containing fields like `progress`, `total`, `message`, and `callback_type`.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after successfully writing every file.
+/// - On failure: Returns an error if there are issues creating or writing to the JSON files.
+///
+/// # Errors
+/// This function will return an error if:
+/// - The `machines` HashMap is empty, indicating that there is no data to write.
+/// - There are any I/O errors when creating or writing to the JSON files.
+///
+/// # JSON Structure
+/// Each file contains an array of flat JSON objects:
+/// - `machines.json`: The metadata of the machines, without their associated collections.
+/// - `extended_data.json`, `bios_sets.json`, `roms.json`, `disks.json`, `history_sections.json` and `resources.json`:
+///   The associated collections of the machines, with the same fields as when nested.
+/// - `device_refs.json`, `softwares.json` and `samples.json`: The `name` of each device reference, software list and sample.
+/// - `slots.json`: The `name` of each slot, and `slot_options.json`: The options of the slots, referencing their slot with the `slot_id` key.
+pub fn write_json_normalized(
+    export_path: &str,
+    machines: &HashMap<String, Machine>,
+    progress_callback: ProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // If the machines were not loaded, return an error
+    if machines.is_empty() {
+        return Err("No machines data loaded, please read the data first.".into());
+    }
+
+    progress_callback(get_progress_info("Writing normalized machines to JSON"));
+
+    let mut machines_table = JsonTable::create(export_path, "machines")?;
+    let mut extended_data_table = JsonTable::create(export_path, "extended_data")?;
+    let mut bios_sets_table = JsonTable::create(export_path, "bios_sets")?;
+    let mut roms_table = JsonTable::create(export_path, "roms")?;
+    let mut device_refs_table = JsonTable::create(export_path, "device_refs")?;
+    let mut softwares_table = JsonTable::create(export_path, "softwares")?;
+    let mut samples_table = JsonTable::create(export_path, "samples")?;
+    let mut disks_table = JsonTable::create(export_path, "disks")?;
+    let mut history_sections_table = JsonTable::create(export_path, "history_sections")?;
+    let mut resources_table = JsonTable::create(export_path, "resources")?;
+    let mut slots_table = JsonTable::create(export_path, "slots")?;
+    let mut slot_options_table = JsonTable::create(export_path, "slot_options")?;
+
+    let total_elements = machines.len();
+    let batch = std::cmp::max(total_elements / 30, 1);
+
+    for (i, machine) in machines_sorted(machines).into_iter().enumerate() {
+        let machine_id = machines_table.push(machine_fields_to_json(machine), None)?;
+        let key = Some((machine_id, machine.name.as_str()));
+
+        if let Some(extended_data) = &machine.extended_data {
+            extended_data_table.push(into_fields(extended_data_to_json(extended_data)), key)?;
+        }
+        for bios_set in &machine.bios_sets {
+            bios_sets_table.push(into_fields(bios_set_to_json(bios_set)), key)?;
+        }
+        for rom in &machine.roms {
+            roms_table.push(into_fields(rom_to_json(rom)), key)?;
+        }
+        for device_ref in &machine.device_refs {
+            device_refs_table.push(into_fields(json!({ "name": device_ref.name })), key)?;
+        }
+        for software in &machine.software_list {
+            softwares_table.push(into_fields(json!({ "name": software.name })), key)?;
+        }
+        for sample in &machine.samples {
+            samples_table.push(into_fields(json!({ "name": sample.name })), key)?;
+        }
+        for disk in &machine.disks {
+            disks_table.push(into_fields(disk_to_json(disk)), key)?;
+        }
+        for history_section in &machine.history_sections {
+            history_sections_table
+                .push(into_fields(history_section_to_json(history_section)), key)?;
+        }
+        for resource in &machine.resources {
+            resources_table.push(
+                into_fields(resource_to_json(resource, ResourcePaths::Raw)),
+                key,
+            )?;
+        }
+        for slot in &machine.slots {
+            let slot_id = slots_table.push(into_fields(json!({ "name": slot.name })), key)?;
+            for slot_option in &slot.options {
+                let mut fields = into_fields(slot_option_to_json(slot_option));
+                fields.insert("slot_id".to_string(), json!(slot_id));
+                slot_options_table.push(fields, None)?;
+            }
+        }
+
+        // Progress callback
+        if (i + 1) % batch == 0 {
+            progress_callback(ProgressInfo::progress(
+                (i + 1) as u64,
+                total_elements as u64,
+            ));
+        }
+    }
+
+    for table in [
+        machines_table,
+        extended_data_table,
+        bios_sets_table,
+        roms_table,
+        device_refs_table,
+        softwares_table,
+        samples_table,
+        disks_table,
+        history_sections_table,
+        resources_table,
+        slots_table,
+        slot_options_table,
+    ] {
+        table.finish()?;
+    }
+
+    progress_callback(
+        ProgressInfo::finish(format!(
+            "Normalized JSON exported successfully to {}",
+            export_path
+        ))
+        .with_progress(total_elements as u64, total_elements as u64),
+    );
+
+    Ok(())
+}
+
+/// Represents a JSON file of `write_json_normalized`, holding an array written one row at a time.
+struct JsonTable {
+    /// The writer of the file.
+    writer: BufWriter<File>,
+    /// The number of rows written so far, which is also the id of the last row.
+    rows: usize,
+}
+
+impl JsonTable {
+    /// Creates the JSON file and opens its array.
+    fn create(export_path: &str, file_name: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let mut writer = BufWriter::new(create_json_writer(export_path, file_name)?);
+        writer.write_all(b"[\n")?;
+
+        Ok(JsonTable { writer, rows: 0 })
+    }
+
+    /// Writes a row with the next id and, when given, the `machine_id` and `machine_name` of its machine.
+    /// Returns the id of the row.
+    fn push(
+        &mut self,
+        mut fields: serde_json::Map<String, serde_json::Value>,
+        machine: Option<(usize, &str)>,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        self.rows += 1;
+        fields.insert("id".to_string(), json!(self.rows));
+        if let Some((machine_id, machine_name)) = machine {
+            fields.insert("machine_id".to_string(), json!(machine_id));
+            fields.insert("machine_name".to_string(), json!(machine_name));
+        }
+
+        if self.rows > 1 {
+            self.writer.write_all(b",\n")?;
+        }
+        write_json_value(&mut self.writer, &fields, true)?;
+
+        Ok(self.rows)
+    }
+
+    /// Closes the array and flushes the file.
+    fn finish(mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.writer.write_all(b"\n]")?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Writes a compact index of the machines to `index.json`.
+///
+/// The index holds only the `name`, `description`, `year` and `manufacturer` of each machine, sorted by name,
+/// in a single compact JSON array. Missing values are written as `null`.
+///
+/// # Parameters
+/// - `export_path`: A `&str` representing the directory path where the JSON file will be created.
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after successfully writing the index.
+/// - On failure: Returns an error if there are issues creating or writing to the JSON file.
+pub(crate) fn write_json_index(
+    export_path: &str,
+    machines: &HashMap<String, Machine>,
+    progress_callback: ProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    progress_callback(get_progress_info("Writing machines index to JSON"));
+
+    let index: Vec<serde_json::Value> = machines_sorted(machines)
+        .into_iter()
+        .map(|machine| {
+            json!({
+                "name": machine.name,
+                "description": machine.description,
+                "year": machine.year,
+                "manufacturer": machine.manufacturer,
+            })
+        })
+        .collect();
+
+    let mut writer = BufWriter::new(create_json_writer(export_path, "index")?);
+    write_json_value(&mut writer, &index, false)?;
+    writer.flush()?;
+
+    progress_callback(
+        ProgressInfo::finish(format!("Index exported successfully to {}", export_path))
+            .with_progress(index.len() as u64, index.len() as u64),
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Resource;
+
+    #[test]
+    fn test_write_resource_manifest_groups_by_sha1() {
+        let resource = |machine: &str, type_: &str, sha1: &str| Resource {
+            type_: type_.to_string(),
+            name: format!("{}.png", machine),
+            size: 1024,
+            crc: "0badf00d".to_string(),
+            sha1: sha1.to_string(),
+        };
+
+        let mut machines = HashMap::new();
+        for name in ["pacman", "puckman"] {
+            let mut machine = Machine::new(name.to_string());
+            machine.resources.push(resource(name, "snap", "aaaa"));
+            machines.insert(name.to_string(), machine);
+        }
+        machines
+            .get_mut("pacman")
+            .unwrap()
+            .resources
+            .push(resource("pacman", "titles", "bbbb"));
+
+        let export_path = std::env::temp_dir().join("mame_parser_resource_manifest");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        write_resource_manifest(export_path.to_str().unwrap(), &machines, Box::new(|_| {}))
+            .unwrap();
+
+        let content = std::fs::read_to_string(export_path.join("resources_dedup.json")).unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&content).unwrap();
+
+        assert_eq!(manifest.as_object().unwrap().len(), 2);
+        assert_eq!(manifest["aaaa"]["size"], 1024);
+        assert_eq!(manifest["aaaa"]["usages"].as_array().unwrap().len(), 2);
+        assert_eq!(manifest["aaaa"]["usages"][0]["machine"], "pacman");
+        assert_eq!(manifest["bbbb"]["usages"][0]["type"], "titles");
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_export_machines_to_json_compact() {
+        let machines: HashMap<String, Machine> = ["pacman", "puckman"]
+            .into_iter()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    Machine::builder(name).year("1980").build(),
+                )
+            })
+            .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_json_compact");
+        let export_path_str = export_path.to_str().unwrap();
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let mut contents = Vec::new();
+        for pretty in [true, false] {
+            let options = WriteOptions {
+                pretty,
+                ..WriteOptions::default()
+            };
+            export_machines_to_json(export_path_str, &machines, &options, &progress_callback)
+                .unwrap();
+            contents.push(std::fs::read_to_string(export_path.join("machines.json")).unwrap());
+        }
+
+        assert!(!contents[1].contains('\n'));
+        assert!(contents[1].len() < contents[0].len());
+        let pretty: serde_json::Value = serde_json::from_str(&contents[0]).unwrap();
+        let compact: serde_json::Value = serde_json::from_str(&contents[1]).unwrap();
+        assert_eq!(pretty, compact);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_export_machines_to_json_nested_clones() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman").build(),
+            Machine::builder("puckman").clone_of("pacman").build(),
+            Machine::builder("pacmanf").clone_of("pacman").build(),
+            Machine::builder("mspacmab").clone_of("mspacman").build(),
+            // A clone of a clone is nested under the top-level parent
+            Machine::builder("pacmod").clone_of("puckman").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_json_nested_clones");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        let options = WriteOptions {
+            nested_clones: true,
+            ..WriteOptions::default()
+        };
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        export_machines_to_json(
+            export_path.to_str().unwrap(),
+            &machines,
+            &options,
+            &progress_callback,
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(export_path.join("machines.json")).unwrap();
+        let exported: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap();
+
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0]["name"], "mspacmab");
+        assert_eq!(exported[0]["clones"], serde_json::json!([]));
+        assert_eq!(exported[1]["name"], "pacman");
+        let clones = exported[1]["clones"].as_array().unwrap();
+        assert_eq!(clones.len(), 3);
+        assert_eq!(clones[0]["name"], "pacmanf");
+        assert_eq!(clones[1]["name"], "pacmod");
+        assert_eq!(clones[2]["name"], "puckman");
+
+        // Every machine is in the manifest, in the shard of its top-level parent
+        let options = WriteOptions {
+            shards: 2,
+            ..options
+        };
+        export_machines_to_json(
+            export_path.to_str().unwrap(),
+            &machines,
+            &options,
+            &progress_callback,
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(export_path.join("machines_manifest.json")).unwrap();
+        let manifest: HashMap<String, usize> = serde_json::from_str(&content).unwrap();
+        assert_eq!(manifest.len(), machines.len());
+        assert_eq!(manifest["pacmod"], manifest["pacman"]);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_export_machines_to_json_shards() {
+        let machines: HashMap<String, Machine> = ["pacman", "puckman", "dkong", "galaga", "sf2"]
+            .into_iter()
+            .map(|name| (name.to_string(), Machine::builder(name).build()))
+            .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_json_shards");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        let progress_callback: ProgressCallback = Box::new(|_| {});
+        let options = WriteOptions {
+            pretty: false,
+            shards: 3,
+            ..WriteOptions::default()
+        };
+        export_machines_to_json(
+            export_path.to_str().unwrap(),
+            &machines,
+            &options,
+            &progress_callback,
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(export_path.join("machines_manifest.json")).unwrap();
+        let manifest: HashMap<String, usize> = serde_json::from_str(&content).unwrap();
+        assert_eq!(manifest.len(), machines.len());
+
+        let mut names = Vec::new();
+        for shard in 0..3 {
+            let file_name = format!("machines_{}.json", shard);
+            let content = std::fs::read_to_string(export_path.join(file_name)).unwrap();
+            let shard_machines: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap();
+            for machine in shard_machines {
+                let name = machine["name"].as_str().unwrap().to_string();
+                assert_eq!(manifest[&name], shard);
+                assert_eq!(shard_of(&name, 3), shard);
+                names.push(name);
+            }
+        }
+        names.sort();
+        assert_eq!(names, ["dkong", "galaga", "pacman", "puckman", "sf2"]);
+        assert!(!export_path.join("machines.json").exists());
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_collections_json() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .category("Maze")
+                .subcategory("Collect")
+                .add_language("English")
+                .build(),
+            Machine::builder("galaga")
+                .category("Shooter")
+                .subcategory("Flying Vertical")
+                .add_language("English")
+                .build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_collections_json");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        write_collections_json(export_path.to_str().unwrap(), &machines).unwrap();
+
+        let content = std::fs::read_to_string(export_path.join("collections.json")).unwrap();
+        let collections: serde_json::Value = serde_json::from_str(&content).unwrap();
+
+        for key in [
+            "manufacturers",
+            "series",
+            "categories",
+            "subcategories",
+            "languages",
+            "players",
+        ] {
+            assert!(collections[key].is_array(), "missing {}", key);
+        }
+        assert_eq!(collections["categories"][0]["name"], "Maze");
+        assert_eq!(
+            collections["subcategories"][1]["subcategory"],
+            "Flying Vertical"
+        );
+        assert_eq!(collections["languages"][0]["machines"], 2);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_json_by_decade() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman").year("1980").build(),
+            Machine::builder("galaga").year("1981").build(),
+            Machine::builder("sf2").year("1991").build(),
+            Machine::builder("mystery").year("19??").build(),
+            Machine::builder("bootleg").year("198?").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_json_by_decade");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        write_json_by_decade(export_path.to_str().unwrap(), &machines, Box::new(|_| {})).unwrap();
+
+        let read_json = |file_name: &str| -> serde_json::Value {
+            let content = std::fs::read_to_string(export_path.join(file_name)).unwrap();
+            serde_json::from_str(&content).unwrap()
+        };
+
+        let eighties = read_json("1980s.json");
+        assert_eq!(eighties.as_array().unwrap().len(), 3);
+        assert_eq!(eighties[0]["name"], "bootleg");
+        assert_eq!(read_json("1990s.json")[0]["name"], "sf2");
+        assert_eq!(read_json("unknown.json")[0]["name"], "mystery");
+
+        let index = read_json("decades.json");
+        assert_eq!(index[0]["name"], "1980s");
+        assert_eq!(index[0]["machines"], 3);
+        assert_eq!(index.as_array().unwrap().len(), 3);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+    #[test]
+    fn test_write_json_normalized() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman")
+                .year("1980")
+                .add_rom(Rom {
+                    name: "pacman.6e".to_string(),
+                    size: 4096,
+                    merge: None,
+                    status: None,
+                    crc: Some("c1e6ab10".to_string()),
+                    sha1: None,
+                    region: None,
+                    offset: None,
+                    bios: None,
+                })
+                .build(),
+            Machine::builder("dkong").year("1981").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_json_normalized");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        write_json_normalized(export_path.to_str().unwrap(), &machines, Box::new(|_| {})).unwrap();
+
+        let read_json = |file_name: &str| -> serde_json::Value {
+            let content = std::fs::read_to_string(export_path.join(file_name)).unwrap();
+            serde_json::from_str(&content).unwrap()
+        };
+
+        let machines_json = read_json("machines.json");
+        assert_eq!(machines_json[0]["id"], 1);
+        assert_eq!(machines_json[0]["name"], "dkong");
+        assert_eq!(machines_json[1]["id"], 2);
+        assert!(machines_json[1].get("roms").is_none());
+
+        let roms = read_json("roms.json");
+        assert_eq!(roms.as_array().unwrap().len(), 1);
+        assert_eq!(roms[0]["id"], 1);
+        assert_eq!(roms[0]["machine_id"], 2);
+        assert_eq!(roms[0]["machine_name"], "pacman");
+        assert_eq!(roms[0]["crc"], "c1e6ab10");
+        assert_eq!(read_json("disks.json"), json!([]));
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_machines_to_value() {
+        let machines: HashMap<String, Machine> = [
+            Machine::builder("pacman").manufacturer("Namco").build(),
+            Machine::builder("dkong").manufacturer("Nintendo").build(),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let value = machines_to_value(&machines);
+        let array = value.as_array().unwrap();
+
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[0]["name"], "dkong");
+        assert_eq!(array[1]["manufacturer"], "Namco");
+        assert_eq!(
+            array[1],
+            machine_to_json(&machines["pacman"], ResourcePaths::Raw, false)
+        );
+    }
+
+    #[test]
+    fn test_machine_to_json_debug_fields() {
+        let machine = Machine::builder("pacman")
+            .description("Pac-Man (Midway)")
+            .manufacturer("Namco (Midway license)")
+            .extended_data(ExtendedData {
+                name: Some("Pac-Man".to_string()),
+                manufacturer: Some("Namco".to_string()),
+                ..ExtendedData::default()
+            })
+            .build();
+
+        let value = machine_to_json(&machine, ResourcePaths::Raw, false);
+        assert!(value["extended_data"].get("manufacturer_raw").is_none());
+
+        let value = machine_to_json(&machine, ResourcePaths::Raw, true);
+        let extended_data = &value["extended_data"];
+        assert_eq!(extended_data["name"], "Pac-Man");
+        assert_eq!(extended_data["name_raw"], "Pac-Man (Midway)");
+        assert_eq!(extended_data["manufacturer"], "Namco");
+        assert_eq!(extended_data["manufacturer_raw"], "Namco (Midway license)");
+        assert_eq!(extended_data["year_raw"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_write_charts_json() {
+        let machines: HashMap<String, Machine> = [
+            ("pacman", "Namco", "1980"),
+            ("galaga", "Namco", "1981"),
+            ("dkong", "Nintendo", "1981"),
+            ("sf2", "Capcom", "1991"),
+            ("1942", "Capcom", "1984"),
+            ("frogger", "Konami", "1981"),
+        ]
+        .into_iter()
+        .map(|(name, manufacturer, year)| {
+            let machine = Machine::builder(name)
+                .category("Arcade")
+                .extended_data(ExtendedData {
+                    manufacturer: Some(manufacturer.to_string()),
+                    year: Some(year.to_string()),
+                    ..ExtendedData::default()
+                })
+                .build();
+            (name.to_string(), machine)
+        })
+        .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_json_charts");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        write_charts_json(
+            export_path.to_str().unwrap(),
+            &machines,
+            2,
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(export_path.join("charts.json")).unwrap();
+        let charts: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            charts["manufacturers"],
+            json!([
+                { "label": "Capcom", "value": 2 },
+                { "label": "Namco", "value": 2 },
+                { "label": "Other", "value": 2 },
+            ])
+        );
+        assert_eq!(
+            charts["categories"],
+            json!([{ "label": "Arcade", "value": 6 }])
+        );
+        assert_eq!(charts["years"][0], json!({ "label": "1981", "value": 3 }));
+        assert_eq!(charts["years"].as_array().unwrap().len(), 4);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+}
@@ -2,12 +2,23 @@ use crate::core::models::collections_helper::{
     get_languages_list, get_manufacturers_list, get_players_list, get_series_list,
 };
 use crate::helpers::callback_progress_helper::get_progress_info;
-use crate::models::Machine;
-use crate::progress::{CallbackType, ProgressCallback, ProgressInfo};
-use rusqlite::{params, Connection, Result, Transaction};
-use std::collections::HashMap;
+use crate::models::{BoolEncoding, Machine, ResourcePaths, WriteOptions};
+use crate::progress::{ProgressCallback, ProgressInfo};
+use crate::queries::machines_sorted;
+use rusqlite::{params, Connection, Result};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::error::Error;
-use std::fs;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use std::thread;
+
+/// The version of the schema of the SQLite databases written by `write_sqlite`.
+///
+/// It is stored in the `user_version` pragma of every database and is increased whenever the
+/// tables or columns written by the crate change, so readers can refuse incompatible files.
+pub const SQLITE_SCHEMA_VERSION: i32 = 3;
 
 /// Writes machine data to a SQLite database.
 ///
@@ -19,6 +30,9 @@ use std::fs;
 /// - `data_base_path`: A `&str` representing the file path where the SQLite database will be created.
 /// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data to be exported.
 ///   The key is the machine name, and the value is a `Machine` struct with all associated metadata.
+/// - `options`: A reference to the `WriteOptions`, of which `bool_encoding` sets how the boolean fields are stored,
+///   `id_map` writes a `machine_ids.json` file with the id of each machine next to the database,
+///   and `table_prefix` is prepended to the name of every table.
 /// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the SQLite writing process.
 ///   The callback receives a `ProgressInfo` struct containing fields like `progress`, `total`, `message`, and `callback_type`.
 ///
@@ -39,9 +53,11 @@ use std::fs;
 /// - Tables for machine data, each containing relevant metadata like name, source file, manufacturer, etc.
 /// - Relationships between machines and additional attributes such as languages and players.
 /// - Data is inserted in batches to optimize performance and reduce memory usage.
+/// - The series, manufacturers, languages and players are extracted on a worker thread while the machines are inserted.
 pub fn write_sqlite(
     data_base_path: &str,
     machines: &HashMap<String, Machine>,
+    options: &WriteOptions,
     progress_callback: ProgressCallback,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     // If the machines were not loaded, return an error
@@ -57,6 +73,7 @@ pub fn write_sqlite(
     let mut conn = Connection::open(data_base_path).unwrap();
 
     create_database(&mut conn)?;
+    write_metadata(&conn)?;
 
     let batch_size = 5000;
     let mut batch_count = 0;
@@ -69,62 +86,304 @@ pub fn write_sqlite(
     let mut processed_count = 0;
     let batch = 5000;
 
-    let mut transaction = conn.transaction()?;
-    for machine in machines.values() {
-        insert_machine_data(&transaction, machine)?;
+    // Extract the collections on a worker thread while the machines are inserted,
+    // as it only reads the in-memory map and SQLite writes must be serialized anyway
+    let collections = thread::scope(|scope| -> Result<SqliteCollections> {
+        let extraction = scope.spawn(|| extract_collections(machines));
+
+        // Insert the machines sorted by name, so the ids are stable across runs
+        let mut transaction = conn.transaction()?;
+        for machine in machines_sorted(machines) {
+            insert_machine_data(
+                &transaction,
+                machine,
+                options.bool_encoding,
+                options.resource_paths,
+            )?;
+
+            batch_count += 1;
+            if batch_count >= batch_size {
+                transaction.commit()?;
+                transaction = conn.transaction()?;
+                batch_count = 0;
+            }
 
-        batch_count += 1;
-        if batch_count >= batch_size {
-            transaction.commit()?;
-            transaction = conn.transaction()?;
-            batch_count = 0;
+            processed_count += 1;
+            if processed_count % batch == 0 {
+                progress_callback(ProgressInfo::progress(
+                    processed_count as u64,
+                    total_elements as u64,
+                ));
+            }
         }
 
-        processed_count += 1;
-        if processed_count % batch == 0 {
-            progress_callback(ProgressInfo {
-                progress: processed_count as u64,
-                total: total_elements as u64,
-                message: String::from(""),
-                callback_type: CallbackType::Progress,
-            });
-        }
-    }
+        // Commit any remaining transactions
+        transaction.commit()?;
 
-    // Commit any remaining transactions
-    transaction.commit()?;
+        Ok(extraction.join().expect("Collection extraction panicked"))
+    })?;
 
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: total_elements as u64,
-        message: String::from(""),
-        callback_type: CallbackType::Progress,
-    });
+    progress_callback(ProgressInfo::progress(
+        processed_count as u64,
+        total_elements as u64,
+    ));
 
+    finish_database(
+        &mut conn,
+        data_base_path,
+        &collections,
+        processed_count,
+        options,
+        &progress_callback,
+    )
+}
+
+/// Runs the steps of `write_sqlite` that follow the insertion of the machines.
+///
+/// The relations and the languages and players relationships are created, and the optional raw manufacturers,
+/// machine ids file and table prefix of the options are applied. A finish update is sent at the end.
+///
+/// # Parameters
+/// - `conn`: A mutable reference to the `Connection` of the database, with every machine inserted and committed.
+/// - `data_base_path`: A `&str` representing the file path of the SQLite database.
+/// - `collections`: The `SqliteCollections` with the names of the series, manufacturers, languages and players.
+/// - `processed_count`: The number of machines inserted, reported by the finish update.
+/// - `options`: A reference to the `WriteOptions` of the export.
+/// - `progress_callback`: A reference to the callback receiving the progress updates.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` once the database is complete.
+/// - On failure: Returns an error if any SQL statement fails or the machine ids file cannot be written.
+fn finish_database(
+    conn: &mut Connection,
+    data_base_path: &str,
+    collections: &SqliteCollections,
+    processed_count: usize,
+    options: &WriteOptions,
+    progress_callback: &ProgressCallback,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
     // Add relations
-    create_relations(&mut conn, &machines, &progress_callback)?;
+    create_relations(conn, collections, progress_callback)?;
 
     // Add languages relations
     progress_callback(get_progress_info("Adding languages relations"));
-    extract_and_insert_languages(&mut conn, &machines)?;
-    insert_machine_language_relationships(&mut conn)?;
+    insert_names(conn, "languages", &collections.languages)?;
+    insert_machine_language_relationships(conn)?;
 
     // Add players relations
     progress_callback(get_progress_info("Adding players relations"));
-    extract_and_insert_players(&mut conn, &machines)?;
-    insert_machine_player_relationships(&mut conn)?;
+    insert_names(conn, "players", &collections.players)?;
+    insert_machine_player_relationships(conn)?;
+
+    // Add the original manufacturers
+    if options.raw_manufacturers {
+        progress_callback(get_progress_info("Adding raw manufacturers"));
+        insert_raw_manufacturers(conn)?;
+    }
+
+    // Write the machine ids sidecar file
+    if options.id_map {
+        progress_callback(get_progress_info("Writing machine ids"));
+        write_machine_ids(data_base_path, &read_machine_ids(conn)?)?;
+    }
+
+    // Prefix the table names, once every statement has run against the unprefixed schema
+    if let Some(table_prefix) = options
+        .table_prefix
+        .as_deref()
+        .filter(|prefix| !prefix.is_empty())
+    {
+        progress_callback(get_progress_info("Adding table prefix"));
+        apply_table_prefix(conn, table_prefix)?;
+    }
 
     let data_base_file = data_base_path.split('/').last().unwrap();
-    progress_callback(ProgressInfo {
-        progress: processed_count as u64,
-        total: processed_count as u64,
-        message: format!("{} exported successfully", data_base_file),
-        callback_type: CallbackType::Finish,
-    });
+    progress_callback(
+        ProgressInfo::finish(format!("{} exported successfully", data_base_file))
+            .with_progress(processed_count as u64, processed_count as u64),
+    );
 
     Ok(())
 }
 
+/// Starts an incremental export of machines to a SQLite database.
+///
+/// This function creates the database like `write_sqlite` does, and returns a `SqliteMachineSink` into which
+/// the machines are inserted one by one, e.g. from the callback of `read_mame_file_streaming`, so the whole
+/// `HashMap` of machines is never held in memory. The machines are inserted in batched transactions, and the
+/// relations are created by `SqliteMachineSink::finish` from the data already stored in the database.
+///
+/// Unlike `write_sqlite`, which inserts the machines sorted by name, the ids of the machines follow the order
+/// in which they are inserted.
+///
+/// # Parameters
+/// - `data_base_path`: A `&str` representing the file path where the SQLite database will be created. An existing file is replaced.
+/// - `options`: A reference to the `WriteOptions`, used like `write_sqlite` does.
+/// - `progress_callback`: A callback function of type `ProgressCallback` that provides progress updates during the export.
+///
+/// # Returns
+/// Returns a `Result<SqliteMachineSink, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the `SqliteMachineSink` into which the machines are inserted.
+/// - On failure: Returns an error if the database cannot be created.
+///
+/// # Example
+/// ```no_run
+/// use mame_parser::file_handling::write_sqlite_streaming;
+/// use mame_parser::models::WriteOptions;
+/// use mame_parser::readers::read_mame_file_streaming;
+///
+/// let mut sink = write_sqlite_streaming("machines.db", &WriteOptions::default(), Box::new(|_| {}))?;
+/// read_mame_file_streaming("MAME 0.270.dat", |machine| sink.insert(&machine), Box::new(|_| {}))?;
+/// sink.finish()?;
+/// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+pub fn write_sqlite_streaming(
+    data_base_path: &str,
+    options: &WriteOptions,
+    progress_callback: ProgressCallback,
+) -> Result<SqliteMachineSink, Box<dyn Error + Send + Sync>> {
+    // Remove the database file if it already exists
+    if fs::metadata(data_base_path).is_ok() {
+        fs::remove_file(data_base_path)?;
+    }
+
+    let mut conn = Connection::open(data_base_path)?;
+
+    create_database(&mut conn)?;
+    write_metadata(&conn)?;
+
+    progress_callback(get_progress_info(
+        format!("Writing {}", data_base_path).as_str(),
+    ));
+    conn.execute_batch("BEGIN")?;
+
+    Ok(SqliteMachineSink {
+        conn,
+        data_base_path: data_base_path.to_string(),
+        options: options.clone(),
+        progress_callback,
+        batch_count: 0,
+        inserted_count: 0,
+    })
+}
+
+/// Receives the machines of an incremental export to a SQLite database, as returned by `write_sqlite_streaming`.
+///
+/// The machines are inserted with `insert`, and the export is completed with `finish`. A sink dropped
+/// without calling `finish` leaves the database without relations, and the last batch of machines is discarded.
+pub struct SqliteMachineSink {
+    conn: Connection,
+    data_base_path: String,
+    options: WriteOptions,
+    progress_callback: ProgressCallback,
+    batch_count: usize,
+    inserted_count: usize,
+}
+
+impl SqliteMachineSink {
+    /// Inserts a machine into the database.
+    ///
+    /// The machines are committed in batches of 5000. A machine with the same name as a machine already
+    /// inserted replaces it.
+    pub fn insert(&mut self, machine: &Machine) -> Result<(), Box<dyn Error + Send + Sync>> {
+        insert_machine_data(
+            &self.conn,
+            machine,
+            self.options.bool_encoding,
+            self.options.resource_paths,
+        )?;
+
+        self.batch_count += 1;
+        self.inserted_count += 1;
+        if self.batch_count >= 5000 {
+            self.conn.execute_batch("COMMIT; BEGIN")?;
+            self.batch_count = 0;
+            (self.progress_callback)(ProgressInfo::progress(self.inserted_count as u64, 0));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of machines inserted so far.
+    pub fn inserted_count(&self) -> usize {
+        self.inserted_count
+    }
+
+    /// Commits the remaining machines and creates the relations, completing the export.
+    ///
+    /// The names of the series, manufacturers, languages and players are read back from the database,
+    /// then the same steps as `write_sqlite` are run. Returns an error if no machine was inserted.
+    pub fn finish(mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.conn.execute_batch("COMMIT")?;
+
+        if self.inserted_count == 0 {
+            return Err("No machines data loaded, please read the data first.".into());
+        }
+
+        (self.progress_callback)(ProgressInfo::progress(
+            self.inserted_count as u64,
+            self.inserted_count as u64,
+        ));
+
+        let collections = read_collections(&self.conn)?;
+        finish_database(
+            &mut self.conn,
+            &self.data_base_path,
+            &collections,
+            self.inserted_count,
+            &self.options,
+            &self.progress_callback,
+        )
+    }
+}
+
+/// Reads the unique series, manufacturers, languages and players of the machines stored in the database.
+///
+/// This function is the counterpart of `extract_collections` for the machines inserted by a `SqliteMachineSink`,
+/// which are not available in memory. The names are sorted like `extract_collections` sorts them.
+///
+/// # Parameters
+/// - `conn`: A reference to the `Connection` of the database, with every machine inserted and committed.
+///
+/// # Returns
+/// Returns a `Result<SqliteCollections>` with the sorted names of each collection.
+fn read_collections(conn: &Connection) -> Result<SqliteCollections> {
+    let read_values = |query: &str| -> Result<Vec<String>> {
+        let mut stmt = conn.prepare(query)?;
+        let values = stmt.query_map([], |row| row.get(0))?;
+        values.collect()
+    };
+    let split_values = |values: Vec<String>| -> Vec<String> {
+        values
+            .iter()
+            .flat_map(|value| value.split(',').map(|s| s.trim().to_string()))
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    };
+
+    let mut languages = split_values(read_values(
+        "SELECT languages FROM machines WHERE languages IS NOT NULL",
+    )?);
+    // Machines without languages are stored with an empty list
+    languages.retain(|language| !language.is_empty());
+
+    Ok(SqliteCollections {
+        series: read_values(
+            "SELECT DISTINCT series FROM machines WHERE series IS NOT NULL ORDER BY series",
+        )?,
+        manufacturers: read_values(
+            "SELECT DISTINCT manufacturer FROM extended_data
+             WHERE manufacturer IS NOT NULL ORDER BY manufacturer",
+        )?,
+        languages,
+        players: split_values(read_values(
+            "SELECT players FROM extended_data WHERE players IS NOT NULL",
+        )?),
+    })
+}
+
 /// Creates the necessary tables in the SQLite database.
 ///
 /// This function initializes the SQLite database by creating all the required tables for storing machine data,
@@ -163,6 +422,8 @@ pub fn write_sqlite(
 /// - `disks`: Stores disk information for each machine.
 /// - `history_sections`: Stores historical sections related to each machine.
 /// - `resources`: Stores resource information such as size, type, and checksums for each machine.
+/// - `slots`: Stores the expansion slots of each machine.
+/// - `slot_options`: Stores the devices that can be plugged into each slot.
 fn create_database(conn: &mut Connection) -> Result<()> {
     // Series table
     conn.execute(
@@ -243,6 +504,7 @@ fn create_database(conn: &mut Connection) -> Result<()> {
                   category TEXT,
                   subcategory TEXT,
                   is_mature INTEGER,
+                  version_added TEXT,
                   languages TEXT,
                   category_id INTEGER,
                   subcategory_id INTEGER,
@@ -290,6 +552,8 @@ fn create_database(conn: &mut Connection) -> Result<()> {
                   players TEXT,
                   is_parent INTEGER,
                   year TEXT,
+                  max_players INTEGER,
+                  players_simultaneous INTEGER,
                   machine_id INTEGER,
                   FOREIGN KEY(machine_id) REFERENCES machines(id)
                   )",
@@ -320,6 +584,9 @@ fn create_database(conn: &mut Connection) -> Result<()> {
                   status TEXT,
                   crc TEXT,
                   sha1 TEXT,
+                  region TEXT,
+                  offset TEXT,
+                  bios TEXT,
                   machine_id INTEGER,
                   FOREIGN KEY(machine_id) REFERENCES machines(id)
                   )",
@@ -408,6 +675,270 @@ fn create_database(conn: &mut Connection) -> Result<()> {
         [],
     )?;
 
+    // Slots table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS slots (
+                  id INTEGER PRIMARY KEY AUTOINCREMENT,
+                  machine_name TEXT,
+                  name TEXT,
+                  machine_id INTEGER,
+                  FOREIGN KEY(machine_id) REFERENCES machines(id)
+                  )",
+        [],
+    )?;
+
+    // Slot options table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS slot_options (
+                  id INTEGER PRIMARY KEY AUTOINCREMENT,
+                  slot_id INTEGER,
+                  name TEXT,
+                  devname TEXT,
+                  is_default INTEGER,
+                  FOREIGN KEY(slot_id) REFERENCES slots(id)
+                  )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Records the schema version and the origin of the SQLite database.
+///
+/// This function sets the `user_version` pragma to `SQLITE_SCHEMA_VERSION` and fills a `metadata` table
+/// with the version of the crate, the schema version and the UTC time of the export.
+///
+/// # Parameters
+/// - `conn`: A reference to the `Connection` to the SQLite database.
+///
+/// # Returns
+/// Returns a `Result<()>`:
+/// - On success: Returns `Ok(())` after recording the metadata.
+/// - On failure: Returns an error if there are issues executing any of the SQL statements.
+///
+/// # Created Data
+/// - `metadata`: Stores `key`/`value` pairs for `crate_version`, `schema_version` and `exported_at`.
+fn write_metadata(conn: &Connection) -> Result<()> {
+    conn.pragma_update(None, "user_version", SQLITE_SCHEMA_VERSION)?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS metadata (
+                  key TEXT PRIMARY KEY,
+                  value TEXT
+                  )",
+        [],
+    )?;
+    conn.execute(
+        "INSERT OR REPLACE INTO metadata (key, value) VALUES
+         ('crate_version', ?1),
+         ('schema_version', ?2),
+         ('exported_at', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
+        params![env!("CARGO_PKG_VERSION"), SQLITE_SCHEMA_VERSION.to_string()],
+    )?;
+
+    Ok(())
+}
+
+/// Represents an error returned by `verify_sqlite_schema`.
+///
+/// # Variants
+/// - `Query`: The schema of the database could not be queried.
+/// - `Mismatch`: Some of the tables or columns written by this crate are missing from the database,
+///   which usually means that it was created by an incompatible version of the crate or by another tool.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The schema of the database could not be queried.
+    Query(rusqlite::Error),
+    /// Tables or columns expected by the crate are missing.
+    Mismatch {
+        /// The names of the missing tables.
+        missing_tables: Vec<String>,
+        /// The missing columns of the existing tables, as `(table, column)` pairs.
+        missing_columns: Vec<(String, String)>,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Query(err) => write!(f, "Failed to query the database schema: {}", err),
+            SchemaError::Mismatch {
+                missing_tables,
+                missing_columns,
+            } => {
+                write!(f, "The database schema does not match the expected schema")?;
+                if !missing_tables.is_empty() {
+                    write!(f, "; missing tables: {}", missing_tables.join(", "))?;
+                }
+                if !missing_columns.is_empty() {
+                    let columns: Vec<String> = missing_columns
+                        .iter()
+                        .map(|(table, column)| format!("{}.{}", table, column))
+                        .collect();
+                    write!(f, "; missing columns: {}", columns.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for SchemaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SchemaError::Query(err) => Some(err),
+            SchemaError::Mismatch { .. } => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for SchemaError {
+    fn from(err: rusqlite::Error) -> Self {
+        SchemaError::Query(err)
+    }
+}
+
+/// Verifies that an existing SQLite database has the schema written by `write_sqlite`.
+///
+/// The expected tables and columns are those created by this version of the crate. They are compared with the
+/// tables listed in `sqlite_master` and their columns, so the check can be run before inserting into a database
+/// that was not created by the current version of the crate. Extra tables and columns are allowed.
+///
+/// # Parameters
+/// - `conn`: A reference to the `Connection` to the SQLite database to verify.
+///
+/// # Returns
+/// Returns a `Result<(), SchemaError>`:
+/// - On success: Returns `Ok(())` if every expected table and column exists.
+/// - On failure: Returns `SchemaError::Mismatch` listing the missing tables and columns,
+///   or `SchemaError::Query` if the schema cannot be read.
+pub fn verify_sqlite_schema(conn: &Connection) -> std::result::Result<(), SchemaError> {
+    let mut expected = Connection::open_in_memory()?;
+    create_database(&mut expected)?;
+
+    let existing_tables = read_table_names(conn)?;
+    let mut missing_tables = Vec::new();
+    let mut missing_columns = Vec::new();
+
+    for table in read_table_names(&expected)? {
+        if !existing_tables.contains(&table) {
+            missing_tables.push(table);
+            continue;
+        }
+
+        let existing_columns = read_column_names(conn, &table)?;
+        for column in read_column_names(&expected, &table)? {
+            if !existing_columns.contains(&column) {
+                missing_columns.push((table.clone(), column));
+            }
+        }
+    }
+
+    if missing_tables.is_empty() && missing_columns.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaError::Mismatch {
+            missing_tables,
+            missing_columns,
+        })
+    }
+}
+
+/// Prepends a prefix to the name of every table of the SQLite database.
+///
+/// The tables are renamed with `ALTER TABLE ... RENAME TO`, which also updates the foreign keys
+/// referencing them, so the prefixed schema keeps its relations.
+///
+/// # Parameters
+/// - `conn`: A reference to the `Connection` to the SQLite database.
+/// - `table_prefix`: The prefix prepended to the table names (e.g. `mame_`).
+///
+/// # Returns
+/// Returns a `Result<()>`:
+/// - On success: Returns `Ok(())` after renaming every table.
+/// - On failure: Returns an error if any table cannot be renamed, e.g. if the prefixed name is already used.
+fn apply_table_prefix(conn: &Connection, table_prefix: &str) -> Result<()> {
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+
+    for table in read_table_names(conn)? {
+        conn.execute(
+            &format!(
+                "ALTER TABLE {} RENAME TO {}",
+                quote(&table),
+                quote(&format!("{}{}", table_prefix, table))
+            ),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Reads the names of the tables of a SQLite database, sorted by name.
+///
+/// # Parameters
+/// - `conn`: A reference to the `Connection` to the SQLite database.
+///
+/// # Returns
+/// Returns a `Result<Vec<String>>` with the names of the tables, excluding the internal `sqlite_` tables.
+fn read_table_names(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+         ORDER BY name",
+    )?;
+    let names = stmt.query_map([], |row| row.get(0))?;
+    names.collect()
+}
+
+/// Reads the names of the columns of a table of a SQLite database.
+///
+/// # Parameters
+/// - `conn`: A reference to the `Connection` to the SQLite database.
+/// - `table`: The name of the table.
+///
+/// # Returns
+/// Returns a `Result<Vec<String>>` with the names of the columns, in declaration order.
+fn read_column_names(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+    let names = stmt.query_map([table], |row| row.get(0))?;
+    names.collect()
+}
+
+/// Reads the ids assigned to the machines in the SQLite database.
+///
+/// # Parameters
+/// - `conn`: A reference to the `Connection` to the SQLite database.
+///
+/// # Returns
+/// Returns a `Result<HashMap<String, i64>>`:
+/// - On success: Contains a `HashMap` where the keys are the machine names and the values are their row ids.
+/// - On failure: Returns an error if the machines cannot be queried.
+fn read_machine_ids(conn: &Connection) -> Result<HashMap<String, i64>> {
+    let mut stmt = conn.prepare("SELECT name, id FROM machines")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+    rows.collect()
+}
+
+/// Writes the ids of the machines to a `machine_ids.json` file in the folder of the SQLite database.
+///
+/// # Parameters
+/// - `data_base_path`: A `&str` representing the file path of the SQLite database.
+/// - `machine_ids`: A reference to a `HashMap` with the row id of each machine name.
+///
+/// # Returns
+/// Returns a `Result<(), Box<dyn Error + Send + Sync>>`:
+/// - On success: Returns `Ok(())` after writing the file, where the ids are sorted by machine name.
+/// - On failure: Returns an error if the file cannot be created or written.
+fn write_machine_ids(
+    data_base_path: &str,
+    machine_ids: &HashMap<String, i64>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let file_path = Path::new(data_base_path).with_file_name("machine_ids.json");
+    let writer = BufWriter::new(File::create(file_path)?);
+    let sorted_ids: BTreeMap<&String, &i64> = machine_ids.iter().collect();
+    serde_json::to_writer_pretty(writer, &sorted_ids)?;
+
     Ok(())
 }
 
@@ -418,9 +949,11 @@ fn create_database(conn: &mut Connection) -> Result<()> {
 /// Existing entries are replaced if there are conflicts to ensure the data is up-to-date.
 ///
 /// # Parameters
-/// - `transaction`: A reference to a `Transaction` object representing an active SQLite transaction.
+/// - `transaction`: A reference to the `Connection` of an active SQLite transaction (a `Transaction` derefs to it).
 ///   This transaction is used to perform multiple insertions atomically.
 /// - `machine`: A reference to a `Machine` struct containing all the data to be inserted into the database.
+/// - `bool_encoding`: The `BoolEncoding` used for the boolean fields.
+/// - `resource_paths`: The `ResourcePaths` form in which the resource names are written.
 ///
 /// # Returns
 /// Returns a `Result<()>`:
@@ -443,21 +976,27 @@ fn create_database(conn: &mut Connection) -> Result<()> {
 /// - `disks`: Inserts or replaces disk information for the machine.
 /// - `history_sections`: Inserts or replaces historical sections related to the machine.
 /// - `resources`: Inserts or replaces resource information such as size, type, and checksums for the machine.
-fn insert_machine_data(transaction: &Transaction, machine: &Machine) -> Result<()> {
+/// - `slots` and `slot_options`: Inserts the expansion slots of the machine and the devices that can be plugged into them.
+fn insert_machine_data(
+    transaction: &Connection,
+    machine: &Machine,
+    bool_encoding: BoolEncoding,
+    resource_paths: ResourcePaths,
+) -> Result<()> {
     transaction.execute(
         "INSERT OR REPLACE INTO machines (
                   name, source_file, rom_of, clone_of, is_bios, is_device, runnable, is_mechanical, sample_of,
-                  description, year, manufacturer, driver_status, players, series, category, subcategory, is_mature, languages
-                  ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+                  description, year, manufacturer, driver_status, players, series, category, subcategory, is_mature, version_added, languages
+                  ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)",
         params![
             machine.name,
             machine.source_file,
             machine.rom_of,
             machine.clone_of,
-            machine.is_bios,
-            machine.is_device,
-            machine.runnable,
-            machine.is_mechanical,
+            bool_encoding.encode(machine.is_bios),
+            bool_encoding.encode(machine.is_device),
+            bool_encoding.encode(machine.runnable),
+            bool_encoding.encode(machine.is_mechanical),
             machine.sample_of,
             machine.description,
             machine.year,
@@ -467,15 +1006,16 @@ fn insert_machine_data(transaction: &Transaction, machine: &Machine) -> Result<(
             machine.series,
             machine.category,
             machine.subcategory,
-            machine.is_mature,
+            bool_encoding.encode(machine.is_mature),
+            machine.version_added,
             machine.languages.join(", ")
         ],
     )?;
 
     if let Some(extended_data) = &machine.extended_data {
         transaction.execute(
-            "INSERT OR REPLACE INTO extended_data (machine_name, name, manufacturer, players, is_parent, year) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![machine.name, extended_data.name, extended_data.manufacturer, extended_data.players, extended_data.is_parent, extended_data.year],
+            "INSERT OR REPLACE INTO extended_data (machine_name, name, manufacturer, players, is_parent, year, max_players, players_simultaneous) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![machine.name, extended_data.name, extended_data.manufacturer, extended_data.players, bool_encoding.encode(extended_data.is_parent), extended_data.year, extended_data.max_players, bool_encoding.encode(extended_data.players_simultaneous)],
         )?;
     }
 
@@ -489,8 +1029,8 @@ fn insert_machine_data(transaction: &Transaction, machine: &Machine) -> Result<(
     for rom in &machine.roms {
         transaction.execute(
             "INSERT OR REPLACE INTO roms (
-                      machine_name, name, size, merge, status, crc, sha1
-                      ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                      machine_name, name, size, merge, status, crc, sha1, region, offset, bios
+                      ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 machine.name,
                 rom.name,
@@ -498,7 +1038,10 @@ fn insert_machine_data(transaction: &Transaction, machine: &Machine) -> Result<(
                 rom.merge,
                 rom.status,
                 rom.crc,
-                rom.sha1
+                rom.sha1,
+                rom.region,
+                rom.offset,
+                rom.bios
             ],
         )?;
     }
@@ -562,7 +1105,7 @@ fn insert_machine_data(transaction: &Transaction, machine: &Machine) -> Result<(
             params![
                 machine.name,
                 resource.type_,
-                resource.name,
+                resource_paths.name_of(resource),
                 resource.size,
                 resource.crc,
                 resource.sha1
@@ -570,44 +1113,89 @@ fn insert_machine_data(transaction: &Transaction, machine: &Machine) -> Result<(
         )?;
     }
 
+    for slot in &machine.slots {
+        transaction.execute(
+            "INSERT INTO slots (machine_name, name) VALUES (?1, ?2)",
+            params![machine.name, slot.name],
+        )?;
+        let slot_id = transaction.last_insert_rowid();
+
+        for slot_option in &slot.options {
+            transaction.execute(
+                "INSERT INTO slot_options (
+                          slot_id, name, devname, is_default
+                          ) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    slot_id,
+                    slot_option.name,
+                    slot_option.devname,
+                    slot_option.is_default
+                ],
+            )?;
+        }
+    }
+
     Ok(())
 }
 
-/// Extracts languages from the machine data and inserts them into the SQLite database.
+/// Represents the unique names of the collections stored in their own SQLite tables.
 ///
-/// This function processes all the machines in the provided `HashMap` to extract a unique list of languages.
-/// It then inserts each language into the `languages` table in the SQLite database.
-/// If a language already exists in the table, the insertion is ignored to avoid duplication.
+/// The names are extracted from the in-memory machines by `extract_collections` and sorted,
+/// so the ids assigned to them are stable across runs.
+struct SqliteCollections {
+    series: Vec<String>,
+    manufacturers: Vec<String>,
+    languages: Vec<String>,
+    players: Vec<String>,
+}
+
+/// Extracts the unique series, manufacturers, languages and players of the machines.
+///
+/// This function only reads the machine data, so it can run on a worker thread while the machines are inserted.
+///
+/// # Parameters
+/// - `machines`: A reference to a `HashMap<String, Machine>` containing all the machine data.
+///
+/// # Returns
+/// Returns the `SqliteCollections` with the sorted names of each collection.
+fn extract_collections(machines: &HashMap<String, Machine>) -> SqliteCollections {
+    let sorted_names = |collection: HashMap<String, usize>| -> Vec<String> {
+        let mut names: Vec<String> = collection.into_keys().collect();
+        names.sort();
+        names
+    };
+
+    SqliteCollections {
+        series: sorted_names(get_series_list(machines)),
+        manufacturers: sorted_names(get_manufacturers_list(machines)),
+        languages: sorted_names(get_languages_list(machines)),
+        players: sorted_names(get_players_list(machines)),
+    }
+}
+
+/// Inserts names into a collection table of the SQLite database.
+///
+/// Each name is inserted into the `name` column of the table in a single transaction.
+/// If a name already exists in the table, the insertion is ignored to avoid duplication.
 ///
 /// # Parameters
 /// - `conn`: A mutable reference to a `Connection` representing the SQLite database connection.
-/// - `machines`: A reference to a `HashMap<String, Machine>` containing all the machine data, from which the languages will be extracted.
+/// - `table`: The name of the table (`series`, `manufacturers`, `languages` or `players`).
+/// - `names`: The names to insert, in the order their ids should be assigned.
 ///
 /// # Returns
 /// Returns a `Result<()>`:
-/// - On success: Returns `Ok(())` after successfully inserting all unique languages into the database.
+/// - On success: Returns `Ok(())` after successfully inserting all the names into the table.
 /// - On failure: Returns an error if there are issues executing any of the SQL statements.
-///
-/// # Errors
-/// This function will return an error if:
-/// - There are SQL syntax errors or constraint violations during the insert operations.
-/// - The transaction encounters any I/O issues or other database errors.
-///
-/// # Inserted Data
-/// - `languages`: Inserts each unique language extracted from the machine data into the `languages` table.
-fn extract_and_insert_languages(
-    conn: &mut Connection,
-    machines: &HashMap<String, Machine>,
-) -> Result<()> {
-    let languages_hash = get_languages_list(&machines);
-    let mut languages: Vec<String> = languages_hash.keys().cloned().collect();
-    languages.sort();
-
+fn insert_names(conn: &mut Connection, table: &str, names: &[String]) -> Result<()> {
     let tx = conn.transaction()?;
     {
-        let mut insert_stmt = tx.prepare("INSERT OR IGNORE INTO languages (name) VALUES (?)")?;
-        for language in languages {
-            insert_stmt.execute([&language])?;
+        let mut insert_stmt = tx.prepare(&format!(
+            "INSERT OR IGNORE INTO {} (name) VALUES (?)",
+            table
+        ))?;
+        for name in names {
+            insert_stmt.execute([name])?;
         }
     }
     tx.commit()?;
@@ -665,48 +1253,6 @@ fn insert_machine_language_relationships(conn: &mut Connection) -> Result<()> {
     Ok(())
 }
 
-/// Extracts player information from the machine data and inserts it into the SQLite database.
-///
-/// This function processes all the machines in the provided `HashMap` to extract a unique list of player types.
-/// It then inserts each player type into the `players` table in the SQLite database.
-/// If a player type already exists in the table, the insertion is ignored to avoid duplication.
-///
-/// # Parameters
-/// - `conn`: A mutable reference to a `Connection` representing the SQLite database connection.
-/// - `machines`: A reference to a `HashMap<String, Machine>` containing all the machine data, from which the player types will be extracted.
-///
-/// # Returns
-/// Returns a `Result<()>`:
-/// - On success: Returns `Ok(())` after successfully inserting all unique player types into the database.
-/// - On failure: Returns an error if there are issues executing any of the SQL statements.
-///
-/// # Errors
-/// This function will return an error if:
-/// - There are SQL syntax errors or constraint violations during the insert operations.
-/// - The transaction encounters any I/O issues or other database errors.
-///
-/// # Inserted Data
-/// - `players`: Inserts each unique player type extracted from the machine data into the `players` table.
-fn extract_and_insert_players(
-    conn: &mut Connection,
-    machines: &HashMap<String, Machine>,
-) -> Result<()> {
-    let players_hash = get_players_list(&machines);
-    let mut players: Vec<String> = players_hash.keys().cloned().collect();
-    players.sort();
-
-    let tx = conn.transaction()?;
-    {
-        let mut insert_stmt = tx.prepare("INSERT OR IGNORE INTO players (name) VALUES (?)")?;
-        for player in players {
-            insert_stmt.execute([&player])?;
-        }
-    }
-    tx.commit()?;
-
-    Ok(())
-}
-
 /// Inserts relationships between machines and players into the SQLite database.
 ///
 /// This function establishes relationships between machines and their associated player types in the `machine_players` table.
@@ -762,6 +1308,46 @@ fn insert_machine_player_relationships(conn: &mut Connection) -> Result<()> {
     Ok(())
 }
 
+/// Creates the `raw_manufacturers` table and fills it with the original manufacturer strings.
+///
+/// The `manufacturers` table only contains the normalized manufacturers from the extended data, while the
+/// `manufacturer` column of the `machines` table keeps the original string of each machine. This table lists
+/// each distinct original string once, along with the id of the normalized manufacturer it was normalized to,
+/// so machines can be queried by either form. It must be called after `create_relations`.
+///
+/// # Parameters
+/// - `conn`: A reference to a `Connection` representing the SQLite database connection.
+///
+/// # Returns
+/// Returns a `Result<()>`:
+/// - On success: Returns `Ok(())` after creating and filling the table.
+/// - On failure: Returns an error if there are issues executing any of the SQL statements.
+///
+/// # Created Data
+/// - `raw_manufacturers`: Stores each original manufacturer string (`name`) and the `manufacturer_id` of its normalized form.
+fn insert_raw_manufacturers(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS raw_manufacturers (
+                  id INTEGER PRIMARY KEY AUTOINCREMENT,
+                  name TEXT UNIQUE,
+                  manufacturer_id INTEGER,
+                  FOREIGN KEY (manufacturer_id) REFERENCES manufacturers(id)
+                  )",
+        [],
+    )?;
+    conn.execute(
+        "INSERT OR IGNORE INTO raw_manufacturers (name, manufacturer_id)
+         SELECT manufacturer, MIN(manufacturer_id)
+         FROM machines
+         WHERE manufacturer IS NOT NULL
+         GROUP BY manufacturer
+         ORDER BY manufacturer",
+        [],
+    )?;
+
+    Ok(())
+}
+
 /// Creates and updates relationships between different entities in the SQLite database.
 ///
 /// This function establishes and updates various relationships between entities such as machines, categories, subcategories, series, and manufacturers.
@@ -770,7 +1356,7 @@ fn insert_machine_player_relationships(conn: &mut Connection) -> Result<()> {
 ///
 /// # Parameters
 /// - `conn`: A mutable reference to a `Connection` representing the SQLite database connection.
-/// - `machines`: A reference to a `HashMap<String, Machine>` containing all machine data for creating and updating relationships.
+/// - `collections`: A reference to the `SqliteCollections` extracted from the machines, with the series and manufacturers to insert.
 /// - `progress_callback`: A reference to a callback function of type `ProgressCallback` that provides progress updates during the process of creating and updating relations.
 ///
 /// # Returns
@@ -789,10 +1375,10 @@ fn insert_machine_player_relationships(conn: &mut Connection) -> Result<()> {
 /// - `subcategories`: Inserts unique subcategories associated with categories and updates machines with the corresponding `subcategory_id`.
 /// - `series`: Inserts unique series names and updates machines with the corresponding `series_id`.
 /// - `manufacturers`: Inserts unique manufacturer names from the `extended_data` and updates machines with the corresponding `manufacturer_id`.
-/// - Updates various tables (`bios_sets`, `roms`, `device_refs`, `softwares`, `samples`, `disks`, `history_sections`, `resources`) to link their records with the correct `machine_id`.
+/// - Updates various tables (`bios_sets`, `roms`, `device_refs`, `softwares`, `samples`, `disks`, `history_sections`, `resources`, `slots`) to link their records with the correct `machine_id`.
 fn create_relations(
     conn: &mut Connection,
-    machines: &HashMap<String, Machine>,
+    collections: &SqliteCollections,
     progress_callback: &ProgressCallback,
 ) -> Result<()> {
     progress_callback(get_progress_info("Creating relations"));
@@ -830,19 +1416,7 @@ fn create_relations(
 
     progress_callback(get_progress_info("Adding series"));
     // Add series
-    let series_hash = get_series_list(&machines);
-    let mut series: Vec<String> = series_hash.keys().cloned().collect();
-    series.sort();
-
-    // let series = get_list(&SERIES);
-    let tx = conn.transaction()?;
-    {
-        let mut insert_stmt = tx.prepare("INSERT OR IGNORE INTO series (name) VALUES (?)")?;
-        for series_name in series {
-            insert_stmt.execute([&series_name])?;
-        }
-    }
-    tx.commit()?;
+    insert_names(conn, "series", &collections.series)?;
     // Update machines with series_id
     conn.execute(
         "UPDATE machines
@@ -852,18 +1426,7 @@ fn create_relations(
 
     progress_callback(get_progress_info("Adding manufacturers"));
     // Add manufacturers from extended data
-    let manufacturers_hash = get_manufacturers_list(&machines);
-    let mut manufacturers: Vec<String> = manufacturers_hash.keys().cloned().collect();
-    manufacturers.sort();
-    let tx = conn.transaction()?;
-    {
-        let mut insert_stmt =
-            tx.prepare("INSERT OR IGNORE INTO manufacturers (name) VALUES (?)")?;
-        for manufacturer in manufacturers {
-            insert_stmt.execute([&manufacturer])?;
-        }
-    }
-    tx.commit()?;
+    insert_names(conn, "manufacturers", &collections.manufacturers)?;
 
     progress_callback(get_progress_info("Updating machines relations"));
     // Update machines with manufacturer_id
@@ -965,6 +1528,325 @@ fn create_relations(
          )",
         [],
     )?;
+    // Update slots with machine_id
+    conn.execute(
+        "UPDATE slots
+         SET machine_id = (
+             SELECT id
+             FROM machines
+             WHERE machines.name = slots.machine_name
+         )",
+        [],
+    )?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExtendedData;
+
+    #[test]
+    fn test_write_sqlite_streaming() {
+        let export_path = std::env::temp_dir().join("mame_parser_sqlite_streaming");
+        std::fs::create_dir_all(&export_path).unwrap();
+        let file_path = export_path.join("MAME 0.1.dat");
+        std::fs::write(
+            &file_path,
+            r#"<?xml version="1.0"?>
+<datafile>
+    <machine name="pacman">
+        <description>Pac-Man (Midway)</description>
+        <year>1980</year>
+        <manufacturer>Namco (Midway license)</manufacturer>
+    </machine>
+    <machine name="puckman" cloneof="pacman" romof="pacman">
+        <description>Puck Man (Japan set 1)</description>
+        <year>1980</year>
+        <manufacturer>Namco</manufacturer>
+    </machine>
+</datafile>"#,
+        )
+        .unwrap();
+        let data_base_path = export_path.join("machines.db");
+
+        let mut sink = write_sqlite_streaming(
+            data_base_path.to_str().unwrap(),
+            &WriteOptions::default(),
+            Box::new(|_| {}),
+        )
+        .unwrap();
+        let read_count = crate::readers::read_mame_file_streaming(
+            file_path.to_str().unwrap(),
+            |machine| sink.insert(&machine),
+            Box::new(|_| {}),
+        )
+        .unwrap();
+        assert_eq!(read_count, 2);
+        assert_eq!(sink.inserted_count(), 2);
+        sink.finish().unwrap();
+
+        let conn = Connection::open(&data_base_path).unwrap();
+        let manufacturers: Vec<String> = conn
+            .prepare("SELECT name FROM manufacturers ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(manufacturers, ["Namco"]);
+        let linked_machines: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM machines WHERE manufacturer_id IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(linked_machines, 2);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_sqlite_metadata() {
+        let machines: HashMap<String, Machine> =
+            [("pacman".to_string(), Machine::builder("pacman").build())]
+                .into_iter()
+                .collect();
+        let export_path = std::env::temp_dir().join("mame_parser_sqlite_metadata");
+        std::fs::create_dir_all(&export_path).unwrap();
+        let data_base_path = export_path.join("machines.db");
+        write_sqlite(
+            data_base_path.to_str().unwrap(),
+            &machines,
+            &WriteOptions::default(),
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        let conn = Connection::open(&data_base_path).unwrap();
+        let user_version: i32 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(user_version, SQLITE_SCHEMA_VERSION);
+        let crate_version: String = conn
+            .query_row(
+                "SELECT value FROM metadata WHERE key = 'crate_version'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(crate_version, env!("CARGO_PKG_VERSION"));
+        let exported_at: String = conn
+            .query_row(
+                "SELECT value FROM metadata WHERE key = 'exported_at'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(exported_at.ends_with('Z'));
+        drop(conn);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_sqlite_table_prefix() {
+        let machines: HashMap<String, Machine> =
+            [("pacman".to_string(), Machine::builder("pacman").build())]
+                .into_iter()
+                .collect();
+        let export_path = std::env::temp_dir().join("mame_parser_sqlite_table_prefix");
+        std::fs::create_dir_all(&export_path).unwrap();
+        let data_base_path = export_path.join("machines.db");
+        let options = WriteOptions {
+            table_prefix: Some("mame_".to_string()),
+            ..WriteOptions::default()
+        };
+        write_sqlite(
+            data_base_path.to_str().unwrap(),
+            &machines,
+            &options,
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        let conn = Connection::open(&data_base_path).unwrap();
+        let table_names = read_table_names(&conn).unwrap();
+        assert!(table_names.contains(&"mame_machines".to_string()));
+        assert!(table_names.contains(&"mame_metadata".to_string()));
+        assert!(table_names.iter().all(|table| table.starts_with("mame_")));
+
+        let name: String = conn
+            .query_row("SELECT name FROM mame_machines", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "pacman");
+        let referenced_table: String = conn
+            .query_row(
+                "SELECT \"table\" FROM pragma_foreign_key_list('mame_roms')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(referenced_table, "mame_machines");
+
+        drop(conn);
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_verify_sqlite_schema() {
+        let machines: HashMap<String, Machine> =
+            [("pacman".to_string(), Machine::builder("pacman").build())]
+                .into_iter()
+                .collect();
+        let export_path = std::env::temp_dir().join("mame_parser_sqlite_verify_schema");
+        std::fs::create_dir_all(&export_path).unwrap();
+        let data_base_path = export_path.join("machines.db");
+        write_sqlite(
+            data_base_path.to_str().unwrap(),
+            &machines,
+            &WriteOptions::default(),
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        let conn = Connection::open(&data_base_path).unwrap();
+        assert!(verify_sqlite_schema(&conn).is_ok());
+        drop(conn);
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE machines (id INTEGER PRIMARY KEY, name TEXT)",
+            [],
+        )
+        .unwrap();
+        match verify_sqlite_schema(&conn) {
+            Err(SchemaError::Mismatch {
+                missing_tables,
+                missing_columns,
+            }) => {
+                assert!(missing_tables.contains(&"roms".to_string()));
+                assert!(!missing_tables.contains(&"machines".to_string()));
+                assert!(missing_columns.contains(&("machines".to_string(), "year".to_string())));
+                assert!(!missing_columns.contains(&("machines".to_string(), "name".to_string())));
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_sqlite_stable_ids() {
+        let machines: HashMap<String, Machine> = ["pacman", "galaga", "dkong", "sf2", "mslug"]
+            .into_iter()
+            .map(|name| (name.to_string(), Machine::builder(name).build()))
+            .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_sqlite_stable_ids");
+        std::fs::create_dir_all(&export_path).unwrap();
+
+        let read_ids = |file_name: &str| -> Vec<(i64, String)> {
+            let data_base_path = export_path.join(file_name);
+            write_sqlite(
+                data_base_path.to_str().unwrap(),
+                &machines,
+                &WriteOptions {
+                    id_map: true,
+                    ..Default::default()
+                },
+                Box::new(|_| {}),
+            )
+            .unwrap();
+
+            let conn = Connection::open(&data_base_path).unwrap();
+            let mut stmt = conn
+                .prepare("SELECT id, name FROM machines ORDER BY id")
+                .unwrap();
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .unwrap();
+            rows.map(|row| row.unwrap()).collect()
+        };
+
+        let first = read_ids("first.db");
+        let conn = Connection::open(export_path.join("first.db")).unwrap();
+        let machine_ids = read_machine_ids(&conn).unwrap();
+        assert_eq!(machine_ids.len(), 5);
+        assert_eq!(machine_ids["dkong"], first[0].0);
+        let sidecar = std::fs::read_to_string(export_path.join("machine_ids.json")).unwrap();
+        let sidecar: HashMap<String, i64> = serde_json::from_str(&sidecar).unwrap();
+        assert_eq!(sidecar, machine_ids);
+
+        let second = read_ids("second.db");
+
+        assert_eq!(first, second);
+        let names: Vec<&str> = first.iter().map(|(_, name)| name.as_str()).collect();
+        assert_eq!(names, ["dkong", "galaga", "mslug", "pacman", "sf2"]);
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_sqlite_raw_manufacturers() {
+        let machine = |name: &str, manufacturer: &str| {
+            Machine::builder(name)
+                .manufacturer(manufacturer)
+                .extended_data(ExtendedData {
+                    manufacturer: Some("Namco".to_string()),
+                    ..Default::default()
+                })
+                .build()
+        };
+        let machines: HashMap<String, Machine> = [
+            machine("pacman", "Namco (Midway license)"),
+            machine("galaga", "Namco"),
+            machine("galagao", "Namco"),
+        ]
+        .into_iter()
+        .map(|machine| (machine.name.clone(), machine))
+        .collect();
+
+        let export_path = std::env::temp_dir().join("mame_parser_sqlite_raw_manufacturers");
+        std::fs::create_dir_all(&export_path).unwrap();
+        let data_base_path = export_path.join("machines.db");
+
+        write_sqlite(
+            data_base_path.to_str().unwrap(),
+            &machines,
+            &WriteOptions {
+                raw_manufacturers: true,
+                ..Default::default()
+            },
+            Box::new(|_| {}),
+        )
+        .unwrap();
+
+        let conn = Connection::open(&data_base_path).unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT raw_manufacturers.name, manufacturers.name
+                 FROM raw_manufacturers
+                 JOIN manufacturers ON manufacturers.id = raw_manufacturers.manufacturer_id
+                 ORDER BY raw_manufacturers.name",
+            )
+            .unwrap();
+        let rows: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+
+        assert_eq!(
+            rows,
+            [
+                ("Namco".to_string(), "Namco".to_string()),
+                ("Namco (Midway license)".to_string(), "Namco".to_string()),
+            ]
+        );
+
+        std::fs::remove_dir_all(&export_path).unwrap();
+    }
+}
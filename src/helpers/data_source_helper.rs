@@ -1,8 +1,25 @@
 use reqwest::blocking::Client;
 use scraper::{Html, Selector};
+use std::cmp::Ordering;
 use std::error::Error;
+use std::path::Path;
 use url::Url;
 
+/// Creates the HTTP client used to fetch the data source pages and to download the data files.
+///
+/// The client explicitly enables the `gzip` and `deflate` decompression of reqwest: a response with a
+/// `Content-Encoding: gzip` or `deflate` header, as sent by some mirrors on top of an already-compressed archive,
+/// is transparently decoded, so the downloaded bytes are the raw archive as served, not a doubly-compressed file.
+///
+/// # Returns
+/// Returns a `Result<Client, Box<dyn Error + Send + Sync>>`:
+/// - On success: Contains the configured blocking `Client`.
+/// - On failure: Contains an error if the client cannot be initialized (e.g. its TLS backend).
+pub(crate) fn http_client() -> Result<Client, Box<dyn Error + Send + Sync>> {
+    let client = Client::builder().gzip(true).deflate(true).build()?;
+    Ok(client)
+}
+
 /// Extracts the data source URL from a given web page based on a matching string.
 ///
 /// This function downloads the HTML of the provided URL, parses it to find an anchor (`<a>`) tag
@@ -31,7 +48,7 @@ pub(crate) fn get_data_source(
     matching: &str,
 ) -> Result<String, Box<dyn Error + Send + Sync>> {
     // Download the HTML
-    let client = Client::new();
+    let client = http_client()?;
     let response = client.get(url).send()?;
     let body = response.text()?;
 
@@ -88,6 +105,74 @@ pub(crate) fn get_file_name_from_url(url: &str) -> String {
     file_name.to_string()
 }
 
+/// Extracts the version of a data file from its archive file name.
+///
+/// The version is the last run of digits of the file name, without its extension (e.g., `271` for
+/// `MAME_Dats_271.7z` or `pS_AllProject_20241030_271_(mame).zip`).
+///
+/// # Parameters
+/// - `file_name`: A string slice (`&str`) representing the archive file name.
+///
+/// # Returns
+/// Returns an `Option<String>` with the version, or `None` if the file name contains no digits.
+///
+pub(crate) fn get_version_from_file_name(file_name: &str) -> Option<String> {
+    let stem = Path::new(file_name).file_stem()?.to_str()?;
+
+    stem.split(|c: char| !c.is_ascii_digit())
+        .rfind(|digits| !digits.is_empty())
+        .map(str::to_string)
+}
+
+/// Compares two versions extracted by `get_version_from_file_name`.
+///
+/// # Returns
+/// Returns `true` if `version` is numerically greater than `current_version`.
+///
+pub(crate) fn is_newer_version(version: &str, current_version: &str) -> bool {
+    compare_versions(version, current_version) == Ordering::Greater
+}
+
+/// Compares two versions by their numeric components.
+///
+/// The runs of digits of each version are compared one by one as numbers, so `0.100` is newer than `0.99`
+/// and `0271` is the same version as `271`. Missing trailing components count as `0`. Versions without
+/// any digit are compared as strings.
+///
+/// # Parameters
+/// - `version`: The first version.
+/// - `other_version`: The second version.
+///
+/// # Returns
+/// Returns the `Ordering` of `version` relative to `other_version`.
+///
+pub(crate) fn compare_versions(version: &str, other_version: &str) -> Ordering {
+    let components = version_components(version);
+    let other_components = version_components(other_version);
+    if components.is_empty() && other_components.is_empty() {
+        return version.cmp(other_version);
+    }
+
+    (0..components.len().max(other_components.len()))
+        .map(|i| {
+            let component = components.get(i).copied().unwrap_or("");
+            let other_component = other_components.get(i).copied().unwrap_or("");
+            // Without their leading zeros, the longer run of digits is the larger number
+            (component.len(), component).cmp(&(other_component.len(), other_component))
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Splits a version into its runs of digits, without their leading zeros.
+fn version_components(version: &str) -> Vec<&str> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|digits| !digits.is_empty())
+        .map(|digits| digits.trim_start_matches('0'))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +243,34 @@ mod tests {
         let result = get_file_name_from_url(url);
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_get_version_from_file_name() {
+        assert_eq!(
+            get_version_from_file_name("MAME_Dats_271.7z"),
+            Some("271".to_string())
+        );
+        assert_eq!(
+            get_version_from_file_name("nplayers0271.zip"),
+            Some("0271".to_string())
+        );
+        assert_eq!(
+            get_version_from_file_name("pS_AllProject_20241030_271_(mame).zip"),
+            Some("271".to_string())
+        );
+        assert_eq!(get_version_from_file_name("catver.ini"), None);
+
+        assert!(is_newer_version("272", "0271"));
+        assert!(!is_newer_version("0271", "271"));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert!(is_newer_version("0.100", "0.99"));
+        assert!(!is_newer_version("0.99", "0.100"));
+        assert!(is_newer_version("0.250.1", "0.250"));
+        assert_eq!(compare_versions("0.250.0", "0.250"), Ordering::Equal);
+        assert_eq!(compare_versions("1000", "999"), Ordering::Greater);
+        assert_eq!(compare_versions("beta", "alpha"), Ordering::Greater);
+    }
 }
@@ -1,8 +1,18 @@
+use flate2::read::GzDecoder;
 use std::error::Error;
-use std::fs::{self};
-use std::io::{self};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Cursor, Read};
 use std::path::Path;
 
+/// The default size of the read buffers of the readers, in bytes.
+pub(crate) const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// The minimum size of the read buffers of the readers, in bytes.
+///
+/// Smaller buffers break the readers: an empty buffer is read as the end of the file, and the byte order mark
+/// of a file must fit in the first buffer to be skipped.
+pub(crate) const MIN_BUFFER_SIZE: usize = 1024;
+
 /// Ensures that the specified folder exists, creating it if necessary.
 ///
 /// This function checks whether the provided path exists, and if it does not, attempts to create
@@ -31,6 +41,132 @@ pub(crate) fn ensure_folder_exists(path: &Path) -> io::Result<()> {
     Ok(())
 }
 
+/// Opens a text file for buffered reading, transparently decompressing it if it is gzipped.
+///
+/// A file is considered gzipped if its name ends with `.gz` or if it starts with the gzip magic bytes
+/// (`1f 8b`). Plain files are read as they are. A leading byte order mark is removed from the content,
+/// and UTF-16 content (detected by its byte order mark) is decoded, so the reader always yields UTF-8.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to open.
+///
+/// # Returns
+/// Returns an `io::Result<Box<dyn BufRead>>`:
+/// - On success: A buffered reader over the UTF-8 content of the file, decompressed if needed.
+/// - On failure: An `io::Error` if the file cannot be opened or read.
+///
+pub(crate) fn open_text_file(file_path: &str) -> io::Result<Box<dyn BufRead>> {
+    open_text_file_with_capacity(file_path, DEFAULT_BUFFER_SIZE)
+}
+
+/// Opens a text file for buffered reading like `open_text_file`, using buffers of the given capacity.
+///
+/// # Parameters
+/// - `file_path`: A `&str` representing the path to the file to open.
+/// - `capacity`: The capacity of the read buffers, in bytes.
+///
+/// # Returns
+/// Returns an `io::Result<Box<dyn BufRead>>` like `open_text_file`.
+///
+pub(crate) fn open_text_file_with_capacity(
+    file_path: &str,
+    capacity: usize,
+) -> io::Result<Box<dyn BufRead>> {
+    let mut reader = BufReader::with_capacity(capacity, File::open(file_path)?);
+
+    let is_gzipped =
+        file_path.to_lowercase().ends_with(".gz") || reader.fill_buf()?.starts_with(&[0x1f, 0x8b]);
+
+    if is_gzipped {
+        skip_bom(Box::new(BufReader::with_capacity(
+            capacity,
+            GzDecoder::new(reader),
+        )))
+    } else {
+        skip_bom(Box::new(reader))
+    }
+}
+
+/// Reads the whole content of a reader as text, like `open_text_file` does for a file.
+///
+/// The content is decompressed if it is gzipped, and its byte order mark is removed, decoding it if it is UTF-16.
+///
+/// # Parameters
+/// - `reader`: The reader positioned at the start of the content.
+///
+/// # Returns
+/// Returns an `io::Result<String>`:
+/// - On success: The decoded text content.
+/// - On failure: An `io::Error` if the content cannot be read, decompressed, or is not valid UTF-8.
+///
+pub(crate) fn read_text(mut reader: impl Read) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let reader: Box<dyn BufRead> = if bytes.starts_with(&[0x1f, 0x8b]) {
+        Box::new(BufReader::new(GzDecoder::new(Cursor::new(bytes))))
+    } else {
+        Box::new(Cursor::new(bytes))
+    };
+
+    let mut text = String::new();
+    skip_bom(reader)?.read_to_string(&mut text)?;
+
+    Ok(text)
+}
+
+/// Removes the byte order mark at the start of a text, as `read_text` does for a reader.
+///
+/// # Parameters
+/// - `contents`: The text, as passed to the `*_from_str` readers.
+///
+/// # Returns
+/// Returns the text without its leading U+FEFF character, if any.
+///
+pub(crate) fn strip_bom(contents: &str) -> &str {
+    contents.strip_prefix('\u{feff}').unwrap_or(contents)
+}
+
+/// Removes the byte order mark at the start of a reader, decoding the content if it is UTF-16.
+///
+/// # Parameters
+/// - `reader`: The buffered reader positioned at the start of the content.
+///
+/// # Returns
+/// Returns an `io::Result<Box<dyn BufRead>>`:
+/// - On success: The same reader past the UTF-8 byte order mark, or a reader over the decoded content
+///   if it starts with a UTF-16 byte order mark. Content without a byte order mark is returned as it is.
+/// - On failure: An `io::Error` if the content cannot be read.
+///
+fn skip_bom(mut reader: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+    let start = reader.fill_buf()?;
+
+    if start.starts_with(&[0xef, 0xbb, 0xbf]) {
+        reader.consume(3);
+        return Ok(reader);
+    }
+
+    let from_bytes: fn([u8; 2]) -> u16 = if start.starts_with(&[0xff, 0xfe]) {
+        u16::from_le_bytes
+    } else if start.starts_with(&[0xfe, 0xff]) {
+        u16::from_be_bytes
+    } else {
+        return Ok(reader);
+    };
+
+    reader.consume(2);
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+
+    Ok(Box::new(Cursor::new(
+        String::from_utf16_lossy(&units).into_bytes(),
+    )))
+}
+
 /// Searches for a file within a specified folder that matches a given regex pattern.
 ///
 /// This function recursively walks through the specified folder, looking for a file name that matches
@@ -74,14 +210,84 @@ pub(crate) fn find_file_with_pattern(
     Err(error_message.into())
 }
 
-pub(crate) struct WorkspacePaths {
+/// Finds every file in a folder and its subfolders whose name matches a regex pattern.
+///
+/// # Parameters
+/// - `folder`: A string slice (`&str`) representing the path to the folder to search.
+/// - `pattern`: A reference to a `Regex` matched against the file names.
+///
+/// # Returns
+/// Returns a `Vec<String>` with the paths of the matching files, sorted. The vector is empty if no file matches.
+pub(crate) fn find_files_with_pattern(folder: &str, pattern: &regex::Regex) -> Vec<String> {
+    let mut file_paths: Vec<String> = walkdir::WalkDir::new(folder)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|file_name| pattern.is_match(file_name))
+        })
+        .map(|entry| entry.path().to_string_lossy().into_owned())
+        .collect();
+    file_paths.sort();
+
+    file_paths
+}
+
+/// Represents the names of the default subfolders used inside the workspace.
+///
+/// # Fields
+/// - `download_path`: The subfolder where the archives are downloaded.
+/// - `extract_path`: The subfolder where the archives are unpacked, one folder per data type.
+/// - `export_path`: The subfolder where the data is exported, one folder per export file type.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkspacePaths {
+    /// The subfolder where the archives are downloaded.
     pub download_path: &'static str,
+    /// The subfolder where the archives are unpacked.
     pub extract_path: &'static str,
+    /// The subfolder where the data is exported.
     pub export_path: &'static str,
 }
 
-pub(crate) const WORKSPACE_PATHS: WorkspacePaths = WorkspacePaths {
+/// The default subfolders used inside the workspace, also used by `WorkspaceLayout::default`.
+pub const WORKSPACE_PATHS: WorkspacePaths = WorkspacePaths {
     download_path: "downloads",
     extract_path: "extracted",
     export_path: "export",
 };
+
+/// Represents the names of the subfolders used inside the workspace.
+///
+/// The `WorkspaceLayout` struct allows overriding the subfolders where the files are downloaded,
+/// unpacked and exported, relative to the workspace path. The default layout uses the `downloads`,
+/// `extracted` and `export` subfolders.
+///
+/// # Fields
+/// - `download_path`: The subfolder where the archives are downloaded.
+/// - `extract_path`: The subfolder where the archives are unpacked, one folder per data type.
+/// - `export_path`: The subfolder where the data is exported, one folder per export file type.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceLayout {
+    /// The subfolder where the archives are downloaded.
+    pub download_path: String,
+    /// The subfolder where the archives are unpacked.
+    pub extract_path: String,
+    /// The subfolder where the data is exported.
+    pub export_path: String,
+}
+
+impl Default for WorkspaceLayout {
+    fn default() -> Self {
+        WorkspaceLayout {
+            download_path: WORKSPACE_PATHS.download_path.to_string(),
+            extract_path: WORKSPACE_PATHS.extract_path.to_string(),
+            export_path: WORKSPACE_PATHS.export_path.to_string(),
+        }
+    }
+}
@@ -16,6 +16,7 @@
 //! * [`Progress tracking`](progress) - Contains tools and types for tracking and managing progress updates during operations.
 //! * [`Crate models`](models) - Defines data types and models used for representing MAME data.
 //! * [`Mame files readers`](readers) - Contains functions for reading and parsing different MAME data file formats.
+//! * [`Queries`](queries) - Contains functions to analyze the relationships between machines, to sort them and to compute statistics.
 //!
 //! # Examples
 //!
@@ -29,21 +30,75 @@ mod helpers;
 pub use core::models::callback_progress as progress;
 /// Management of MAME data files, including downloading, reading, and unpacking.
 pub mod file_handling {
+    pub use crate::core::data_cleanup::machine_enrichment::{
+        attach_alternate_titles, attach_catver, attach_history, attach_languages, attach_nplayers,
+        attach_resources, attach_series, attach_software_titles, reclassify_categories,
+        recompute_parent_flags,
+    };
     pub use crate::core::data_cleanup::machine_filtering::{
-        remove_machines_by_category, remove_machines_by_filter,
+        count_matching_category, count_matching_filter, keep_machines_by_category,
+        keep_machines_by_source_file, keep_machines_in_set, remove_machines_by_category,
+        remove_machines_by_filter, remove_machines_in_set, remove_test_machines,
+        remove_test_machines_with, remove_undumped_machines, DEFAULT_TEST_KEYWORDS,
+        DEFAULT_TEST_SOURCE_FILES,
+    };
+    pub use crate::core::file_handling::export_verification::verify_export;
+    pub use crate::core::file_handling::file_downloader::{
+        download_file, download_file_with_options, download_files, download_files_with_options,
+        download_to_writer, download_to_writer_with_options, is_update_available,
+        is_update_available_with_options,
+    };
+    pub use crate::core::file_handling::file_reader::{
+        estimate_total_entries, estimate_total_entries_with_options, read_file, read_file_timed,
+        read_file_with_options, read_files, read_files_sequential,
+        read_files_sequential_with_options, read_files_timed, read_files_with_errors,
+        read_files_with_options, ReadDurations, ReadErrors, ReadOutcome,
+    };
+    pub use crate::core::file_handling::file_unpacker::{
+        unpack_file, unpack_file_with_options, unpack_files, unpack_files_with_options,
+    };
+    pub use crate::core::file_handling::file_writer::{
+        write_files, write_files_multi, write_files_multi_with_options, write_files_with_options,
+        write_index,
+    };
+    pub use crate::core::file_handling::workspace_status::{
+        data_file_path, data_file_path_with_layout, workspace_status, workspace_status_with_layout,
     };
-    pub use crate::core::file_handling::file_downloader::{download_file, download_files};
-    pub use crate::core::file_handling::file_reader::{read_file, read_files};
-    pub use crate::core::file_handling::file_unpacker::{unpack_file, unpack_files};
-    pub use crate::core::file_handling::file_writer::write_files;
+    pub use crate::core::models::cancellation::join_all_or_cancel;
+    pub use crate::core::writers::json_writer::{
+        machines_to_value, write_charts_json, write_collections_json, write_json_by_decade,
+        write_json_normalized, write_resource_manifest,
+    };
+    pub use crate::core::writers::sqlite_writer::{
+        verify_sqlite_schema, write_sqlite_streaming, SQLITE_SCHEMA_VERSION,
+    };
+    pub use crate::helpers::file_system_helpers::WORKSPACE_PATHS;
 }
 /// Data models and types used for MAME data processing.
 pub mod models {
+    pub use crate::core::data_cleanup::filter_pipeline::FilterPipeline;
     pub use crate::core::data_cleanup::machine_filtering::Category;
     pub use crate::core::data_cleanup::machine_filtering::MachineFilter;
-    pub use crate::core::file_handling::file_writer::ExportFileType;
+    pub use crate::core::data_cleanup::name_normalization::{
+        extract_alternate_titles, normalize_hash, parse_year, HashKind, YearValue,
+    };
+    pub use crate::core::file_handling::export_verification::Discrepancy;
+    pub use crate::core::file_handling::file_downloader::{DownloadOptions, SourceOverride};
+    pub use crate::core::file_handling::file_reader::{ErrorPolicy, ReadOptions};
+    pub use crate::core::file_handling::file_unpacker::UnpackOptions;
+    pub use crate::core::file_handling::file_writer::{
+        BoolEncoding, ExportFileType, ResourcePaths, WriteOptions,
+    };
+    pub use crate::core::file_handling::workspace_status::DataTypeStatus;
+    pub use crate::core::models::cancellation::CancelFlag;
+    pub use crate::core::models::collections_helper::SeriesRules;
     pub use crate::core::models::core_models::*;
     pub use crate::core::models::mame_data_types::MameDataType;
+    pub use crate::core::queries::dataset_stats::DatasetStats;
+    pub use crate::core::queries::distinct_values::MachineField;
+    pub use crate::core::queries::parent_relationships::ParentKind;
+    pub use crate::core::writers::sqlite_writer::{SchemaError, SqliteMachineSink};
+    pub use crate::helpers::file_system_helpers::{WorkspaceLayout, WorkspacePaths};
 
     pub mod collections {
         pub use crate::core::models::collections_helper::get_categories_list;
@@ -51,17 +106,52 @@ pub mod models {
         pub use crate::core::models::collections_helper::get_manufacturers_list;
         pub use crate::core::models::collections_helper::get_players_list;
         pub use crate::core::models::collections_helper::get_series_list;
+        pub use crate::core::models::collections_helper::get_source_files_list;
         pub use crate::core::models::collections_helper::get_subcategories_list;
+        pub use crate::core::models::collections_helper::group_series_by_base;
     }
 }
 
+/// Queries to analyze the relationships between machines.
+pub mod queries {
+    pub use crate::core::queries::dataset_stats::dataset_stats;
+    pub use crate::core::queries::device_refs::find_missing_device_refs;
+    pub use crate::core::queries::distinct_values::distinct_values;
+    pub use crate::core::queries::machine_tags::{build_tag_index, machine_tags};
+    pub use crate::core::queries::parent_relationships::classify_parent_relationship;
+    pub use crate::core::queries::partition::partition_by;
+    pub use crate::core::queries::rom_sets::build_rom_sets;
+    pub use crate::core::queries::sorting::{
+        machines_sorted, machines_sorted_by_manufacturer, machines_sorted_by_year,
+    };
+}
+
 /// Module for reading and parsing MAME data files.
 pub mod readers {
-    pub use crate::core::readers::catver_reader::read_catver_file;
-    pub use crate::core::readers::history_reader::read_history_file;
-    pub use crate::core::readers::languages_reader::read_languages_file;
-    pub use crate::core::readers::mame_reader::read_mame_file;
-    pub use crate::core::readers::nplayers_reader::read_nplayers_file;
-    pub use crate::core::readers::resources_reader::read_resources_file;
-    pub use crate::core::readers::series_reader::read_series_file;
+    pub use crate::core::readers::catver_reader::{
+        read_catver_file, read_catver_from_reader, read_catver_from_str,
+    };
+    pub use crate::core::readers::history_reader::{
+        read_history_file, read_history_from_reader, read_history_from_str,
+    };
+    pub use crate::core::readers::languages_reader::{
+        read_languages_file, read_languages_from_reader, read_languages_from_str,
+    };
+    pub use crate::core::readers::mame_reader::{
+        read_machine_names, read_mame_file, read_mame_file_filtered, read_mame_file_streaming,
+        read_mame_from_reader, read_mame_from_str,
+    };
+    pub use crate::core::readers::nplayers_reader::{
+        read_nplayers_file, read_nplayers_from_reader, read_nplayers_from_str,
+    };
+    pub use crate::core::readers::resources_reader::{
+        read_resources_file, read_resources_file_filtered, read_resources_from_reader,
+        read_resources_from_str,
+    };
+    pub use crate::core::readers::series_reader::{
+        read_series_file, read_series_from_reader, read_series_from_str,
+    };
+    pub use crate::core::readers::software_list_reader::{
+        read_software_list_file, read_software_list_from_reader, read_software_list_from_str,
+    };
 }